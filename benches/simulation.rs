@@ -0,0 +1,91 @@
+//! Benchmarks of whole simulations of large generated workloads.
+//!
+//! Run with `cargo bench`; each size is simulated to completion under FCFS
+//! and the default MLFQ, with a process arriving every time unit so the ready
+//! queues hold most of the workload at once.
+
+use std::collections::VecDeque;
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use mlfq_scheduler_simulator::{
+	algorithms::{FirstComeFirstServe, MultiLevelFeedbackQueue},
+	engine::Engine,
+	process::Process,
+	rng::Rng,
+	time::SimTime,
+	workload::Workload,
+};
+
+// Generates processes of one to four bursts, one arriving every time unit.
+fn processes(count: u32) -> Vec<Process> {
+	let mut rng = Rng::new(1);
+	let mut workload = Workload::new();
+
+	for arrival in 0..count {
+		let bursts = rng.below(4) + 1;
+		let cpu_times: Vec<u64> = (0..bursts).map(|_| rng.below(20) + 1).collect();
+		let io_times: Vec<u64> = (1..bursts).map(|_| rng.below(50)).collect();
+
+		workload
+			.process(io_times, cpu_times)
+			.arrival_time(SimTime::new(u64::from(arrival)))
+			.add();
+	}
+
+	workload.into_processes()
+}
+
+fn first_come_first_serve(criterion: &mut Criterion) {
+	let mut group = criterion.benchmark_group("fcfs");
+
+	group.sample_size(10);
+
+	for count in [10_000, 100_000, 1_000_000] {
+		let processes = processes(count);
+
+		group.bench_with_input(
+			BenchmarkId::from_parameter(count),
+			&processes,
+			|bencher, processes| {
+				bencher.iter_batched(
+					|| Engine::new(FirstComeFirstServe::from_processes(processes.clone())),
+					|mut engine| engine.run(),
+					BatchSize::LargeInput,
+				);
+			},
+		);
+	}
+
+	group.finish();
+}
+
+fn multi_level_feedback_queue(criterion: &mut Criterion) {
+	let mut group = criterion.benchmark_group("mlfq");
+
+	group.sample_size(10);
+
+	for count in [10_000, 100_000, 1_000_000] {
+		let processes = processes(count);
+
+		group.bench_with_input(
+			BenchmarkId::from_parameter(count),
+			&processes,
+			|bencher, processes| {
+				bencher.iter_batched(
+					|| {
+						Engine::new(MultiLevelFeedbackQueue::from_processes(VecDeque::from(
+							processes.clone(),
+						)))
+					},
+					|mut engine| engine.run(),
+					BatchSize::LargeInput,
+				);
+			},
+		);
+	}
+
+	group.finish();
+}
+
+criterion_group!(benches, first_come_first_serve, multi_level_feedback_queue);
+criterion_main!(benches);
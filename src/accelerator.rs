@@ -0,0 +1,221 @@
+//! Accelerators, such as GPUs, as a second kind of resource besides the CPUs.
+//!
+//! A process can follow any CPU burst but its last with an accelerator burst,
+//! which runs before the I/O of that gap. Without an [`Accelerator`] every
+//! accelerator burst runs right away, as if each process had a unit to
+//! itself. With one, the bursts share its units through a single queue, served
+//! in the order of its own policy, independently of the CPU scheduler.
+
+use alloc::{vec, vec::Vec};
+use core::{fmt, str::FromStr};
+
+use crate::{process::Process, stats::OnlineStats, time::SimTime};
+
+/// The order the accelerator serves its queue in.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum AcceleratorPolicy {
+	FirstComeFirstServe,
+	/// Serves the shortest queued burst first.
+	ShortestJobFirst,
+}
+
+/// The error returned when an accelerator policy cannot be parsed.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseAcceleratorPolicyError;
+
+impl fmt::Display for ParseAcceleratorPolicyError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str("expected fcfs or sjf")
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseAcceleratorPolicyError {}
+
+impl FromStr for AcceleratorPolicy {
+	type Err = ParseAcceleratorPolicyError;
+
+	fn from_str(text: &str) -> Result<Self, Self::Err> {
+		match text {
+			"fcfs" => Ok(Self::FirstComeFirstServe),
+			"sjf" => Ok(Self::ShortestJobFirst),
+			_ => Err(ParseAcceleratorPolicyError),
+		}
+	}
+}
+
+/// An accelerator burst waiting for a unit.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Job {
+	process: Process,
+	issued_at: SimTime,
+	length: SimTime,
+	/// The I/O the process does once the burst is over.
+	io_time: SimTime,
+	// Breaks ties between jobs issued at the same time.
+	sequence: u64,
+}
+
+/// How much an accelerator was used over a run.
+#[derive(Clone, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AcceleratorSummary {
+	pub units: usize,
+	/// The number of accelerator bursts that have started.
+	pub burst_count: u32,
+	pub busy_time: SimTime,
+	/// The time the started bursts spent queued for a unit.
+	pub total_waiting_time: SimTime,
+	/// The statistics of the time each started burst spent queued.
+	pub waiting: OnlineStats,
+}
+
+impl AcceleratorSummary {
+	// Returns the share of the capacity of the units that was used until the given time, as a percentage.
+	pub fn utilization(&self, total_time: SimTime) -> f64 {
+		self.busy_time.as_f64() / (total_time.as_f64() * self.units as f64) * 100.0
+	}
+
+	pub fn waiting_average(&self) -> f64 {
+		self.waiting.mean()
+	}
+}
+
+/// A pool of identical accelerator units shared by every process.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Accelerator {
+	/// The time each unit is free from.
+	units: Vec<SimTime>,
+	queue: Vec<Job>,
+	policy: AcceleratorPolicy,
+	sequence: u64,
+	summary: AcceleratorSummary,
+}
+
+impl Accelerator {
+	pub fn new(count: usize, policy: AcceleratorPolicy) -> Self {
+		let count = count.max(1);
+
+		Self {
+			units: vec![SimTime::ZERO; count],
+			queue: Vec::new(),
+			policy,
+			sequence: 0,
+			summary: AcceleratorSummary {
+				units: count,
+				..AcceleratorSummary::default()
+			},
+		}
+	}
+
+	pub fn unit_count(&self) -> usize {
+		self.units.len()
+	}
+
+	// Returns whether no burst is waiting for a unit.
+	pub fn is_empty(&self) -> bool {
+		self.queue.is_empty()
+	}
+
+	pub fn summary(&self) -> &AcceleratorSummary {
+		&self.summary
+	}
+
+	// Returns the waiting processes with the length of their accelerator burst.
+	pub fn queue(&self) -> Vec<(u32, SimTime)> {
+		self.queue
+			.iter()
+			.map(|job| (job.process.id, job.length))
+			.collect()
+	}
+
+	// Queues an accelerator burst of the process, followed by the given I/O once it is over.
+	pub fn submit(
+		&mut self,
+		process: Process,
+		issued_at: SimTime,
+		length: SimTime,
+		io_time: SimTime,
+	) {
+		self.sequence += 1;
+
+		tracing::debug!(
+			target: "accelerator",
+			process = process.id,
+			time = %issued_at,
+			length = %length,
+			"queued"
+		);
+
+		self.queue.push(Job {
+			process,
+			issued_at,
+			length,
+			io_time,
+			sequence: self.sequence,
+		});
+	}
+
+	// Returns the earliest time a queued burst can start.
+	pub fn next_start(&self) -> Option<SimTime> {
+		let issued_at = self.queue.iter().map(|job| job.issued_at).min()?;
+		let free_at = self.units.iter().copied().min()?;
+
+		Some(issued_at.max(free_at))
+	}
+
+	// Starts the burst that can start first on the unit free first.
+	//
+	// Returns its process with the time the burst ends and the I/O it does after.
+	pub fn dispatch(&mut self) -> Option<(Process, SimTime, SimTime)> {
+		let start = self.next_start()?;
+		let unit = (0..self.units.len()).min_by_key(|&unit| self.units[unit])?;
+
+		// Choose among the bursts that have been issued by the time the unit starts.
+		let policy = self.policy;
+		let index = self
+			.queue
+			.iter()
+			.enumerate()
+			.filter(|(_, job)| job.issued_at <= start)
+			.min_by_key(|(_, job)| {
+				let order = match policy {
+					AcceleratorPolicy::FirstComeFirstServe => SimTime::ZERO,
+					AcceleratorPolicy::ShortestJobFirst => job.length,
+				};
+
+				(order, job.issued_at, job.sequence)
+			})
+			.map(|(index, _)| index)?;
+
+		let job = self.queue.remove(index);
+		let end = start + job.length;
+
+		tracing::debug!(
+			target: "accelerator",
+			process = job.process.id,
+			unit = unit + 1,
+			start = %start,
+			end = %end,
+			waited = %(start - job.issued_at),
+			"started"
+		);
+
+		self.units[unit] = end;
+		self.summary.burst_count += 1;
+		self.summary.busy_time += job.length;
+		self.summary.total_waiting_time += start - job.issued_at;
+		self.summary.waiting.add((start - job.issued_at).as_f64());
+
+		// The I/O time of the gap was already counted when the CPU burst ended.
+		let mut process = job.process;
+
+		process.turnaround_time += end - job.issued_at;
+
+		Some((process, end, job.io_time))
+	}
+}
@@ -0,0 +1,162 @@
+use alloc::{boxed::Box, vec, vec::Vec};
+
+use crate::{
+	process::Process,
+	scheduler::{Data, Event, Response, Scheduler},
+	time::SimTime,
+};
+
+/// Runs the arrived process with the earliest deadline, preempting it when a
+/// process with an earlier one arrives.
+///
+/// Processes without a deadline or period run only when no process with one
+/// is ready, in arrival order.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EarliestDeadlineFirst {
+	processes: Vec<Process>,
+}
+
+// Returns the deadline a process is scheduled by, where processes without one come last.
+fn deadline(process: &Process) -> SimTime {
+	process.deadline().unwrap_or(SimTime::MAX)
+}
+
+impl EarliestDeadlineFirst {
+	// Creates a new scheduler from a list of processes.
+	pub fn from_processes(processes: Vec<Process>) -> Self {
+		Self { processes }
+	}
+
+	fn find_next_process(&self, current_time: SimTime) -> usize {
+		// Find the arrived process with the earliest deadline, breaking ties by arrival.
+		let chosen = self
+			.processes
+			.iter()
+			.enumerate()
+			.filter(|(_, process)| process.next_arrival <= current_time)
+			.min_by_key(|(_, process)| (deadline(process), process.next_arrival));
+
+		// If no process has arrived, wait for the next one.
+		let chosen = chosen.or_else(|| {
+			self.processes
+				.iter()
+				.enumerate()
+				.min_by_key(|(_, process)| (process.next_arrival, deadline(process)))
+		});
+
+		chosen.map(|(index, _)| index).unwrap_or_default()
+	}
+
+	// Returns how long the process may run before a process with an earlier deadline arrives.
+	fn time_until_preempted(&self, index: usize, start: SimTime, cpu_time: SimTime) -> SimTime {
+		let current = deadline(&self.processes[index]);
+
+		self.processes
+			.iter()
+			.enumerate()
+			.filter(|&(other, process)| {
+				other != index
+					&& deadline(process) < current
+					&& process.next_arrival > start
+					&& process.next_arrival < start + cpu_time
+			})
+			.map(|(_, process)| process.next_arrival - start)
+			.min()
+			.unwrap_or(cpu_time)
+	}
+}
+
+impl Scheduler for EarliestDeadlineFirst {
+	fn push(&mut self, process: Process) {
+		self.processes.push(process);
+	}
+
+	fn drain(&mut self) -> Vec<Process> {
+		self.processes.drain(..).collect()
+	}
+
+	fn remove(&mut self, id: u32) -> Option<Process> {
+		let index = self.processes.iter().position(|process| process.id == id)?;
+
+		Some(self.processes.remove(index))
+	}
+
+	fn processes(&self) -> Box<dyn Iterator<Item = &Process> + '_> {
+		Box::new(self.processes.iter())
+	}
+
+	fn is_empty(&self) -> bool {
+		self.processes.is_empty()
+	}
+
+	fn preempts_on_arrival(&self) -> bool {
+		true
+	}
+
+	fn step(&mut self, current_time: SimTime) -> Data {
+		let process_index = self.find_next_process(current_time);
+
+		let (idle_time, cpu_time) = {
+			let process = &self.processes[process_index];
+
+			(
+				process.next_arrival.saturating_sub(current_time),
+				process.cpu_times.front().copied().unwrap(),
+			)
+		};
+
+		let start_time = current_time + idle_time;
+		let run_time = self.time_until_preempted(process_index, start_time, cpu_time);
+
+		let process = &mut self.processes[process_index];
+
+		// If the process has not arrived yet, wait until it does.
+		let waiting_time = current_time.saturating_sub(process.next_arrival);
+
+		let process_id = process.id;
+		let start = Event::Start {
+			id: process_id,
+			time: start_time,
+		};
+
+		process.cpu_times.pop_front();
+
+		// If a process with an earlier deadline arrives first, keep the rest of the burst for later.
+		let io_time = if run_time < cpu_time {
+			process.cpu_times.push_front(cpu_time - run_time);
+
+			SimTime::ZERO
+		} else {
+			process.io_times.pop_front().unwrap_or_default()
+		};
+
+		// Update the process's metrics.
+		process.next_arrival = run_time + io_time + start_time;
+		process.record_wait(waiting_time);
+		process.turnaround_time += run_time + io_time + waiting_time;
+		process.record_start(start_time);
+		process.record_run(run_time, start_time + run_time);
+
+		if run_time == cpu_time {
+			process.complete_burst(start_time + run_time);
+		}
+
+		// If the process has no more CPU times, remove it from the list.
+		let response = if process.cpu_times.is_empty() {
+			let process = self.processes.remove(process_index);
+
+			Response::Success(process)
+		} else {
+			Response::Empty
+		};
+
+		Data {
+			process_id,
+			cpu_time: run_time,
+			idle_time,
+			response,
+			events: vec![start],
+		}
+	}
+}
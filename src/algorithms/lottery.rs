@@ -0,0 +1,149 @@
+use alloc::{boxed::Box, vec, vec::Vec};
+
+use crate::{
+	process::Process,
+	rng::Rng,
+	scheduler::{Data, Event, Response, Scheduler},
+	time::SimTime,
+};
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Lottery {
+	processes: Vec<Process>,
+	quanta: SimTime,
+	rng: Rng,
+}
+
+impl Lottery {
+	// Creates a new scheduler from a list of processes, drawing tickets with the seed.
+	pub fn from_processes(processes: Vec<Process>, quanta: SimTime, seed: u64) -> Self {
+		Self {
+			processes,
+			quanta,
+			rng: Rng::new(seed),
+		}
+	}
+
+	fn find_next_process(&mut self, current_time: SimTime) -> usize {
+		let total_tickets: u64 = self
+			.processes
+			.iter()
+			.filter(|process| process.next_arrival <= current_time)
+			.map(|process| u64::from(process.tickets))
+			.sum();
+
+		// If no process has arrived, wait for the next one.
+		if total_tickets == 0 {
+			return self
+				.processes
+				.iter()
+				.enumerate()
+				.min_by_key(|(_, process)| process.next_arrival)
+				.map(|(index, _)| index)
+				.unwrap_or_default();
+		}
+
+		// Draw the winning ticket and find the arrived process holding it.
+		let mut winner = self.rng.below(total_tickets);
+
+		for (index, process) in self.processes.iter().enumerate() {
+			if process.next_arrival > current_time {
+				continue;
+			}
+
+			let tickets = u64::from(process.tickets);
+
+			if winner < tickets {
+				return index;
+			}
+
+			winner -= tickets;
+		}
+
+		unreachable!("the winning ticket is always held by an arrived process")
+	}
+}
+
+impl Scheduler for Lottery {
+	fn push(&mut self, process: Process) {
+		self.processes.push(process);
+	}
+
+	fn drain(&mut self) -> Vec<Process> {
+		self.processes.drain(..).collect()
+	}
+
+	fn remove(&mut self, id: u32) -> Option<Process> {
+		let index = self.processes.iter().position(|process| process.id == id)?;
+
+		Some(self.processes.remove(index))
+	}
+
+	fn processes(&self) -> Box<dyn Iterator<Item = &Process> + '_> {
+		Box::new(self.processes.iter())
+	}
+
+	fn is_empty(&self) -> bool {
+		self.processes.is_empty()
+	}
+
+	fn step(&mut self, current_time: SimTime) -> Data {
+		let process_index = self.find_next_process(current_time);
+		let process = &mut self.processes[process_index];
+
+		// If the process has not arrived yet, wait until it does.
+		let (idle_time, waiting_time) = if process.next_arrival >= current_time {
+			(process.next_arrival - current_time, SimTime::ZERO)
+		} else {
+			(SimTime::ZERO, current_time - process.next_arrival)
+		};
+
+		let process_id = process.id;
+		let start = Event::Start {
+			id: process_id,
+			time: current_time + idle_time,
+		};
+
+		// Run the process for the quanta, keeping the rest of the burst for the next draw.
+		let cpu_time = process.cpu_times.pop_front().unwrap();
+		let (cpu_time, io_time, completed) = if cpu_time > self.quanta {
+			process.cpu_times.push_front(cpu_time - self.quanta);
+
+			(self.quanta, SimTime::ZERO, false)
+		} else {
+			(
+				cpu_time,
+				process.io_times.pop_front().unwrap_or_default(),
+				true,
+			)
+		};
+
+		process.next_arrival = cpu_time + io_time + idle_time + current_time;
+		process.record_wait(waiting_time);
+		process.turnaround_time += cpu_time + io_time + waiting_time;
+		process.record_start(current_time + idle_time);
+		process.record_run(cpu_time, current_time + idle_time + cpu_time);
+
+		if completed {
+			process.complete_burst(current_time + idle_time + cpu_time);
+		}
+
+		// If the process has no more CPU times, remove it from the list.
+		let response = if process.cpu_times.is_empty() {
+			let process = self.processes.remove(process_index);
+
+			Response::Success(process)
+		} else {
+			Response::Empty
+		};
+
+		Data {
+			process_id,
+			cpu_time,
+			idle_time,
+			response,
+			events: vec![start],
+		}
+	}
+}
@@ -0,0 +1,24 @@
+mod earliest_deadline_first;
+mod first_come_first_serve;
+mod lottery;
+mod multi_level_feedback_queue;
+mod priority;
+mod rate_monotonic;
+mod round_robin;
+mod shortest_job_first;
+mod soft_real_time;
+mod stride;
+
+pub use earliest_deadline_first::EarliestDeadlineFirst;
+pub use first_come_first_serve::FirstComeFirstServe;
+pub use lottery::Lottery;
+pub use multi_level_feedback_queue::{
+	BatchStats, BoostRecord, LevelPolicy, LevelScheduler, MultiLevelFeedbackQueue,
+	ParseLevelPolicyError, DEFAULT_LEVELS,
+};
+pub use priority::PriorityScheduler;
+pub use rate_monotonic::RateMonotonic;
+pub use round_robin::RoundRobin;
+pub use shortest_job_first::ShortestJobFirst;
+pub use soft_real_time::SoftRealTime;
+pub use stride::Stride;
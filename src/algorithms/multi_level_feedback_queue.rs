@@ -1,425 +1,659 @@
-use std::{
-	collections::VecDeque,
-	sync::atomic::{AtomicU32, Ordering},
+use alloc::{
+	boxed::Box,
+	collections::{BTreeMap, VecDeque},
+	vec::Vec,
+};
+use core::{fmt, str::FromStr};
+
+use crate::{
+	algorithms::{
+		FirstComeFirstServe, Lottery, PriorityScheduler, RoundRobin, ShortestJobFirst, Stride,
+	},
+	process::Process,
+	scheduler::{Data, Event, LevelLists, Response, Scheduler},
+	time::SimTime,
 };
 
-/// Loads the test processes.
-fn load_test_processes() -> Vec<Process> {
-	let list = [
-		Process::new(
-			[27, 31, 43, 18, 22, 26, 24].into(),
-			[5, 3, 5, 4, 6, 4, 3, 4].into(),
-		),
-		Process::new(
-			[48, 44, 42, 37, 76, 41, 31, 43].into(),
-			[4, 5, 7, 12, 9, 4, 9, 7, 8].into(),
-		),
-		Process::new(
-			[33, 41, 65, 21, 61, 18, 26, 31].into(),
-			[8, 12, 18, 14, 4, 15, 14, 5, 6].into(),
-		),
-		Process::new(
-			[35, 41, 45, 51, 61, 54, 82, 77].into(),
-			[3, 4, 5, 3, 4, 5, 6, 5, 3].into(),
-		),
-		Process::new(
-			[24, 21, 36, 26, 31, 28, 21, 13, 11].into(),
-			[16, 17, 5, 16, 7, 13, 11, 6, 3, 4].into(),
-		),
-		Process::new(
-			[22, 8, 10, 12, 14, 18, 24, 30].into(),
-			[11, 4, 5, 6, 7, 9, 12, 15, 8].into(),
-		),
-		Process::new(
-			[46, 41, 42, 21, 32, 19, 33].into(),
-			[14, 17, 11, 15, 4, 7, 16, 10].into(),
-		),
-		Process::new([14, 33, 51, 73, 87].into(), [4, 5, 6, 14, 16, 6].into()),
-	];
-
-	list.into()
+/// The queue policy of a single level.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(tag = "policy", rename_all = "kebab-case")
+)]
+pub enum LevelPolicy {
+	RoundRobin {
+		quanta: SimTime,
+	},
+	FirstComeFirstServe,
+	ShortestJobFirst {
+		predicted: bool,
+	},
+	Priority {
+		preemptive: bool,
+		aging: Option<SimTime>,
+	},
+	Lottery {
+		quanta: SimTime,
+		seed: u64,
+	},
+	Stride {
+		quanta: SimTime,
+	},
 }
 
-/// Process ID counter.
-static PROCESS_LAST: AtomicU32 = AtomicU32::new(1);
+impl LevelPolicy {
+	// Creates an empty scheduler for the level.
+	pub fn build(self) -> LevelScheduler {
+		match self {
+			Self::RoundRobin { quanta } => {
+				LevelScheduler::RoundRobin(RoundRobin::from_processes(VecDeque::new(), quanta))
+			}
+			Self::FirstComeFirstServe => {
+				LevelScheduler::FirstComeFirstServe(FirstComeFirstServe::from_processes(Vec::new()))
+			}
+			Self::ShortestJobFirst { predicted } => LevelScheduler::ShortestJobFirst(
+				ShortestJobFirst::from_processes(Vec::new()).with_prediction(predicted),
+			),
+			Self::Priority { preemptive, aging } => LevelScheduler::Priority(
+				PriorityScheduler::from_processes(Vec::new(), preemptive).with_aging(aging),
+			),
+			Self::Lottery { quanta, seed } => {
+				LevelScheduler::Lottery(Lottery::from_processes(Vec::new(), quanta, seed))
+			}
+			Self::Stride { quanta } => {
+				LevelScheduler::Stride(Stride::from_processes(Vec::new(), quanta))
+			}
+		}
+	}
 
-struct Process {
-	id: u32,
-	next_arrival: u32,
-	io_times: VecDeque<u32>,
-	cpu_times: VecDeque<u32>,
+	// Returns whether the level makes progress, which a zero quantum never does.
+	pub fn is_valid(&self) -> bool {
+		match self {
+			Self::RoundRobin { quanta }
+			| Self::Lottery { quanta, .. }
+			| Self::Stride { quanta } => !quanta.is_zero(),
+			_ => true,
+		}
+	}
+}
 
-	turnaround_time: u32,
-	waiting_time: u32,
-	response_time: u32,
+/// The scheduler of a single level, as built from its policy.
+///
+/// Unlike a boxed scheduler, a level can be cloned and serialized along with
+/// the processes queued in it.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LevelScheduler {
+	RoundRobin(RoundRobin),
+	FirstComeFirstServe(FirstComeFirstServe),
+	ShortestJobFirst(ShortestJobFirst),
+	Priority(PriorityScheduler),
+	Lottery(Lottery),
+	Stride(Stride),
 }
 
-impl Process {
-	fn new(io_times: VecDeque<u32>, cpu_times: VecDeque<u32>) -> Self {
-		// Assign a unique ID to each process.
-		let id = PROCESS_LAST.fetch_add(1, Ordering::SeqCst);
+impl LevelScheduler {
+	fn scheduler(&self) -> &dyn Scheduler {
+		match self {
+			Self::RoundRobin(scheduler) => scheduler,
+			Self::FirstComeFirstServe(scheduler) => scheduler,
+			Self::ShortestJobFirst(scheduler) => scheduler,
+			Self::Priority(scheduler) => scheduler,
+			Self::Lottery(scheduler) => scheduler,
+			Self::Stride(scheduler) => scheduler,
+		}
+	}
 
-		Self {
-			id,
-			next_arrival: 0,
-			io_times,
-			cpu_times,
-
-			turnaround_time: 0,
-			waiting_time: 0,
-			response_time: u32::MAX,
+	fn scheduler_mut(&mut self) -> &mut dyn Scheduler {
+		match self {
+			Self::RoundRobin(scheduler) => scheduler,
+			Self::FirstComeFirstServe(scheduler) => scheduler,
+			Self::ShortestJobFirst(scheduler) => scheduler,
+			Self::Priority(scheduler) => scheduler,
+			Self::Lottery(scheduler) => scheduler,
+			Self::Stride(scheduler) => scheduler,
 		}
 	}
 }
 
-/// The response of the scheduler after a step.
-#[derive(Default)]
-enum Response {
-	Success(Process),
-	Failure(Process),
-
-	#[default]
-	Empty,
-}
+impl Scheduler for LevelScheduler {
+	fn push(&mut self, process: Process) {
+		self.scheduler_mut().push(process);
+	}
 
-/// The data returned by the scheduler after a step.
-struct Data {
-	cpu_time: u32,
-	idle_time: u32,
+	fn drain(&mut self) -> Vec<Process> {
+		self.scheduler_mut().drain()
+	}
 
-	response: Response,
-}
+	fn remove(&mut self, id: u32) -> Option<Process> {
+		self.scheduler_mut().remove(id)
+	}
 
-struct RoundRobin {
-	processes: VecDeque<Process>,
-	quanta: u32,
-}
+	fn get(&self, id: u32) -> Option<&Process> {
+		self.scheduler().get(id)
+	}
 
-impl RoundRobin {
-	// Creates a new scheduler from a list of processes.
-	fn from_processes(processes: VecDeque<Process>, quanta: u32) -> Self {
-		Self { processes, quanta }
+	fn processes(&self) -> Box<dyn Iterator<Item = &Process> + '_> {
+		self.scheduler().processes()
 	}
 
 	fn is_empty(&self) -> bool {
-		self.processes.is_empty()
+		self.scheduler().is_empty()
 	}
 
-	fn io_busy(&self, current_time: u32) -> bool {
-		self.processes
-			.iter()
-			.all(|process| process.next_arrival > current_time)
+	fn is_ready(&self, current_time: SimTime) -> bool {
+		self.scheduler().is_ready(current_time)
 	}
 
-	// Returns a list of processes that are waiting for IO.
-	fn io_remaining(&self, current_time: u32) -> Vec<(u32, u32)> {
-		self.processes
-			.iter()
-			.filter(|&process| (process.next_arrival > current_time))
-			.map(|process| (process.id, process.next_arrival - current_time))
-			.collect()
+	fn io_remaining(&self, current_time: SimTime) -> Vec<(u32, SimTime)> {
+		self.scheduler().io_remaining(current_time)
 	}
 
-	// Returns a list of processes that are waiting for CPU.
-	fn cpu_remaining(&self, current_time: u32) -> Vec<(u32, u32)> {
-		self.processes
-			.iter()
-			.filter(|&process| (process.next_arrival <= current_time))
-			.map(|process| (process.id, process.cpu_times.front().copied().unwrap()))
-			.collect()
+	fn cpu_remaining(&self, current_time: SimTime) -> Vec<(u32, SimTime)> {
+		self.scheduler().cpu_remaining(current_time)
 	}
 
-	// Steps the scheduler forward by one time unit.
-	fn step(&mut self, current_time: u32) -> Data {
-		// Get the next process to run.
-		let mut process = self.processes.pop_front().unwrap();
+	fn level_count(&self) -> usize {
+		self.scheduler().level_count()
+	}
 
-		// Calculate the idle time and waiting time.
-		let (idle_time, waiting_time) = if process.next_arrival >= current_time {
-			(process.next_arrival - current_time, 0)
-		} else {
-			(0, current_time - process.next_arrival)
-		};
+	fn levels(&self, current_time: SimTime) -> Vec<LevelLists> {
+		self.scheduler().levels(current_time)
+	}
 
-		println!("Start P{} at {}", process.id, current_time + idle_time);
+	fn preempts_on_arrival(&self) -> bool {
+		self.scheduler().preempts_on_arrival()
+	}
 
-		// Run the process for the quanta.
-		let cpu_time = process.cpu_times.pop_front().unwrap();
-		let (cpu_time, io_time, fail) = if cpu_time > self.quanta {
-			// If the process has more CPU time than the quanta, run it again.
-			process.cpu_times.push_front(cpu_time - self.quanta);
+	fn step(&mut self, current_time: SimTime) -> Data {
+		self.scheduler_mut().step(current_time)
+	}
+}
 
-			(self.quanta, 0, true)
-		} else {
-			// Otherwise, run the process for the remaining CPU time.
-			let io_time = process.io_times.pop_front().unwrap_or(0);
+/// The error returned when a level policy cannot be parsed.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseLevelPolicyError;
 
-			(cpu_time, io_time, false)
-		};
+impl fmt::Display for ParseLevelPolicyError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str(
+			"expected rr:<quanta>, fcfs, sjf[:predicted], priority[:preemptive][:aging=<interval>], lottery:<quanta>[:seed=<seed>], or stride:<quanta>",
+		)
+	}
+}
 
-		process.next_arrival = cpu_time + io_time + idle_time + current_time;
-		process.waiting_time += waiting_time;
-		process.turnaround_time += cpu_time + io_time + waiting_time;
-		process.response_time = process.response_time.min(current_time + idle_time);
+#[cfg(feature = "std")]
+impl std::error::Error for ParseLevelPolicyError {}
 
-		// Add the process back to the queue if it still has CPU time remaining.
-		let response = if process.cpu_times.is_empty() {
-			Response::Success(process)
-		} else if fail {
-			Response::Failure(process)
-		} else {
-			self.processes.push_back(process);
+impl FromStr for LevelPolicy {
+	type Err = ParseLevelPolicyError;
 
-			Response::Empty
-		};
+	// Parses policies such as `rr:5`, `fcfs`, `sjf:predicted`, `priority:preemptive:aging=10`, or `lottery:5:seed=7`.
+	fn from_str(text: &str) -> Result<Self, Self::Err> {
+		let mut parts = text.split(':');
 
-		Data {
-			cpu_time,
-			idle_time,
-			response,
+		match parts.next() {
+			Some("rr") => {
+				let quanta: Option<SimTime> = parts.next().and_then(|part| part.parse().ok());
+
+				match (quanta, parts.next()) {
+					(Some(quanta), None) if !quanta.is_zero() => Ok(Self::RoundRobin { quanta }),
+					_ => Err(ParseLevelPolicyError),
+				}
+			}
+			Some("fcfs") if parts.next().is_none() => Ok(Self::FirstComeFirstServe),
+			Some("sjf") => match (parts.next(), parts.next()) {
+				(None, _) => Ok(Self::ShortestJobFirst { predicted: false }),
+				(Some("predicted"), None) => Ok(Self::ShortestJobFirst { predicted: true }),
+				_ => Err(ParseLevelPolicyError),
+			},
+			Some("priority") => {
+				let mut preemptive = false;
+				let mut aging = None;
+
+				for part in parts {
+					if part == "preemptive" {
+						preemptive = true;
+					} else if let Some(interval) = part.strip_prefix("aging=") {
+						aging = Some(interval.parse().map_err(|_| ParseLevelPolicyError)?);
+					} else {
+						return Err(ParseLevelPolicyError);
+					}
+				}
+
+				Ok(Self::Priority { preemptive, aging })
+			}
+			Some("lottery") => {
+				let quanta: Option<SimTime> = parts.next().and_then(|part| part.parse().ok());
+				let seed = match parts.next() {
+					Some(part) => part
+						.strip_prefix("seed=")
+						.and_then(|seed| seed.parse().ok()),
+					None => Some(1),
+				};
+
+				match (quanta, seed, parts.next()) {
+					(Some(quanta), Some(seed), None) if !quanta.is_zero() => {
+						Ok(Self::Lottery { quanta, seed })
+					}
+					_ => Err(ParseLevelPolicyError),
+				}
+			}
+			Some("stride") => {
+				let quanta: Option<SimTime> = parts.next().and_then(|part| part.parse().ok());
+
+				match (quanta, parts.next()) {
+					(Some(quanta), None) if !quanta.is_zero() => Ok(Self::Stride { quanta }),
+					_ => Err(ParseLevelPolicyError),
+				}
+			}
+			_ => Err(ParseLevelPolicyError),
 		}
 	}
 }
 
-struct FirstComeFirstServe {
-	processes: Vec<Process>,
+/// The levels used when none are configured.
+pub const DEFAULT_LEVELS: [LevelPolicy; 3] = [
+	LevelPolicy::RoundRobin {
+		quanta: SimTime::new(5),
+	},
+	LevelPolicy::RoundRobin {
+		quanta: SimTime::new(10),
+	},
+	LevelPolicy::FirstComeFirstServe,
+];
+
+/// The statistics of a single priority boost.
+///
+/// Waiting is compared over the window since the previous boost and the window
+/// until the next one, or the end of the process if it finishes first.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BoostRecord {
+	pub time: SimTime,
+
+	/// The processes moved back to the first level.
+	pub boosted: Vec<u32>,
+	/// The processes that were already in the first level.
+	pub resident: Vec<u32>,
+
+	pub boosted_waiting_before: SimTime,
+	pub boosted_waiting_after: SimTime,
+	pub resident_waiting_before: SimTime,
+	pub resident_waiting_after: SimTime,
 }
 
-impl FirstComeFirstServe {
-	// Creates a new scheduler from a list of processes.
-	fn from_processes(processes: Vec<Process>) -> Self {
-		Self { processes }
+impl BoostRecord {
+	// Returns how much the waiting time of the boosted processes dropped.
+	pub fn waiting_drop(&self) -> i64 {
+		self.boosted_waiting_before.as_u64() as i64 - self.boosted_waiting_after.as_u64() as i64
 	}
 
-	fn is_empty(&self) -> bool {
-		self.processes.is_empty()
+	// Returns how much extra waiting the boost cost the first level.
+	pub fn level_1_cost(&self) -> i64 {
+		self.resident_waiting_after.as_u64() as i64 - self.resident_waiting_before.as_u64() as i64
 	}
+}
 
-	// Returns a list of processes that are waiting for IO.
-	fn io_remaining(&self, current_time: u32) -> Vec<(u32, u32)> {
-		self.processes
-			.iter()
-			.filter(|&process| (process.next_arrival > current_time))
-			.map(|process| (process.id, process.next_arrival - current_time))
-			.collect()
-	}
+/// The statistics of running the last level in batches.
+#[derive(Clone, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BatchStats {
+	pub batches: u32,
+	/// The slices run in batches, including the first of each.
+	pub bursts: u32,
+	/// The time processes in higher levels were ready but held back by a batch, summed over the processes.
+	pub delay: SimTime,
+}
 
-	// Returns a list of processes that are waiting for CPU.
-	fn cpu_remaining(&self, current_time: u32) -> Vec<(u32, u32)> {
-		self.processes
-			.iter()
-			.filter(|&process| (process.next_arrival <= current_time))
-			.map(|process| (process.id, process.cpu_times.front().copied().unwrap()))
-			.collect()
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MultiLevelFeedbackQueue {
+	levels: Vec<LevelScheduler>,
+
+	boost_interval: Option<SimTime>,
+	next_boost: SimTime,
+	boosts: Vec<BoostRecord>,
+	// The waiting time of each process at the last boost.
+	waiting_marks: BTreeMap<u32, SimTime>,
+	batching: bool,
+	// The slices left in the current batch of the last level.
+	batch_left: usize,
+	batch_stats: BatchStats,
+	// The process that ran last and the level it was left in, since the engine looks it up after every step.
+	#[cfg_attr(feature = "serde", serde(skip))]
+	last_run: Option<(u32, usize)>,
+}
+
+impl MultiLevelFeedbackQueue {
+	// Creates a new scheduler from a list of processes.
+	pub fn from_processes(processes: VecDeque<Process>) -> Self {
+		Self::with_levels(processes, &DEFAULT_LEVELS)
 	}
 
-	// Find the process with the lowest next arrival time.
-	fn find_next_process(&self) -> usize {
-		let mut chosen_index = 0;
+	// Creates a new scheduler with the given level policies, from the highest priority down.
+	pub fn with_levels(processes: VecDeque<Process>, policies: &[LevelPolicy]) -> Self {
+		assert!(!policies.is_empty(), "MLFQ needs at least one level");
 
-		for (index, process) in self.processes.iter().enumerate() {
-			if process.next_arrival < self.processes[chosen_index].next_arrival {
-				chosen_index = index;
-			}
+		let mut levels: Vec<_> = policies.iter().map(|policy| policy.build()).collect();
+
+		for process in processes {
+			levels[0].push(process);
 		}
 
-		chosen_index
+		Self {
+			levels,
+
+			boost_interval: None,
+			next_boost: SimTime::ZERO,
+			boosts: Vec::new(),
+			waiting_marks: BTreeMap::new(),
+			batching: false,
+			batch_left: 0,
+			batch_stats: BatchStats::default(),
+			last_run: None,
+		}
 	}
 
-	// Steps the scheduler forward by one time unit.
-	fn step(&mut self, current_time: u32) -> Data {
-		let process_index = self.find_next_process();
-		let process = &mut self.processes[process_index];
+	// Moves every process back to the first level once per interval, which must not be zero.
+	pub fn with_boost_interval(mut self, boost_interval: SimTime) -> Self {
+		assert!(
+			!boost_interval.is_zero(),
+			"the boost interval must not be zero"
+		);
 
-		// If the process has not arrived yet, wait until it does.
-		let (idle_time, waiting_time) = if process.next_arrival >= current_time {
-			(process.next_arrival - current_time, 0)
-		} else {
-			(0, current_time - process.next_arrival)
-		};
+		self.boost_interval = Some(boost_interval);
+		self.next_boost = boost_interval;
+		self
+	}
 
-		println!("Start P{} at {}", process.id, current_time + idle_time);
+	// Lets a process that yields mid-quanta donate the rest of it to the next process in its round robin level.
+	//
+	// A process running on a donated quanta is demoted if it uses it up, like on a fresh one.
+	pub fn with_donation(mut self, donation: bool) -> Self {
+		self.levels = self
+			.levels
+			.into_iter()
+			.map(|level| match level {
+				LevelScheduler::RoundRobin(scheduler) => {
+					LevelScheduler::RoundRobin(scheduler.with_donation(donation))
+				}
+				level => level,
+			})
+			.collect();
+		self
+	}
 
-		// Pop the next CPU time from the process.
-		let cpu_time = process.cpu_times.pop_front().unwrap();
-		// Pop the next IO time from the process.
-		let io_time = process.io_times.pop_front().unwrap_or(0);
+	// Runs the processes ready in the last level back-to-back once it is picked, without going back
+	// to the higher levels until each has run or a boost moves them up.
+	pub fn with_batching(mut self, batching: bool) -> Self {
+		self.batching = batching;
+		self
+	}
 
-		// Update the process's metrics.
-		process.next_arrival = cpu_time + io_time + idle_time + current_time;
-		process.waiting_time += waiting_time;
-		process.turnaround_time += cpu_time + io_time + waiting_time;
-		process.response_time = process.response_time.min(current_time + idle_time);
+	pub fn boosts(&self) -> &[BoostRecord] {
+		&self.boosts
+	}
 
-		// If the process has no more CPU times, remove it from the list.
-		let response = if process.cpu_times.is_empty() {
-			let process = self.processes.remove(process_index);
+	// Returns the statistics of the batches, if the last level runs in batches.
+	pub fn batching(&self) -> Option<&BatchStats> {
+		self.batching.then_some(&self.batch_stats)
+	}
 
-			Response::Success(process)
-		} else {
-			Response::Empty
-		};
+	// Picks the level to run next, keeping to the last level while its batch lasts.
+	//
+	// Returns the level along with the number of ready processes in higher levels the batch holds back.
+	fn find_batch_level(&mut self, current_time: SimTime) -> (usize, usize) {
+		let last = self.levels.len() - 1;
+
+		if self.batch_left > 0 && self.levels[last].is_ready(current_time) {
+			let held = self.levels[..last]
+				.iter()
+				.map(|level| level.cpu_remaining(current_time).len())
+				.sum();
+
+			self.batch_left -= 1;
+			self.batch_stats.bursts += 1;
+
+			return (last, held);
+		}
 
-		Data {
-			cpu_time,
-			idle_time,
-			response,
+		let index = self.find_next_level(current_time);
+
+		self.batch_left = 0;
+
+		// Start a batch of every process ready in the last level.
+		if self.batching && index == last && self.levels[last].is_ready(current_time) {
+			self.batch_left = self.levels[last]
+				.cpu_remaining(current_time)
+				.len()
+				.saturating_sub(1);
+			self.batch_stats.batches += 1;
+			self.batch_stats.bursts += 1;
 		}
+
+		(index, 0)
 	}
-}
 
-struct MultiLevelFeedbackQueue {
-	level_1: RoundRobin,
-	level_2: RoundRobin,
-	level_3: FirstComeFirstServe,
-}
+	// Returns the waiting time a process accumulated since the last boost.
+	fn waiting_since_mark(&self, id: u32, waiting_time: SimTime) -> SimTime {
+		waiting_time - self.waiting_marks.get(&id).copied().unwrap_or_default()
+	}
 
-impl MultiLevelFeedbackQueue {
-	// Creates a new scheduler from a list of processes.
-	fn from_processes(processes: VecDeque<Process>) -> Self {
-		Self {
-			level_1: RoundRobin::from_processes(processes, 5),
-			level_2: RoundRobin::from_processes(VecDeque::new(), 10),
-			level_3: FirstComeFirstServe::from_processes(Vec::new()),
+	// Adds the waiting of a process to the window after the last boost.
+	fn close_window(&mut self, id: u32, waiting_time: SimTime) {
+		let waiting = self.waiting_since_mark(id, waiting_time);
+
+		if let Some(record) = self.boosts.last_mut() {
+			if record.boosted.contains(&id) {
+				record.boosted_waiting_after += waiting;
+			} else if record.resident.contains(&id) {
+				record.resident_waiting_after += waiting;
+			}
 		}
 	}
 
-	// Print the list of processes waiting for IO and CPU.
-	fn show_lists(&self, current_time: u32) {
-		// Chains the lists together and sorts them by the next arrival time for IO and CPU.
-		let mut io_list: Vec<_> = self
-			.level_1
-			.io_remaining(current_time)
-			.into_iter()
-			.chain(self.level_2.io_remaining(current_time))
-			.chain(self.level_3.io_remaining(current_time))
-			.collect();
+	// Boosts every process in the lower levels if the interval has passed, returning how many moved.
+	fn boost(&mut self, current_time: SimTime) -> Option<usize> {
+		let boost_interval = self.boost_interval?;
 
-		io_list.sort_unstable_by_key(|data| data.0);
+		if current_time < self.next_boost {
+			return None;
+		}
 
-		let mut cpu_list: Vec<_> = self
-			.level_1
-			.cpu_remaining(current_time)
-			.into_iter()
-			.chain(self.level_2.cpu_remaining(current_time))
-			.chain(self.level_3.cpu_remaining(current_time))
-			.collect();
+		while self.next_boost <= current_time {
+			self.next_boost += boost_interval;
+		}
 
-		cpu_list.sort_unstable_by_key(|data| data.0);
+		// There is nothing to boost if the lower levels are empty.
+		if self.levels[1..].iter().all(|level| level.is_empty()) {
+			return None;
+		}
 
-		// Print the IO list if it is not empty.
-		if !io_list.is_empty() {
-			print!("IO: ");
+		let lower: Vec<_> = self.levels[1..]
+			.iter_mut()
+			.flat_map(|level| level.drain())
+			.collect();
 
-			for (id, time) in io_list {
-				print!("(P{id} {time}) ");
-			}
+		// Snapshot the waiting time of every process in each group.
+		let resident: Vec<_> = self.levels[0]
+			.processes()
+			.map(|process| (process.id, process.waiting_time))
+			.collect();
+		let boosted: Vec<_> = lower
+			.iter()
+			.map(|process| (process.id, process.waiting_time))
+			.collect();
 
-			println!();
+		// Close the window of the previous boost.
+		for &(id, waiting_time) in resident.iter().chain(&boosted) {
+			self.close_window(id, waiting_time);
 		}
 
-		// Print the CPU list if it is not empty.
-		if !cpu_list.is_empty() {
-			print!("CPU: ");
+		let record = BoostRecord {
+			time: current_time,
+			boosted: boosted.iter().map(|data| data.0).collect(),
+			resident: resident.iter().map(|data| data.0).collect(),
+			boosted_waiting_before: boosted
+				.iter()
+				.map(|&(id, waiting_time)| self.waiting_since_mark(id, waiting_time))
+				.sum(),
+			boosted_waiting_after: SimTime::ZERO,
+			resident_waiting_before: resident
+				.iter()
+				.map(|&(id, waiting_time)| self.waiting_since_mark(id, waiting_time))
+				.sum(),
+			resident_waiting_after: SimTime::ZERO,
+		};
 
-			for (id, time) in cpu_list {
-				print!("(P{id} {time}) ");
-			}
+		// Start a new window.
+		self.waiting_marks
+			.extend(resident.into_iter().chain(boosted));
+
+		let count = record.boosted.len();
+
+		self.boosts.push(record);
+
+		for mut process in lower {
+			process.enter_level(1, current_time);
 
-			println!();
+			self.levels[0].push(process);
 		}
+
+		Some(count)
 	}
 
-	fn is_empty(&self) -> bool {
-		self.level_1.is_empty() && self.level_2.is_empty() && self.level_3.is_empty()
+	// Picks the level to run next.
+	fn find_next_level(&self, current_time: SimTime) -> usize {
+		let last = self.levels.len() - 1;
+
+		// Run the first level that is not empty and not busy.
+		if let Some(index) = self
+			.levels
+			.iter()
+			.position(|level| level.is_ready(current_time))
+		{
+			return index;
+		}
+
+		// Otherwise, wait on the last level, or the highest level that is not empty.
+		if !self.levels[last].is_empty() {
+			last
+		} else {
+			self.levels
+				.iter()
+				.position(|level| !level.is_empty())
+				.unwrap_or(last)
+		}
 	}
+}
 
-	// Steps the scheduler forward by one time unit.
-	fn step(&mut self, current_time: u32) -> Data {
-		self.show_lists(current_time);
+impl Scheduler for MultiLevelFeedbackQueue {
+	// Adds a process to the level it was last in, which is level 1 for new processes.
+	fn push(&mut self, process: Process) {
+		let last = self.levels.len() - 1;
+		let level = process
+			.level_history
+			.last()
+			.map_or(0, |visit| visit.level as usize - 1);
 
-		// If the first level is not empty and not busy, run it.
-		if !self.level_1.is_empty() && !self.level_1.io_busy(current_time) {
-			let mut data = self.level_1.step(current_time);
+		self.levels[level.min(last)].push(process);
+	}
 
-			// If the process failed, downgrade it to the second level.
-			if let Response::Failure(process) = data.response {
-				println!("Downgraded P{} to level 2", process.id);
+	fn drain(&mut self) -> Vec<Process> {
+		self.levels
+			.iter_mut()
+			.flat_map(|level| level.drain())
+			.collect()
+	}
 
-				self.level_2.processes.push_back(process);
+	fn remove(&mut self, id: u32) -> Option<Process> {
+		self.levels.iter_mut().find_map(|level| level.remove(id))
+	}
 
-				data.response = Response::Empty;
-			}
+	// Looks in the level the process was last left in first, in case it has not moved since.
+	fn get(&self, id: u32) -> Option<&Process> {
+		self.last_run
+			.filter(|&(last, _)| last == id)
+			.and_then(|(_, index)| self.levels[index].get(id))
+			.or_else(|| self.levels.iter().find_map(|level| level.get(id)))
+	}
 
-			data
-		// If the second level is not empty and not busy, run it.
-		} else if !self.level_2.is_empty() && !self.level_2.io_busy(current_time) {
-			let mut data = self.level_2.step(current_time);
+	fn processes(&self) -> Box<dyn Iterator<Item = &Process> + '_> {
+		Box::new(self.levels.iter().flat_map(|level| level.processes()))
+	}
 
-			// If the process failed, downgrade it to the third level.
-			if let Response::Failure(process) = data.response {
-				println!("Downgraded P{} to level 3", process.id);
+	fn level_count(&self) -> usize {
+		self.levels.len()
+	}
 
-				self.level_3.processes.push(process);
+	fn levels(&self, current_time: SimTime) -> Vec<LevelLists> {
+		self.levels
+			.iter()
+			.map(|level| LevelLists {
+				io: level.io_remaining(current_time),
+				cpu: level.cpu_remaining(current_time),
+			})
+			.collect()
+	}
 
-				data.response = Response::Empty;
-			}
+	// Any level that preempts for arrivals needs to see them coming.
+	fn preempts_on_arrival(&self) -> bool {
+		self.levels.iter().any(|level| level.preempts_on_arrival())
+	}
 
-			data
-		// Otherwise, run the third level.
-		} else {
-			self.level_3.step(current_time)
+	fn step(&mut self, current_time: SimTime) -> Data {
+		let boosted = self.boost(current_time);
+
+		// A boost moves the rest of the batch up, ending it.
+		if boosted.is_some() {
+			self.batch_left = 0;
 		}
-	}
-}
 
-fn main() {
-	let processes = load_test_processes();
-	let process_count = processes.len() as f64;
-
-	let mut scheduler = MultiLevelFeedbackQueue::from_processes(processes.into());
-
-	let mut total_turnaround_time = 0;
-	let mut total_waiting_time = 0;
-	let mut total_response_time = 0;
-	let mut idle_time = 0;
-	let mut current_time = 0;
-
-	// Handle the response from the scheduler.
-	while !scheduler.is_empty() {
-		let data = scheduler.step(current_time);
-
-		match data.response {
-			Response::Success(process) => {
-				total_turnaround_time += process.turnaround_time;
-				total_waiting_time += process.waiting_time;
-				total_response_time += process.response_time;
-
-				println!(
-					"End P{} with Turnaround Time: {}, Waiting Time: {}, Response Time: {}",
-					process.id,
-					process.turnaround_time,
-					process.waiting_time,
-					process.response_time
-				);
+		let (index, held) = self.find_batch_level(current_time);
+
+		tracing::trace!(target: "mlfq", time = %current_time, level = index + 1, "picked level");
+
+		let mut data = self.levels[index].step(current_time);
+
+		self.batch_stats.delay += data.cpu_time * held as u64;
+
+		self.last_run = Some((data.process_id, index));
+
+		// If the process failed, downgrade it to the next level.
+		if let Response::Failure(mut process) = data.response {
+			let next = (index + 1).min(self.levels.len() - 1);
+			let level = next as u32 + 1;
+
+			if next != index {
+				tracing::debug!(target: "mlfq", process = process.id, level, "demoted");
+
+				data.events.push(Event::Downgrade {
+					id: process.id,
+					level,
+				});
+
+				process.enter_level(level, process.next_arrival);
 			}
-			Response::Failure(process) => panic!("P{} failed", process.id),
-			Response::Empty => {}
+
+			self.levels[next].push(process);
+			self.last_run = Some((data.process_id, next));
+
+			data.response = Response::Empty;
 		}
 
-		idle_time += data.idle_time;
-		current_time += data.cpu_time + data.idle_time;
+		if let Some(count) = boosted {
+			tracing::info!(target: "mlfq", time = %current_time, count, "boosted");
 
-		println!();
-	}
+			data.events.insert(0, Event::Boost { count });
+		}
 
-	let turnaround_average = f64::from(total_turnaround_time) / process_count;
-	let waiting_average = f64::from(total_waiting_time) / process_count;
-	let response_average = f64::from(total_response_time) / process_count;
-	let cpu_utilization = (1.0 - f64::from(idle_time) / f64::from(current_time)) * 100.0;
+		// Count the waiting of a finished process towards the last boost.
+		if let Response::Success(process) = &data.response {
+			self.close_window(process.id, process.waiting_time);
+		}
 
-	println!("Turnaround Time: {turnaround_average:.2}");
-	println!("Waiting Time: {waiting_average:.2}");
-	println!("Response Time: {response_average:.2}");
-	println!("CPU Utilization: {cpu_utilization:.2}%");
+		data
+	}
 }
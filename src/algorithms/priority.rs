@@ -0,0 +1,194 @@
+use alloc::{boxed::Box, vec, vec::Vec};
+use core::cmp::Reverse;
+
+use crate::{
+	process::Process,
+	scheduler::{Data, Event, Response, Scheduler},
+	time::SimTime,
+};
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PriorityScheduler {
+	processes: Vec<Process>,
+	preemptive: bool,
+	aging: Option<SimTime>,
+}
+
+impl PriorityScheduler {
+	// Creates a new scheduler from a list of processes.
+	pub fn from_processes(processes: Vec<Process>, preemptive: bool) -> Self {
+		Self {
+			processes,
+			preemptive,
+			aging: None,
+		}
+	}
+
+	// Raises the priority of a waiting process by one for every interval it waits.
+	pub fn with_aging(mut self, aging: Option<SimTime>) -> Self {
+		self.aging = aging;
+		self
+	}
+
+	// Returns the priority of a process including the bonus it gained from aging.
+	fn effective_priority(&self, process: &Process, current_time: SimTime) -> u32 {
+		match self.aging {
+			Some(interval) if !interval.is_zero() && process.next_arrival < current_time => {
+				let intervals = (current_time - process.next_arrival).as_u64() / interval.as_u64();
+
+				process
+					.priority
+					.saturating_add(u32::try_from(intervals).unwrap_or(u32::MAX))
+			}
+			_ => process.priority,
+		}
+	}
+
+	fn find_next_process(&self, current_time: SimTime) -> usize {
+		let ready = self
+			.processes
+			.iter()
+			.enumerate()
+			.filter(|(_, process)| process.next_arrival <= current_time);
+
+		// Find the arrived process with the highest priority, breaking ties by arrival.
+		let chosen = ready.min_by_key(|(_, process)| {
+			(
+				Reverse(self.effective_priority(process, current_time)),
+				process.next_arrival,
+			)
+		});
+
+		// If no process has arrived, wait for the next one.
+		let chosen = chosen.or_else(|| {
+			self.processes
+				.iter()
+				.enumerate()
+				.min_by_key(|(_, process)| (process.next_arrival, Reverse(process.priority)))
+		});
+
+		chosen.map(|(index, _)| index).unwrap_or_default()
+	}
+
+	// Returns how long the process may run before a process with a higher priority arrives.
+	fn time_until_preempted(
+		&self,
+		index: usize,
+		priority: u32,
+		start: SimTime,
+		cpu_time: SimTime,
+	) -> SimTime {
+		if !self.preemptive {
+			return cpu_time;
+		}
+
+		self.processes
+			.iter()
+			.enumerate()
+			.filter(|&(other, process)| {
+				other != index
+					&& process.priority > priority
+					&& process.next_arrival > start
+					&& process.next_arrival < start + cpu_time
+			})
+			.map(|(_, process)| process.next_arrival - start)
+			.min()
+			.unwrap_or(cpu_time)
+	}
+}
+
+impl Scheduler for PriorityScheduler {
+	fn push(&mut self, process: Process) {
+		self.processes.push(process);
+	}
+
+	fn drain(&mut self) -> Vec<Process> {
+		self.processes.drain(..).collect()
+	}
+
+	fn remove(&mut self, id: u32) -> Option<Process> {
+		let index = self.processes.iter().position(|process| process.id == id)?;
+
+		Some(self.processes.remove(index))
+	}
+
+	fn processes(&self) -> Box<dyn Iterator<Item = &Process> + '_> {
+		Box::new(self.processes.iter())
+	}
+
+	fn is_empty(&self) -> bool {
+		self.processes.is_empty()
+	}
+
+	fn preempts_on_arrival(&self) -> bool {
+		self.preemptive
+	}
+
+	fn step(&mut self, current_time: SimTime) -> Data {
+		let process_index = self.find_next_process(current_time);
+
+		let (idle_time, priority, cpu_time) = {
+			let process = &self.processes[process_index];
+
+			(
+				process.next_arrival.saturating_sub(current_time),
+				self.effective_priority(process, current_time),
+				process.cpu_times.front().copied().unwrap(),
+			)
+		};
+
+		let start_time = current_time + idle_time;
+		let run_time = self.time_until_preempted(process_index, priority, start_time, cpu_time);
+
+		let process = &mut self.processes[process_index];
+
+		// If the process has not arrived yet, wait until it does.
+		let waiting_time = current_time.saturating_sub(process.next_arrival);
+
+		let process_id = process.id;
+		let start = Event::Start {
+			id: process_id,
+			time: start_time,
+		};
+
+		process.cpu_times.pop_front();
+
+		// If a higher priority process arrives first, keep the rest of the burst for later.
+		let io_time = if run_time < cpu_time {
+			process.cpu_times.push_front(cpu_time - run_time);
+
+			SimTime::ZERO
+		} else {
+			process.io_times.pop_front().unwrap_or_default()
+		};
+
+		// Update the process's metrics.
+		process.next_arrival = run_time + io_time + start_time;
+		process.record_wait(waiting_time);
+		process.turnaround_time += run_time + io_time + waiting_time;
+		process.record_start(start_time);
+		process.record_run(run_time, start_time + run_time);
+
+		if run_time == cpu_time {
+			process.complete_burst(start_time + run_time);
+		}
+
+		// If the process has no more CPU times, remove it from the list.
+		let response = if process.cpu_times.is_empty() {
+			let process = self.processes.remove(process_index);
+
+			Response::Success(process)
+		} else {
+			Response::Empty
+		};
+
+		Data {
+			process_id,
+			cpu_time: run_time,
+			idle_time,
+			response,
+			events: vec![start],
+		}
+	}
+}
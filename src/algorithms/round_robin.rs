@@ -0,0 +1,137 @@
+use alloc::{boxed::Box, collections::VecDeque, vec, vec::Vec};
+
+use crate::{
+	process::Process,
+	scheduler::{Data, Event, Response, Scheduler},
+	time::SimTime,
+};
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RoundRobin {
+	processes: VecDeque<Process>,
+	quanta: SimTime,
+	// Whether a process that yields mid-quanta donates the rest of it to the next process.
+	donation: bool,
+	// The time the last process yielded at and the quanta it left, for a process starting right then.
+	donated: Option<(SimTime, SimTime)>,
+}
+
+impl RoundRobin {
+	// Creates a new scheduler from a list of processes.
+	pub fn from_processes(processes: VecDeque<Process>, quanta: SimTime) -> Self {
+		Self {
+			processes,
+			quanta,
+			donation: false,
+			donated: None,
+		}
+	}
+
+	// Gives the next process the rest of the quanta of a process that yields early, instead of a fresh one.
+	pub fn with_donation(mut self, donation: bool) -> Self {
+		self.donation = donation;
+		self
+	}
+}
+
+impl Scheduler for RoundRobin {
+	fn push(&mut self, process: Process) {
+		self.processes.push_back(process);
+	}
+
+	fn drain(&mut self) -> Vec<Process> {
+		self.processes.drain(..).collect()
+	}
+
+	fn remove(&mut self, id: u32) -> Option<Process> {
+		let index = self.processes.iter().position(|process| process.id == id)?;
+
+		self.processes.remove(index)
+	}
+
+	// Searches from the back, where the process that just ran was put.
+	fn get(&self, id: u32) -> Option<&Process> {
+		self.processes.iter().rev().find(|process| process.id == id)
+	}
+
+	fn processes(&self) -> Box<dyn Iterator<Item = &Process> + '_> {
+		Box::new(self.processes.iter())
+	}
+
+	fn is_empty(&self) -> bool {
+		self.processes.is_empty()
+	}
+
+	fn step(&mut self, current_time: SimTime) -> Data {
+		// Get the next process to run.
+		let mut process = self.processes.pop_front().unwrap();
+
+		// Calculate the idle time and waiting time.
+		let (idle_time, waiting_time) = if process.next_arrival >= current_time {
+			(process.next_arrival - current_time, SimTime::ZERO)
+		} else {
+			(SimTime::ZERO, current_time - process.next_arrival)
+		};
+
+		let process_id = process.id;
+		let start = Event::Start {
+			id: process_id,
+			time: current_time + idle_time,
+		};
+
+		// A process starting as soon as another yielded runs for the rest of its quanta.
+		let start_time = current_time + idle_time;
+		let quanta = match self.donated.take() {
+			Some((time, quanta)) if time == start_time => quanta,
+			_ => self.quanta,
+		};
+
+		// Run the process for the quanta.
+		let cpu_time = process.cpu_times.pop_front().unwrap();
+		let (cpu_time, io_time, fail) = if cpu_time > quanta {
+			// If the process has more CPU time than the quanta, run it again.
+			process.cpu_times.push_front(cpu_time - quanta);
+
+			(quanta, SimTime::ZERO, true)
+		} else {
+			// Otherwise, run the process for the remaining CPU time.
+			let io_time = process.io_times.pop_front().unwrap_or_default();
+
+			(cpu_time, io_time, false)
+		};
+
+		process.next_arrival = cpu_time + io_time + idle_time + current_time;
+		process.record_wait(waiting_time);
+		process.turnaround_time += cpu_time + io_time + waiting_time;
+		process.record_start(current_time + idle_time);
+		process.record_run(cpu_time, current_time + idle_time + cpu_time);
+
+		if !fail {
+			process.complete_burst(current_time + idle_time + cpu_time);
+		}
+
+		if self.donation && cpu_time < quanta {
+			self.donated = Some((start_time + cpu_time, quanta - cpu_time));
+		}
+
+		// Add the process back to the queue if it still has CPU time remaining.
+		let response = if process.cpu_times.is_empty() {
+			Response::Success(process)
+		} else if fail {
+			Response::Failure(process)
+		} else {
+			self.processes.push_back(process);
+
+			Response::Empty
+		};
+
+		Data {
+			process_id,
+			cpu_time,
+			idle_time,
+			response,
+			events: vec![start],
+		}
+	}
+}
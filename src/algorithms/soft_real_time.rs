@@ -0,0 +1,149 @@
+use alloc::{boxed::Box, vec, vec::Vec};
+use core::cmp::Ordering;
+
+use crate::{
+	process::Process,
+	scheduler::{Data, Event, Response, Scheduler},
+	time::SimTime,
+};
+
+/// Runs soft real-time processes ahead of the rest, most at risk first.
+///
+/// Among processes with a deadline, the one that has used the most of its
+/// miss budget runs first, then the one with the earliest deadline. Processes
+/// without a deadline share what is left in arrival order, and every process
+/// runs for at most a quanta before the choice is made again.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SoftRealTime {
+	processes: Vec<Process>,
+	quanta: SimTime,
+}
+
+// Orders two processes by how urgently they need the CPU, most urgent first.
+fn urgency(first: &Process, second: &Process) -> Ordering {
+	match (first.deadline(), second.deadline()) {
+		(Some(first_deadline), Some(second_deadline)) => second
+			.budget_used()
+			.total_cmp(&first.budget_used())
+			.then(first_deadline.cmp(&second_deadline)),
+		(Some(_), None) => Ordering::Less,
+		(None, Some(_)) => Ordering::Greater,
+		(None, None) => Ordering::Equal,
+	}
+	.then(first.next_arrival.cmp(&second.next_arrival))
+}
+
+impl SoftRealTime {
+	// Creates a new scheduler from a list of processes.
+	pub fn from_processes(processes: Vec<Process>, quanta: SimTime) -> Self {
+		Self { processes, quanta }
+	}
+
+	fn find_next_process(&self, current_time: SimTime) -> usize {
+		let chosen = self
+			.processes
+			.iter()
+			.enumerate()
+			.filter(|(_, process)| process.next_arrival <= current_time)
+			.min_by(|(_, first), (_, second)| urgency(first, second));
+
+		// If no process has arrived, wait for the next one.
+		let chosen = chosen.or_else(|| {
+			self.processes
+				.iter()
+				.enumerate()
+				.min_by(|(_, first), (_, second)| {
+					first
+						.next_arrival
+						.cmp(&second.next_arrival)
+						.then_with(|| urgency(first, second))
+				})
+		});
+
+		chosen.map(|(index, _)| index).unwrap_or_default()
+	}
+}
+
+impl Scheduler for SoftRealTime {
+	fn push(&mut self, process: Process) {
+		self.processes.push(process);
+	}
+
+	fn drain(&mut self) -> Vec<Process> {
+		self.processes.drain(..).collect()
+	}
+
+	fn remove(&mut self, id: u32) -> Option<Process> {
+		let index = self.processes.iter().position(|process| process.id == id)?;
+
+		Some(self.processes.remove(index))
+	}
+
+	fn processes(&self) -> Box<dyn Iterator<Item = &Process> + '_> {
+		Box::new(self.processes.iter())
+	}
+
+	fn is_empty(&self) -> bool {
+		self.processes.is_empty()
+	}
+
+	fn step(&mut self, current_time: SimTime) -> Data {
+		let process_index = self.find_next_process(current_time);
+		let process = &mut self.processes[process_index];
+
+		// If the process has not arrived yet, wait until it does.
+		let (idle_time, waiting_time) = if process.next_arrival >= current_time {
+			(process.next_arrival - current_time, SimTime::ZERO)
+		} else {
+			(SimTime::ZERO, current_time - process.next_arrival)
+		};
+
+		let process_id = process.id;
+		let start = Event::Start {
+			id: process_id,
+			time: current_time + idle_time,
+		};
+
+		// Run the process for the quanta, keeping the rest of the burst for its next turn.
+		let cpu_time = process.cpu_times.pop_front().unwrap();
+		let (cpu_time, io_time, completed) = if cpu_time > self.quanta {
+			process.cpu_times.push_front(cpu_time - self.quanta);
+
+			(self.quanta, SimTime::ZERO, false)
+		} else {
+			(
+				cpu_time,
+				process.io_times.pop_front().unwrap_or_default(),
+				true,
+			)
+		};
+
+		process.next_arrival = cpu_time + io_time + idle_time + current_time;
+		process.record_wait(waiting_time);
+		process.turnaround_time += cpu_time + io_time + waiting_time;
+		process.record_start(current_time + idle_time);
+		process.record_run(cpu_time, current_time + idle_time + cpu_time);
+
+		if completed {
+			process.complete_burst(current_time + idle_time + cpu_time);
+		}
+
+		// If the process has no more CPU times, remove it from the list.
+		let response = if process.cpu_times.is_empty() {
+			let process = self.processes.remove(process_index);
+
+			Response::Success(process)
+		} else {
+			Response::Empty
+		};
+
+		Data {
+			process_id,
+			cpu_time,
+			idle_time,
+			response,
+			events: vec![start],
+		}
+	}
+}
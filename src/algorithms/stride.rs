@@ -0,0 +1,161 @@
+use alloc::{boxed::Box, collections::BTreeMap, vec, vec::Vec};
+
+use crate::{
+	process::Process,
+	scheduler::{Data, Event, Response, Scheduler},
+	time::SimTime,
+};
+
+/// The number divided by a process's tickets to get its stride.
+const STRIDE_SCALE: u64 = 1 << 20;
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Stride {
+	processes: Vec<Process>,
+	quanta: SimTime,
+	// The pass value of each process.
+	passes: BTreeMap<u32, u64>,
+}
+
+impl Stride {
+	// Creates a new scheduler from a list of processes.
+	pub fn from_processes(processes: Vec<Process>, quanta: SimTime) -> Self {
+		let passes = processes.iter().map(|process| (process.id, 0)).collect();
+
+		Self {
+			processes,
+			quanta,
+			passes,
+		}
+	}
+
+	fn pass(&self, process: &Process) -> u64 {
+		self.passes.get(&process.id).copied().unwrap_or_default()
+	}
+
+	fn find_next_process(&self, current_time: SimTime) -> usize {
+		// Find the arrived process with the lowest pass, breaking ties by arrival.
+		let chosen = self
+			.processes
+			.iter()
+			.enumerate()
+			.filter(|(_, process)| process.next_arrival <= current_time)
+			.min_by_key(|(_, process)| (self.pass(process), process.next_arrival));
+
+		// If no process has arrived, wait for the next one.
+		let chosen = chosen.or_else(|| {
+			self.processes
+				.iter()
+				.enumerate()
+				.min_by_key(|(_, process)| (process.next_arrival, self.pass(process)))
+		});
+
+		chosen.map(|(index, _)| index).unwrap_or_default()
+	}
+}
+
+impl Scheduler for Stride {
+	// Processes start, or rejoin, at no lower than the lowest pass so they cannot monopolize the CPU.
+	fn push(&mut self, process: Process) {
+		let lowest = self
+			.processes
+			.iter()
+			.map(|process| self.pass(process))
+			.min()
+			.unwrap_or_default();
+
+		let pass = self.passes.entry(process.id).or_default();
+
+		*pass = (*pass).max(lowest);
+
+		self.processes.push(process);
+	}
+
+	fn drain(&mut self) -> Vec<Process> {
+		self.passes.clear();
+
+		self.processes.drain(..).collect()
+	}
+
+	// The pass of a removed process is kept for when it rejoins.
+	fn remove(&mut self, id: u32) -> Option<Process> {
+		let index = self.processes.iter().position(|process| process.id == id)?;
+
+		Some(self.processes.remove(index))
+	}
+
+	fn processes(&self) -> Box<dyn Iterator<Item = &Process> + '_> {
+		Box::new(self.processes.iter())
+	}
+
+	fn is_empty(&self) -> bool {
+		self.processes.is_empty()
+	}
+
+	fn step(&mut self, current_time: SimTime) -> Data {
+		let process_index = self.find_next_process(current_time);
+		let process = &mut self.processes[process_index];
+
+		// If the process has not arrived yet, wait until it does.
+		let (idle_time, waiting_time) = if process.next_arrival >= current_time {
+			(process.next_arrival - current_time, SimTime::ZERO)
+		} else {
+			(SimTime::ZERO, current_time - process.next_arrival)
+		};
+
+		let process_id = process.id;
+		let start = Event::Start {
+			id: process_id,
+			time: current_time + idle_time,
+		};
+
+		// Run the process for the quanta, keeping the rest of the burst for its next turn.
+		let cpu_time = process.cpu_times.pop_front().unwrap();
+		let (cpu_time, io_time, completed) = if cpu_time > self.quanta {
+			process.cpu_times.push_front(cpu_time - self.quanta);
+
+			(self.quanta, SimTime::ZERO, false)
+		} else {
+			(
+				cpu_time,
+				process.io_times.pop_front().unwrap_or_default(),
+				true,
+			)
+		};
+
+		process.next_arrival = cpu_time + io_time + idle_time + current_time;
+		process.record_wait(waiting_time);
+		process.turnaround_time += cpu_time + io_time + waiting_time;
+		process.record_start(current_time + idle_time);
+		process.record_run(cpu_time, current_time + idle_time + cpu_time);
+
+		if completed {
+			process.complete_burst(current_time + idle_time + cpu_time);
+		}
+
+		// Advance the pass of the process by its stride.
+		let stride = STRIDE_SCALE / u64::from(process.tickets.max(1));
+
+		*self.passes.entry(process_id).or_default() += stride;
+
+		// If the process has no more CPU times, remove it from the list.
+		let response = if process.cpu_times.is_empty() {
+			let process = self.processes.remove(process_index);
+
+			self.passes.remove(&process_id);
+
+			Response::Success(process)
+		} else {
+			Response::Empty
+		};
+
+		Data {
+			process_id,
+			cpu_time,
+			idle_time,
+			response,
+			events: vec![start],
+		}
+	}
+}
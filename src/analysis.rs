@@ -0,0 +1,277 @@
+//! Analyzing the trace of a finished run.
+//!
+//! [`priority_inversions`] finds the intervals where a ready process waited
+//! while a process it outranks ran. A process that was dispatched before a
+//! higher-ranked one became ready may finish its slice unless the scheduler is
+//! preemptive, so only waits outside of those legal windows count.
+//!
+//! [`order_inversions`] compares the order processes finished in with the
+//! order of another run, such as FCFS on the same workload, counting the pairs
+//! that swapped places to show how much a policy reorders work.
+
+use alloc::{collections::BTreeMap, vec::Vec};
+
+use crate::{
+	process::Process,
+	time::SimTime,
+	trace::{Trace, TraceEvent},
+};
+
+/// How processes are ranked when looking for inversions.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Ranking {
+	/// The static priority of each process, where larger values run first.
+	///
+	/// Aging is not taken into account, so an aged process that runs ahead of
+	/// a higher priority shows up as an inversion.
+	Priority { preemptive: bool },
+	/// The MLFQ level of each process, where lower levels run first.
+	///
+	/// Levels are followed through the demotions and boosts in the trace, and
+	/// a dispatched process always runs out its quantum.
+	Levels,
+}
+
+/// An interval where a process waited while a process it outranks ran.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Inversion {
+	/// The higher-ranked process that waited.
+	pub waiting: u32,
+	/// The lower-ranked process that ran.
+	pub running: u32,
+	pub cpu: usize,
+	pub start: SimTime,
+	pub end: SimTime,
+}
+
+impl Inversion {
+	pub fn duration(&self) -> SimTime {
+		self.end - self.start
+	}
+}
+
+/// A process running on a CPU.
+struct Running {
+	cpu: usize,
+	id: u32,
+}
+
+/// The state of each process while scanning a trace.
+struct Scan {
+	ranking: Ranking,
+	priorities: BTreeMap<u32, u32>,
+	levels: BTreeMap<u32, u32>,
+	// The time each ready process has been waiting since.
+	ready: BTreeMap<u32, SimTime>,
+	running: Vec<Running>,
+	// The inversions that have not ended yet.
+	open: Vec<Inversion>,
+	inversions: Vec<Inversion>,
+}
+
+impl Scan {
+	// Returns whether the first process should run before the second.
+	fn outranks(&self, first: u32, second: u32) -> bool {
+		match self.ranking {
+			Ranking::Priority { .. } => self.priorities.get(&first) > self.priorities.get(&second),
+			Ranking::Levels => {
+				let level = |id| self.levels.get(&id).copied().unwrap_or(1);
+
+				level(first) < level(second)
+			}
+		}
+	}
+
+	fn is_preemptive(&self) -> bool {
+		matches!(self.ranking, Ranking::Priority { preemptive: true })
+	}
+
+	// Marks a process ready, opening an inversion against every running process it outranks if the scheduler preempts.
+	fn ready(&mut self, id: u32, time: SimTime) {
+		self.ready.insert(id, time);
+
+		if !self.is_preemptive() {
+			return;
+		}
+
+		for index in 0..self.running.len() {
+			let Running { cpu, id: running } = self.running[index];
+
+			if self.outranks(id, running) {
+				self.open(id, running, cpu, time);
+			}
+		}
+	}
+
+	fn dispatch(&mut self, id: u32, cpu: usize, time: SimTime) {
+		self.ready.remove(&id);
+		self.close(|inversion| inversion.waiting == id, time);
+
+		let waiting: Vec<u32> = self.ready.keys().copied().collect();
+
+		for waiting in waiting {
+			if self.outranks(waiting, id) {
+				self.open(waiting, id, cpu, time);
+			}
+		}
+
+		self.running.push(Running { cpu, id });
+	}
+
+	fn stop(&mut self, id: u32, cpu: usize, time: SimTime) {
+		self.running
+			.retain(|running| running.cpu != cpu || running.id != id);
+		self.close(
+			|inversion| inversion.cpu == cpu && inversion.running == id,
+			time,
+		);
+	}
+
+	fn open(&mut self, waiting: u32, running: u32, cpu: usize, start: SimTime) {
+		self.open.push(Inversion {
+			waiting,
+			running,
+			cpu,
+			start,
+			end: start,
+		});
+	}
+
+	// Ends the open inversions that match at the given time.
+	fn close(&mut self, matches: impl Fn(&Inversion) -> bool, time: SimTime) {
+		let mut index = 0;
+
+		while index < self.open.len() {
+			if matches(&self.open[index]) {
+				let mut inversion = self.open.remove(index);
+
+				inversion.end = time;
+
+				if !inversion.duration().is_zero() {
+					self.inversions.push(inversion);
+				}
+			} else {
+				index += 1;
+			}
+		}
+	}
+}
+
+/// Finds every priority inversion in the trace of a run of the processes.
+///
+/// The processes give the arrival times and priorities, so they should be
+/// the workload the run started from. Inversions are returned by start time.
+pub fn priority_inversions(
+	trace: &Trace,
+	processes: &[Process],
+	ranking: Ranking,
+) -> Vec<Inversion> {
+	let mut scan = Scan {
+		ranking,
+		priorities: processes
+			.iter()
+			.map(|process| (process.id, process.priority))
+			.collect(),
+		levels: BTreeMap::new(),
+		ready: BTreeMap::new(),
+		running: Vec::new(),
+		open: Vec::new(),
+		inversions: Vec::new(),
+	};
+
+	// Children arrive when they are spawned, which the trace records.
+	let mut arrivals: Vec<(SimTime, u32)> = processes
+		.iter()
+		.filter(|process| process.spawn.is_none())
+		.map(|process| (process.arrival_time, process.id))
+		.collect();
+
+	arrivals.sort_by(|first, second| second.cmp(first));
+
+	for record in &trace.records {
+		// Processes that arrive at the time of a dispatch compete for it.
+		while arrivals
+			.last()
+			.is_some_and(|&(time, _)| time <= record.time)
+		{
+			let (time, id) = arrivals.pop().unwrap();
+
+			scan.ready(id, time);
+		}
+
+		let (time, cpu) = (record.time, record.cpu);
+
+		match record.event {
+			TraceEvent::Dispatch { id } => scan.dispatch(id, cpu, time),
+			TraceEvent::Preempt { id } => {
+				scan.stop(id, cpu, time);
+				scan.ready(id, time);
+			}
+			TraceEvent::Demote { id, level } => {
+				scan.levels.insert(id, level);
+			}
+			TraceEvent::Boost { .. } => scan.levels.clear(),
+			TraceEvent::IoStart { id, .. }
+			| TraceEvent::Finish { id, .. }
+			| TraceEvent::Crash { id, .. } => scan.stop(id, cpu, time),
+			TraceEvent::IoComplete { id } | TraceEvent::Spawn { id, .. } => scan.ready(id, time),
+			TraceEvent::Alarm { .. }
+			| TraceEvent::CpuAdded { .. }
+			| TraceEvent::CpuRemoved { .. }
+			| TraceEvent::Timeout { .. } => {}
+		}
+	}
+
+	let mut inversions = scan.inversions;
+
+	inversions.sort_by_key(|inversion| (inversion.start, inversion.cpu));
+	inversions
+}
+
+/// Counts the pairs of processes that finished in the opposite order of the baseline.
+///
+/// Only processes that finished in both runs are compared, so a run that was
+/// cancelled or crashed processes can still be compared with a full baseline.
+pub fn order_inversions(order: &[u32], baseline: &[u32]) -> u64 {
+	let ranks: BTreeMap<u32, usize> = baseline
+		.iter()
+		.enumerate()
+		.map(|(rank, &id)| (id, rank))
+		.collect();
+
+	let mut order: Vec<usize> = order
+		.iter()
+		.filter_map(|id| ranks.get(id).copied())
+		.collect();
+	let mut buffer = alloc::vec![0; order.len()];
+
+	count_inversions(&mut order, &mut buffer)
+}
+
+// Sorts the ranks with a merge sort, counting each pair it finds out of order.
+fn count_inversions(ranks: &mut [usize], buffer: &mut [usize]) -> u64 {
+	if ranks.len() < 2 {
+		return 0;
+	}
+
+	let middle = ranks.len() / 2;
+	let mut count = count_inversions(&mut ranks[..middle], &mut buffer[..middle])
+		+ count_inversions(&mut ranks[middle..], &mut buffer[middle..]);
+
+	let (mut left, mut right) = (0, middle);
+
+	for slot in buffer[..ranks.len()].iter_mut() {
+		if right == ranks.len() || left < middle && ranks[left] <= ranks[right] {
+			*slot = ranks[left];
+			left += 1;
+		} else {
+			// Every rank left in the first half comes after this one in the baseline.
+			count += (middle - left) as u64;
+			*slot = ranks[right];
+			right += 1;
+		}
+	}
+
+	ranks.copy_from_slice(&buffer[..ranks.len()]);
+	count
+}
@@ -0,0 +1,208 @@
+//! An audit log of every dispatch decision, with the queues it was made from.
+//!
+//! An [`AuditLog`] given to the engine records, for each step, the process the
+//! scheduler dispatched and a snapshot of every queue at that instant: the
+//! ready and waiting processes of each level, and the queues of the I/O
+//! devices and the accelerator. Each decision can then be looked at on its
+//! own after the run, without stepping through it again.
+//!
+//! Snapshots are compressed by storing only the queues that changed since the
+//! previous decision, with every queue stored once per [`KEYFRAME_INTERVAL`]
+//! decisions, so rebuilding one never replays more than that many entries.
+
+use alloc::vec::Vec;
+
+use crate::{
+	accelerator::Accelerator,
+	devices::IoSubsystem,
+	scheduler::{LevelLists, Scheduler},
+	time::SimTime,
+};
+
+/// The number of decisions between snapshots that store every queue.
+pub const KEYFRAME_INTERVAL: usize = 64;
+
+/// A list of processes, each with a time, as the console shows them.
+type Queue = Vec<(u32, SimTime)>;
+
+/// A process the scheduler dispatched, and when and where it ran.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Decision {
+	/// The time the decision was made at, when the CPU became free.
+	pub time: SimTime,
+	pub cpu: usize,
+	pub id: u32,
+	pub start: SimTime,
+	pub end: SimTime,
+}
+
+/// Every queue at the instant of a decision.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct QueueSnapshot {
+	/// The processes waiting in each level, from the first down.
+	pub levels: Vec<LevelLists>,
+	/// The requests queued on each I/O device, with the length of their I/O burst.
+	pub devices: Vec<Queue>,
+	/// The processes queued for the accelerator, with the length of their burst.
+	pub accelerator: Queue,
+}
+
+/// A decision, with the queues that changed since the one before.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Entry {
+	decision: Decision,
+	/// The changed queues by their index, or every queue at a keyframe.
+	changes: Vec<(usize, Queue)>,
+}
+
+/// The dispatch decisions of a run, each with the queues it was made from.
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AuditLog {
+	entries: Vec<Entry>,
+	level_count: usize,
+	device_count: usize,
+	/// The queues of the last decision, which the next one is compared against.
+	///
+	/// Waiting processes are kept with the time they are ready rather than the time left, so
+	/// their entries only change when they move.
+	queues: Vec<Queue>,
+	/// The queues captured for the decision about to be made.
+	#[cfg_attr(feature = "serde", serde(skip))]
+	captured: Vec<Queue>,
+}
+
+impl AuditLog {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	pub fn len(&self) -> usize {
+		self.entries.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.entries.is_empty()
+	}
+
+	pub fn decisions(&self) -> impl Iterator<Item = &Decision> + '_ {
+		self.entries.iter().map(|entry| &entry.decision)
+	}
+
+	// Returns the number of queues stored across every decision, to see how well snapshots compress.
+	pub fn stored_queues(&self) -> usize {
+		self.entries.iter().map(|entry| entry.changes.len()).sum()
+	}
+
+	// Captures every queue at the given time, just before the scheduler makes a decision.
+	pub(crate) fn capture<S: Scheduler>(
+		&mut self,
+		scheduler: &S,
+		io: Option<&IoSubsystem>,
+		accelerator: Option<&Accelerator>,
+		time: SimTime,
+	) {
+		let levels = scheduler.levels(time);
+		let devices = io.map(IoSubsystem::queues).unwrap_or_default();
+
+		self.level_count = levels.len();
+		self.device_count = devices.len();
+		self.captured.clear();
+
+		for level in levels {
+			let io = level
+				.io
+				.into_iter()
+				.map(|(id, remaining)| (id, time + remaining))
+				.collect();
+
+			self.captured.push(io);
+			self.captured.push(level.cpu);
+		}
+
+		self.captured.extend(devices);
+		self.captured
+			.push(accelerator.map(Accelerator::queue).unwrap_or_default());
+	}
+
+	// Records the decision made from the queues captured last.
+	pub(crate) fn record(&mut self, decision: Decision) {
+		let captured = core::mem::take(&mut self.captured);
+		let keyframe = self.entries.len().is_multiple_of(KEYFRAME_INTERVAL)
+			|| captured.len() != self.queues.len();
+
+		let changes = captured
+			.iter()
+			.enumerate()
+			.filter(|&(index, queue)| keyframe || self.queues[index] != *queue)
+			.map(|(index, queue)| (index, queue.clone()))
+			.collect();
+
+		self.queues = captured;
+		self.entries.push(Entry { decision, changes });
+	}
+
+	// Returns a decision with the queues it was made from, rebuilt from the keyframe before it.
+	pub fn decision(&self, index: usize) -> Option<(Decision, QueueSnapshot)> {
+		let entry = self.entries.get(index)?;
+		let keyframe = index - index % KEYFRAME_INTERVAL;
+		let mut queues: Vec<Queue> = Vec::new();
+
+		for entry in &self.entries[keyframe..=index] {
+			for (queue, changes) in &entry.changes {
+				if *queue >= queues.len() {
+					queues.resize(queue + 1, Vec::new());
+				}
+
+				queues[*queue].clone_from(changes);
+			}
+		}
+
+		let time = entry.decision.time;
+		let mut queues = queues.into_iter();
+
+		let levels = (0..self.level_count)
+			.map(|_| LevelLists {
+				io: queues
+					.next()
+					.unwrap_or_default()
+					.into_iter()
+					.map(|(id, ready_at)| (id, ready_at - time))
+					.collect(),
+				cpu: queues.next().unwrap_or_default(),
+			})
+			.collect();
+		let devices = queues.by_ref().take(self.device_count).collect();
+
+		Some((
+			entry.decision,
+			QueueSnapshot {
+				levels,
+				devices,
+				accelerator: queues.next().unwrap_or_default(),
+			},
+		))
+	}
+}
+
+#[cfg(feature = "std")]
+impl AuditLog {
+	/// Saves the audit log as JSON.
+	pub fn save(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+		let file = std::io::BufWriter::new(std::fs::File::create(path)?);
+
+		serde_json::to_writer(file, self)?;
+
+		Ok(())
+	}
+
+	/// Loads an audit log saved as JSON.
+	pub fn load(path: impl AsRef<std::path::Path>) -> std::io::Result<Self> {
+		let file = std::io::BufReader::new(std::fs::File::open(path)?);
+
+		serde_json::from_reader(file)
+			.map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidData, error))
+	}
+}
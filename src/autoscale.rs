@@ -0,0 +1,68 @@
+//! Hooks that watch the length of the ready queue.
+//!
+//! The engine calls a [`QueueHook`] whenever the number of processes waiting
+//! for CPU crosses one of the hook's thresholds, and the hook can answer by
+//! adding or removing a CPU.
+
+use crate::time::SimTime;
+
+/// The ready queue crossing a threshold.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Alarm {
+	pub time: SimTime,
+	pub length: usize,
+	pub threshold: usize,
+	/// Whether the queue grew to the threshold, rather than shrank below it.
+	pub rising: bool,
+	pub cpus: usize,
+}
+
+/// What the engine should do in response to an alarm.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Action {
+	Continue,
+	AddCpu,
+	RemoveCpu,
+}
+
+/// Receives alarms when the ready queue length crosses a threshold.
+pub trait QueueHook: Send {
+	// Returns the queue lengths to watch.
+	fn thresholds(&self) -> &[usize];
+
+	fn on_alarm(&mut self, alarm: &Alarm) -> Action;
+}
+
+/// Adds a CPU when the ready queue gets long and removes one when it gets short.
+pub struct AutoscalePolicy {
+	// The queue lengths at which a CPU is removed and added, in that order.
+	thresholds: [usize; 2],
+	max_cpus: usize,
+}
+
+impl AutoscalePolicy {
+	pub fn new(add_at: usize, remove_below: usize, max_cpus: usize) -> Self {
+		Self {
+			thresholds: [remove_below, add_at],
+			max_cpus,
+		}
+	}
+}
+
+impl QueueHook for AutoscalePolicy {
+	fn thresholds(&self) -> &[usize] {
+		&self.thresholds
+	}
+
+	fn on_alarm(&mut self, alarm: &Alarm) -> Action {
+		let [remove_below, add_at] = self.thresholds;
+
+		if alarm.rising && alarm.threshold == add_at && alarm.cpus < self.max_cpus {
+			Action::AddCpu
+		} else if !alarm.rising && alarm.threshold == remove_below && alarm.cpus > 1 {
+			Action::RemoveCpu
+		} else {
+			Action::Continue
+		}
+	}
+}
@@ -0,0 +1,50 @@
+use std::path::PathBuf;
+
+use clap::Args;
+use mlfq_scheduler_simulator::{audit::AuditLog, console, time::SimTime};
+
+#[derive(Args)]
+pub struct AuditArgs {
+	/// The audit log saved by `run --audit`.
+	#[arg(value_name = "PATH")]
+	path: PathBuf,
+	/// Shows the decisions made at this time with every queue, instead of listing them all.
+	#[arg(long, value_name = "TIME")]
+	at: Option<SimTime>,
+	/// Shows this decision, counting from 1, with every queue.
+	#[arg(long, value_name = "NUMBER", conflicts_with = "at")]
+	decision: Option<usize>,
+}
+
+// Lists the decisions of the audit log, or shows the queues of the ones asked for.
+pub fn run(args: AuditArgs) -> std::io::Result<()> {
+	let audit = AuditLog::load(&args.path)?;
+
+	if let Some(number) = args.decision {
+		match number
+			.checked_sub(1)
+			.and_then(|index| audit.decision(index))
+		{
+			Some((decision, queues)) => {
+				console::show_decision(number - 1, &decision, Some(&queues))
+			}
+			None => eprintln!("The audit log has {} decisions", audit.len()),
+		}
+
+		return Ok(());
+	}
+
+	for (index, decision) in audit.decisions().enumerate() {
+		match args.at {
+			Some(time) if decision.time != time => {}
+			Some(_) => {
+				let queues = audit.decision(index).map(|(_, queues)| queues);
+
+				console::show_decision(index, decision, queues.as_ref());
+			}
+			None => console::show_decision(index, decision, None),
+		}
+	}
+
+	Ok(())
+}
@@ -0,0 +1,191 @@
+use mlfq_scheduler_simulator::{
+	algorithms::LevelPolicy, analysis::order_inversions, cli::Options, console, engine::Summary,
+	process::IoPriority, scheduler::Scheduler, workload::Workload,
+};
+use serde_json::json;
+
+// Prints the metrics of every algorithm in a table, and saves their summaries to `--output` as JSON.
+pub fn run(options: Options) -> std::io::Result<()> {
+	options.init();
+
+	let workload = options.load_workload();
+	let summaries = summaries(&options, &workload);
+
+	console::show_comparison_header();
+
+	// FCFS runs first, so every algorithm is compared with its order.
+	let fcfs = &summaries[0].1.completion_order;
+
+	for (name, summary) in &summaries {
+		let inversions = order_inversions(&summary.completion_order, fcfs);
+
+		console::show_comparison_row(name, summary, inversions);
+	}
+
+	show_phases(&summaries);
+
+	if let (Some(_), Some(without)) = (options.devices, without_io_priority(&workload)) {
+		show_io_priority(&workload, &summaries, &self::summaries(&options, &without));
+	}
+
+	options.save_json(&summaries_json(&summaries))
+}
+
+// Runs every algorithm on the workload, returning the summary of each with its name.
+pub fn summaries(options: &Options, workload: &Workload) -> Vec<(&'static str, Summary)> {
+	let policies = [
+		("First Come First Serve", LevelPolicy::FirstComeFirstServe),
+		(
+			"Shortest Job First",
+			LevelPolicy::ShortestJobFirst { predicted: false },
+		),
+		(
+			"Shortest Job First (predicted)",
+			LevelPolicy::ShortestJobFirst { predicted: true },
+		),
+		(
+			"Priority",
+			LevelPolicy::Priority {
+				preemptive: options.preemptive,
+				aging: options.aging,
+			},
+		),
+		(
+			"Lottery",
+			LevelPolicy::Lottery {
+				quanta: options.quantum(),
+				seed: options.seed,
+			},
+		),
+		(
+			"Stride",
+			LevelPolicy::Stride {
+				quanta: options.quantum(),
+			},
+		),
+	];
+
+	let mut summaries = Vec::new();
+
+	for (name, policy) in policies {
+		let mut scheduler = policy.build();
+
+		for process in workload.clone().into_processes() {
+			scheduler.push(process);
+		}
+
+		let mut engine = options.engine(scheduler);
+
+		engine.run();
+		summaries.push((name, engine.summary().clone()));
+	}
+
+	let mut engine = options.engine(options.mlfq(workload.clone().into_processes()));
+
+	engine.run();
+	summaries.push(("Multi Level Feedback Queue", engine.summary().clone()));
+
+	summaries
+}
+
+// Prints how the bursts of each tag fared under every algorithm, if the workload tags any.
+fn show_phases(summaries: &[(&str, Summary)]) {
+	// List the tags in the order they first finished under any algorithm.
+	let mut tags: Vec<&str> = Vec::new();
+
+	for (_, summary) in summaries {
+		for phase in &summary.phases {
+			if !tags.contains(&phase.tag.as_str()) {
+				tags.push(&phase.tag);
+			}
+		}
+	}
+
+	if tags.is_empty() {
+		return;
+	}
+
+	println!();
+	console::show_phase_header();
+
+	for &tag in &tags {
+		for (name, summary) in summaries {
+			if let Some(phase) = summary.phase(tag) {
+				console::show_phase_row(name, phase);
+			}
+		}
+	}
+}
+
+// Returns the workload with every process at best-effort I/O priority, or none if every process already is.
+pub fn without_io_priority(workload: &Workload) -> Option<Workload> {
+	if workload
+		.processes()
+		.iter()
+		.all(|process| process.io_priority == IoPriority::BestEffort)
+	{
+		return None;
+	}
+
+	let mut without = workload.clone();
+
+	without.set_io_priority(IoPriority::BestEffort);
+
+	Some(without)
+}
+
+// Returns the classes of the workload in the order they first appear.
+pub fn classes(workload: &Workload) -> Vec<&str> {
+	let mut classes: Vec<&str> = Vec::new();
+
+	for process in workload.processes() {
+		if let Some(class) = &process.class {
+			if !classes.contains(&class.as_str()) {
+				classes.push(class);
+			}
+		}
+	}
+
+	classes
+}
+
+// Prints how I/O priority changed the average turnaround under every algorithm, overall and for each class.
+pub fn show_io_priority(
+	workload: &Workload,
+	with: &[(&str, Summary)],
+	without: &[(&str, Summary)],
+) {
+	println!();
+	println!("Turnaround with and without I/O priority");
+	console::show_io_priority_header();
+
+	for ((name, with), (_, without)) in with.iter().zip(without) {
+		console::show_io_priority_row(
+			"all",
+			name,
+			with.turnaround_average(),
+			without.turnaround_average(),
+		);
+	}
+
+	for class in classes(workload) {
+		for ((name, with), (_, without)) in with.iter().zip(without) {
+			if let (Some(with), Some(without)) = (with.class(class), without.class(class)) {
+				console::show_io_priority_row(
+					class,
+					name,
+					with.turnaround_average(),
+					without.turnaround_average(),
+				);
+			}
+		}
+	}
+}
+
+// Lists each summary with the name of its algorithm.
+pub fn summaries_json(summaries: &[(&str, Summary)]) -> serde_json::Value {
+	summaries
+		.iter()
+		.map(|(name, summary)| json!({ "algorithm": name, "summary": summary }))
+		.collect()
+}
@@ -0,0 +1,131 @@
+use std::collections::VecDeque;
+
+use mlfq_scheduler_simulator::{
+	algorithms::{LevelPolicy, MultiLevelFeedbackQueue},
+	analysis::order_inversions,
+	cli::Options,
+	console,
+	engine::Summary,
+	scheduler::Scheduler,
+	workload::{load_desktop_workload, Workload},
+};
+
+use crate::compare;
+
+// Prints the metrics of each algorithm overall and by class, and saves their summaries to `--output` as JSON.
+pub fn run(mut options: Options) -> std::io::Result<()> {
+	options.init();
+
+	// The mix is about contention, so the processes share a disk unless told otherwise.
+	options.devices.get_or_insert(1);
+
+	let workload = load_desktop_workload();
+
+	let summaries = summaries(&options, &workload);
+
+	println!(
+		"Desktop mix: a shell and an editor that spend most of their time waiting on the user,"
+	);
+	println!(
+		"two compiler jobs with long CPU bursts, and a disk indexer, on {} I/O device(s).",
+		options.devices.unwrap()
+	);
+	println!();
+
+	println!("Overall");
+	console::show_comparison_header();
+
+	// FCFS runs first, so every algorithm is compared with its order.
+	let fcfs = &summaries[0].1.completion_order;
+
+	for (name, summary) in &summaries {
+		let inversions = order_inversions(&summary.completion_order, fcfs);
+
+		console::show_comparison_row(name, summary, inversions);
+	}
+
+	println!();
+	println!("By class");
+	console::show_class_header();
+
+	let classes = compare::classes(&workload);
+
+	for &class in &classes {
+		for (name, summary) in &summaries {
+			if let Some(class) = summary.class(class) {
+				console::show_class_row(name, class);
+			}
+		}
+	}
+
+	if let Some(without) = compare::without_io_priority(&workload) {
+		compare::show_io_priority(&workload, &summaries, &self::summaries(&options, &without));
+	}
+
+	println!();
+	println!("Takeaways");
+
+	// Point out which algorithm kept each class waiting the least, and which the most.
+	for &class in &classes {
+		let waiting: Vec<_> = summaries
+			.iter()
+			.filter_map(|(name, summary)| {
+				summary
+					.class(class)
+					.map(|class| (*name, class.waiting_average()))
+			})
+			.collect();
+
+		let best = waiting.iter().min_by(|a, b| a.1.total_cmp(&b.1)).unwrap();
+		let worst = waiting.iter().max_by(|a, b| a.1.total_cmp(&b.1)).unwrap();
+
+		println!(
+			"- The {class} waited least under {} ({:.2} on average) and most under {} ({:.2}).",
+			best.0, best.1, worst.0, worst.1
+		);
+	}
+
+	options.save_json(&compare::summaries_json(&summaries))
+}
+
+// Runs FCFS, round robin, and the MLFQ on the workload, returning the summary of each with its name.
+fn summaries(options: &Options, workload: &Workload) -> Vec<(&'static str, Summary)> {
+	let schedulers: [(&str, Box<dyn Scheduler>); 3] = [
+		(
+			"First Come First Serve",
+			Box::new(LevelPolicy::FirstComeFirstServe.build()),
+		),
+		(
+			"Round Robin",
+			Box::new(
+				MultiLevelFeedbackQueue::with_levels(
+					VecDeque::new(),
+					&[LevelPolicy::RoundRobin {
+						quanta: options.quantum(),
+					}],
+				)
+				.with_donation(options.donate),
+			),
+		),
+		(
+			"Multi Level Feedback Queue",
+			Box::new(options.mlfq(Vec::new())),
+		),
+	];
+
+	let mut summaries = Vec::new();
+
+	for (name, mut scheduler) in schedulers {
+		for process in workload.clone().into_processes() {
+			scheduler.push(process);
+		}
+
+		let mut engine = options.engine(scheduler);
+
+		engine.run();
+
+		summaries.push((name, engine.summary().clone()));
+	}
+
+	summaries
+}
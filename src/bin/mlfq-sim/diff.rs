@@ -0,0 +1,80 @@
+use clap::{Args, Parser};
+use mlfq_scheduler_simulator::{
+	cli::Options,
+	diff::ReportDiff,
+	report::Report,
+	trace::{Trace, TraceIndex, TraceRecorder},
+};
+
+#[derive(Args)]
+pub struct DiffArgs {
+	/// The options of the first run, `vs`, and the options the second run changes.
+	#[arg(
+		value_name = "OPTIONS",
+		trailing_var_arg = true,
+		allow_hyphen_values = true,
+		required = true
+	)]
+	args: Vec<String>,
+}
+
+/// The options of one side of a diff, where a later option overrides an earlier one.
+#[derive(Parser)]
+#[command(name = "mlfq-sim diff", args_override_self = true)]
+struct Side {
+	#[command(flatten)]
+	options: Options,
+}
+
+// Writes the changelog to `--output`, or to stdout.
+pub fn run(args: DiffArgs) -> std::io::Result<()> {
+	let args = args.args;
+
+	let Some(split) = args.iter().position(|arg| arg == "vs") else {
+		eprintln!("Usage: mlfq-sim diff <options> vs <options>");
+		std::process::exit(2);
+	};
+
+	// The second run starts from the options of the first, so only the changes need repeating.
+	let before = parse(&args[..split]);
+	let mut after = parse(&[&args[..split], &args[split + 1..]].concat());
+
+	// Both runs stop on the same Ctrl-C.
+	after.cancellation = before.cancellation.clone();
+	before.init();
+
+	let (before_report, before_trace) = simulate(&before);
+	let (after_report, after_trace) = simulate(&after);
+	let changelog = ReportDiff::new(&before_report, &after_report)
+		.with_traces(
+			&TraceIndex::new(&before_trace),
+			&TraceIndex::new(&after_trace),
+		)
+		.to_string();
+
+	match &after.output {
+		Some(path) => std::fs::write(path, changelog),
+		None => {
+			print!("{changelog}");
+
+			Ok(())
+		}
+	}
+}
+
+fn parse(args: &[String]) -> Options {
+	let args = std::iter::once("mlfq-sim diff").chain(args.iter().map(String::as_str));
+
+	Side::parse_from(args).options
+}
+
+// Runs the MLFQ with the options, returning its report and its trace.
+fn simulate(options: &Options) -> (Report, Trace) {
+	let processes = options.load_workload().into_processes();
+	let mut engine = options
+		.engine(options.mlfq(processes))
+		.with_recorder(TraceRecorder::new());
+	let report = Report::run(&mut engine);
+
+	(report, engine.recorder().unwrap().trace())
+}
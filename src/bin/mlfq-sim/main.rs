@@ -0,0 +1,48 @@
+mod audit;
+mod compare;
+mod desktop_mix;
+mod diff;
+mod query;
+mod run;
+mod sweep;
+
+use clap::{Parser, Subcommand};
+use mlfq_scheduler_simulator::cli::Options;
+
+/// Simulates CPU scheduling algorithms on a workload of processes.
+#[derive(Parser)]
+#[command(name = "mlfq-sim", version)]
+struct Cli {
+	#[command(subcommand)]
+	command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+	/// Runs one algorithm, printing the queues at each step and the summary.
+	Run(run::RunArgs),
+	/// Runs every algorithm on the same workload and prints their metrics side by side.
+	Compare(Options),
+	/// Runs the MLFQ for every combination of the swept parameters and prints the metrics as CSV.
+	Sweep(sweep::SweepArgs),
+	/// Runs the MLFQ with two sets of options separated by `vs` and summarizes how the second run differs.
+	Diff(diff::DiffArgs),
+	/// Runs FCFS, RR, and the MLFQ on the desktop mix and walks through how each class of program fared.
+	DesktopMix(Options),
+	/// Lists the dispatch decisions of an audit log, or shows the queues each was made from.
+	Audit(audit::AuditArgs),
+	/// Shows what was running at a time of a saved trace, or the events of one process.
+	Query(query::QueryArgs),
+}
+
+fn main() -> std::io::Result<()> {
+	match Cli::parse().command {
+		Command::Run(args) => run::run(args),
+		Command::Compare(options) => compare::run(options),
+		Command::Sweep(args) => sweep::run(args),
+		Command::Diff(args) => diff::run(args),
+		Command::DesktopMix(options) => desktop_mix::run(options),
+		Command::Audit(args) => audit::run(args),
+		Command::Query(args) => query::run(args),
+	}
+}
@@ -0,0 +1,48 @@
+use std::path::PathBuf;
+
+use clap::Args;
+use mlfq_scheduler_simulator::{
+	console,
+	time::SimTime,
+	trace::{Trace, TraceIndex},
+};
+
+#[derive(Args)]
+pub struct QueryArgs {
+	/// The trace saved by `run --trace`.
+	#[arg(value_name = "PATH")]
+	path: PathBuf,
+	/// Shows what each CPU was running at this time.
+	#[arg(long, value_name = "TIME", required_unless_present = "process")]
+	at: Option<SimTime>,
+	/// Shows the events of this process.
+	#[arg(long, value_name = "ID")]
+	process: Option<u32>,
+	/// Leaves out the events of the process before this time.
+	#[arg(long, value_name = "TIME", requires = "process")]
+	from: Option<SimTime>,
+	/// Leaves out the events of the process from this time on.
+	#[arg(long, value_name = "TIME", requires = "process")]
+	to: Option<SimTime>,
+}
+
+// Prints the answers to the queries about the trace.
+pub fn run(args: QueryArgs) -> std::io::Result<()> {
+	let trace = Trace::load(&args.path)?;
+	let index = TraceIndex::new(&trace);
+
+	if let Some(time) = args.at {
+		console::show_running(time, &index.running_at(time));
+	}
+
+	if let Some(id) = args.process {
+		let range = args.from.unwrap_or(SimTime::ZERO)..args.to.unwrap_or(SimTime::MAX);
+		let multi_cpu = trace.records.iter().any(|record| record.cpu > 0);
+
+		for record in index.process_events(id, range) {
+			console::show_record(record, multi_cpu);
+		}
+	}
+
+	Ok(())
+}
@@ -0,0 +1,306 @@
+use std::{
+	collections::VecDeque,
+	path::{Path, PathBuf},
+};
+
+use clap::{Args, ValueEnum};
+use mlfq_scheduler_simulator::{
+	algorithms::{
+		EarliestDeadlineFirst, FirstComeFirstServe, LevelPolicy, Lottery, MultiLevelFeedbackQueue,
+		PriorityScheduler, RateMonotonic, ShortestJobFirst, SoftRealTime, Stride,
+	},
+	analysis::{order_inversions, Ranking},
+	cli::Options,
+	console,
+	engine::{Engine, Summary},
+	experiments::{self, WorkloadResult},
+	schedulability::Schedulability,
+	scheduler::Scheduler,
+	workload::Workload,
+};
+
+use crate::compare;
+
+#[derive(Args)]
+pub struct RunArgs {
+	/// The scheduling algorithm to simulate.
+	#[arg(short, long, value_enum, default_value_t = Algorithm::Mlfq)]
+	algorithm: Algorithm,
+	/// Runs the algorithm over every workload file in this directory instead, ranking the workloads by how much the algorithms of `compare` differ on them.
+	#[arg(
+		long,
+		value_name = "PATH",
+		conflicts_with_all = ["workload", "arrivals", "replay", "tui", "interactive", "serve", "inversions"]
+	)]
+	workload_dir: Option<PathBuf>,
+
+	#[command(flatten)]
+	options: Options,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Algorithm {
+	/// First come first serve.
+	Fcfs,
+	/// Shortest job first, on predicted burst lengths with `--predict`.
+	Sjf,
+	/// Static priorities, with `--preemptive` and `--aging`.
+	Priority,
+	/// Round robin with the first of `--quanta`, and `--donate`.
+	Rr,
+	/// Lottery scheduling with the first of `--quanta`.
+	Lottery,
+	/// Stride scheduling with the first of `--quanta`.
+	Stride,
+	/// Soft real-time processes first, by deadline-miss budget and then deadline, with the first of `--quanta`.
+	SoftRt,
+	/// Earliest deadline first, preempting for earlier deadlines.
+	Edf,
+	/// Rate monotonic, preempting for shorter periods.
+	Rm,
+	/// The multi level feedback queue, with `--levels` or `--quanta`, `--donate`, `--batch`, and `--boost`.
+	Mlfq,
+}
+
+pub fn run(args: RunArgs) -> std::io::Result<()> {
+	let RunArgs {
+		algorithm,
+		workload_dir,
+		options,
+	} = args;
+
+	options.init();
+
+	if let Some(dir) = &workload_dir {
+		return run_batch(algorithm, &options, dir);
+	}
+
+	let ranking = match algorithm {
+		Algorithm::Priority => Some(Ranking::Priority {
+			preemptive: options.preemptive,
+		}),
+		Algorithm::Rr | Algorithm::Mlfq => Some(Ranking::Levels),
+		_ => None,
+	};
+
+	if options.inversions && ranking.is_none() {
+		eprintln!("`--inversions` needs the priority, rr, or mlfq algorithm");
+		std::process::exit(2);
+	}
+
+	let workload = options.load_workload();
+	let processes = workload.clone().into_processes();
+
+	// The summary is only meaningful once the run has finished or been cancelled.
+	match algorithm {
+		Algorithm::Fcfs => {
+			let mut engine = options.engine(FirstComeFirstServe::from_processes(processes));
+
+			if options.run(&mut engine)? {
+				println!("Total time: {}", engine.summary().total_time);
+				show_summary(&options, &workload, engine.summary());
+			}
+		}
+		Algorithm::Sjf => {
+			let mut engine = options.engine(
+				ShortestJobFirst::from_processes(processes).with_prediction(options.predicted),
+			);
+
+			if options.run(&mut engine)? {
+				show_summary(&options, &workload, engine.summary());
+			}
+		}
+		Algorithm::Priority => {
+			let scheduler = PriorityScheduler::from_processes(processes, options.preemptive)
+				.with_aging(options.aging);
+			let mut engine = options.engine(scheduler);
+
+			if options.run(&mut engine)? {
+				show_summary(&options, &workload, engine.summary());
+			}
+
+			options.show_inversions(&engine, workload.processes(), ranking.unwrap())?;
+		}
+		Algorithm::Rr => {
+			let level = LevelPolicy::RoundRobin {
+				quanta: options.quantum(),
+			};
+			let scheduler =
+				MultiLevelFeedbackQueue::with_levels(VecDeque::from(processes), &[level])
+					.with_donation(options.donate);
+			let mut engine = options.engine(scheduler);
+
+			if options.run(&mut engine)? {
+				show_summary(&options, &workload, engine.summary());
+			}
+
+			options.show_inversions(&engine, workload.processes(), ranking.unwrap())?;
+		}
+		Algorithm::Lottery => {
+			let mut engine = options.engine(Lottery::from_processes(
+				processes,
+				options.quantum(),
+				options.seed,
+			));
+
+			if options.run(&mut engine)? {
+				show_summary(&options, &workload, engine.summary());
+			}
+		}
+		Algorithm::Stride => {
+			let mut engine = options.engine(Stride::from_processes(processes, options.quantum()));
+
+			if options.run(&mut engine)? {
+				show_summary(&options, &workload, engine.summary());
+			}
+		}
+		Algorithm::SoftRt => {
+			let mut engine =
+				options.engine(SoftRealTime::from_processes(processes, options.quantum()));
+
+			if options.run(&mut engine)? {
+				show_summary(&options, &workload, engine.summary());
+			}
+		}
+		Algorithm::Edf => {
+			let mut engine = options.engine(EarliestDeadlineFirst::from_processes(processes));
+
+			if options.run(&mut engine)? {
+				show_summary(&options, &workload, engine.summary());
+			}
+
+			show_schedulability(&workload);
+		}
+		Algorithm::Rm => {
+			let mut engine = options.engine(RateMonotonic::from_processes(processes));
+
+			if options.run(&mut engine)? {
+				show_summary(&options, &workload, engine.summary());
+			}
+
+			show_schedulability(&workload);
+		}
+		Algorithm::Mlfq => {
+			let mut engine = options.engine(options.mlfq(processes));
+
+			if options.run(&mut engine)? {
+				show_summary(&options, &workload, engine.summary());
+				console::show_boosts(engine.scheduler().boosts());
+
+				if let Some(batching) = engine.scheduler().batching() {
+					console::show_batching(batching);
+				}
+			}
+
+			options.show_inversions(&engine, workload.processes(), ranking.unwrap())?;
+		}
+	}
+
+	Ok(())
+}
+
+// Prints the summary, followed by the order the processes finished in compared with FCFS on the same workload.
+fn show_summary(options: &Options, workload: &Workload, summary: &Summary) {
+	console::show_summary(summary);
+
+	// A cancelled run cannot run FCFS to completion for the comparison.
+	let inversions = summary.cancelled_at.is_none().then(|| {
+		let fcfs = summarize(Algorithm::Fcfs, options, workload);
+
+		order_inversions(&summary.completion_order, &fcfs.completion_order)
+	});
+
+	console::show_completion_order(&summary.completion_order, inversions);
+}
+
+// Prints whether the periodic processes of the workload can meet their deadlines, if it has any.
+fn show_schedulability(workload: &Workload) {
+	if let Some(schedulability) = Schedulability::analyze(workload.processes()) {
+		console::show_schedulability(&schedulability);
+	}
+}
+
+// Runs the algorithm over every workload in the directory and prints the workloads from the one the
+// algorithms differ on most, saving the results to `--output` as JSON.
+fn run_batch(algorithm: Algorithm, options: &Options, dir: &Path) -> std::io::Result<()> {
+	let mut paths = std::fs::read_dir(dir)?
+		.map(|entry| entry.map(|entry| entry.path()))
+		.collect::<std::io::Result<Vec<_>>>()?;
+
+	// Only workload files, so notes kept next to them are left out.
+	paths.retain(|path| {
+		path.extension()
+			.is_some_and(|extension| extension == "txt" || extension == "json")
+	});
+	paths.sort();
+
+	let mut results: Vec<WorkloadResult> = paths
+		.iter()
+		.map(|path| {
+			let workload = options.load_workload_file(path);
+			let turnaround_averages = compare::summaries(options, &workload)
+				.into_iter()
+				.map(|(name, summary)| (name.to_string(), summary.turnaround_average()))
+				.collect();
+
+			WorkloadResult {
+				name: path.file_name().unwrap().to_string_lossy().into_owned(),
+				summary: summarize(algorithm, options, &workload),
+				turnaround_averages,
+			}
+		})
+		.collect();
+
+	experiments::rank_by_sensitivity(&mut results);
+
+	console::show_workload_header();
+
+	for result in &results {
+		console::show_workload_row(result);
+	}
+
+	options.save_json(&results)
+}
+
+// Runs the algorithm on the workload without printing it, returning the summary.
+fn summarize(algorithm: Algorithm, options: &Options, workload: &Workload) -> Summary {
+	fn finish<S: Scheduler>(mut engine: Engine<S>) -> Summary {
+		engine.run();
+		engine.summary().clone()
+	}
+
+	let processes = workload.clone().into_processes();
+	let quantum = options.quantum();
+
+	match algorithm {
+		Algorithm::Fcfs => finish(options.engine(FirstComeFirstServe::from_processes(processes))),
+		Algorithm::Sjf => finish(options.engine(
+			ShortestJobFirst::from_processes(processes).with_prediction(options.predicted),
+		)),
+		Algorithm::Priority => finish(
+			options.engine(
+				PriorityScheduler::from_processes(processes, options.preemptive)
+					.with_aging(options.aging),
+			),
+		),
+		Algorithm::Rr => finish(
+			options.engine(
+				MultiLevelFeedbackQueue::with_levels(
+					VecDeque::from(processes),
+					&[LevelPolicy::RoundRobin { quanta: quantum }],
+				)
+				.with_donation(options.donate),
+			),
+		),
+		Algorithm::Lottery => {
+			finish(options.engine(Lottery::from_processes(processes, quantum, options.seed)))
+		}
+		Algorithm::Stride => finish(options.engine(Stride::from_processes(processes, quantum))),
+		Algorithm::SoftRt => {
+			finish(options.engine(SoftRealTime::from_processes(processes, quantum)))
+		}
+		Algorithm::Edf => finish(options.engine(EarliestDeadlineFirst::from_processes(processes))),
+		Algorithm::Rm => finish(options.engine(RateMonotonic::from_processes(processes))),
+		Algorithm::Mlfq => finish(options.engine(options.mlfq(processes))),
+	}
+}
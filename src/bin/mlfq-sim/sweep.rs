@@ -0,0 +1,68 @@
+use clap::{error::ErrorKind, Args};
+use mlfq_scheduler_simulator::{cli, cli::Options, experiments, experiments::Sweep, time::SimTime};
+
+#[derive(Args)]
+pub struct SweepArgs {
+	/// The quanta of level 1, doubling on each level below.
+	#[arg(long, value_delimiter = ',', default_value = "5", value_parser = cli::parse_positive_time)]
+	sweep_quanta: Vec<SimTime>,
+	/// The boost intervals, where `none` disables boosting.
+	#[arg(long, value_delimiter = ',', default_value = "none", value_parser = parse_boost)]
+	sweep_boost: Vec<Option<SimTime>>,
+	/// The numbers of levels, the last being FCFS.
+	#[arg(long, value_delimiter = ',', default_value = "3", value_parser = cli::parse_count)]
+	sweep_levels: Vec<usize>,
+
+	#[command(flatten)]
+	options: Options,
+}
+
+// Writes the metrics of each configuration as CSV to `--output`, or to stdout.
+pub fn run(args: SweepArgs) -> std::io::Result<()> {
+	let SweepArgs {
+		sweep_quanta,
+		sweep_boost,
+		sweep_levels,
+		options,
+	} = args;
+
+	options.init();
+
+	let sweep = Sweep {
+		quanta: sweep_quanta,
+		boost_intervals: sweep_boost,
+		level_counts: sweep_levels,
+	};
+
+	if let Err(message) = sweep.check() {
+		clap::Error::raw(ErrorKind::ValueValidation, format!("{message}\n")).exit();
+	}
+	let workload = options.load_workload();
+
+	let results = sweep.run(&workload, |scheduler| {
+		let mut engine = options.engine(scheduler);
+
+		engine.run();
+		engine.summary().clone()
+	});
+
+	let csv = experiments::to_csv(&results);
+
+	match &options.output {
+		Some(path) => std::fs::write(path, csv),
+		None => {
+			print!("{csv}");
+
+			Ok(())
+		}
+	}
+}
+
+fn parse_boost(text: &str) -> Result<Option<SimTime>, String> {
+	match text {
+		"none" => Ok(None),
+		_ => cli::parse_positive_time(text)
+			.map(Some)
+			.map_err(|_| "expected an interval greater than 0 or none".to_string()),
+	}
+}
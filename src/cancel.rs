@@ -0,0 +1,30 @@
+//! Stopping a long run from outside the engine.
+//!
+//! A [`CancellationToken`] is shared between the engine and whoever may stop
+//! it, such as a Ctrl-C handler. The engine checks the token after every step,
+//! so a cancelled run always stops between steps, with its metrics covering
+//! the processes that finished before then.
+
+use alloc::sync::Arc;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+/// A flag that asks a run to stop, shared by every clone.
+#[derive(Clone, Default, Debug)]
+pub struct CancellationToken {
+	cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	// Asks every engine holding a clone of the token to stop after its current step.
+	pub fn cancel(&self) {
+		self.cancelled.store(true, Ordering::Relaxed);
+	}
+
+	pub fn is_cancelled(&self) -> bool {
+		self.cancelled.load(Ordering::Relaxed)
+	}
+}
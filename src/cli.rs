@@ -0,0 +1,400 @@
+use std::{
+	io,
+	net::SocketAddr,
+	path::{Path, PathBuf},
+};
+
+use clap::Args;
+use serde::Serialize;
+use tracing::Level;
+use tracing_subscriber::{filter::Targets, fmt, prelude::*};
+
+use crate::{
+	accelerator::{Accelerator, AcceleratorPolicy},
+	algorithms::{LevelPolicy, MultiLevelFeedbackQueue, DEFAULT_LEVELS},
+	analysis::{priority_inversions, Ranking},
+	audit::AuditLog,
+	autoscale::AutoscalePolicy,
+	cancel::CancellationToken,
+	console,
+	devices::{IoPolicy, IoSubsystem},
+	engine::Engine,
+	faults::FaultInjector,
+	generator::{ArrivalRate, Generator},
+	interactive,
+	metrics::MetricsPolicy,
+	process::{BurstPredictor, Process, DEFAULT_ESTIMATE},
+	scheduler::Scheduler,
+	serve::Server,
+	time::SimTime,
+	trace::{Trace, TraceRecorder},
+	workload::{load_test_workload, Workload},
+};
+
+/// The command line options shared by every command.
+#[derive(Args, Debug)]
+pub struct Options {
+	/// Pauses after each dispatch for commands.
+	#[arg(long, conflicts_with_all = ["tui", "quiet"])]
+	pub interactive: bool,
+	/// Shows the simulation in a terminal UI (requires the `tui` feature).
+	#[arg(long, conflicts_with = "quiet")]
+	pub tui: bool,
+	/// Streams each step and the metrics so far as JSON lines to clients of this address, once the first connects.
+	#[arg(long, value_name = "ADDRESS", conflicts_with_all = ["tui", "interactive"])]
+	pub serve: Option<SocketAddr>,
+	/// Simulated time units per second in the terminal UI and when serving.
+	#[arg(long, default_value_t = 20.0)]
+	pub speed: f64,
+	/// Logs every component at the debug level, unless `--log` picks the levels.
+	#[arg(short, long, conflicts_with = "quiet")]
+	pub verbose: bool,
+	/// Prints only the summary, without the queues at each step.
+	#[arg(short, long)]
+	pub quiet: bool,
+	/// The log level of each component, such as `mlfq=debug,io=warn`.
+	#[arg(long, value_name = "LEVELS")]
+	pub log: Option<Targets>,
+	/// Saves a trace of the run to this file.
+	#[arg(long, value_name = "PATH")]
+	pub trace: Option<PathBuf>,
+	/// Saves every dispatch decision with a snapshot of all queues to this file, for `audit`.
+	#[arg(long, value_name = "PATH")]
+	pub audit: Option<PathBuf>,
+	/// Saves the results to this file: the summary as JSON for `run`, the summary of each algorithm as JSON for `compare` and `desktop-mix`, the metrics as CSV for `sweep`, and the changelog for `diff`.
+	#[arg(short, long, value_name = "PATH")]
+	pub output: Option<PathBuf>,
+	/// Prints the priority inversions found in the trace of the run.
+	#[arg(long)]
+	pub inversions: bool,
+	/// Prints this trace instead of running a simulation.
+	#[arg(long, value_name = "PATH")]
+	pub replay: Option<PathBuf>,
+
+	/// The workload file, or the built-in test processes if neither it nor `--arrivals` is given.
+	#[arg(long, value_name = "PATH", conflicts_with = "arrivals")]
+	pub workload: Option<PathBuf>,
+	/// Generates the workload instead, with arrivals until the duration.
+	#[arg(long, value_name = "RATE")]
+	pub arrivals: Option<ArrivalRate>,
+	#[arg(long, default_value = "1000")]
+	pub duration: SimTime,
+	#[arg(long, default_value_t = 1, value_parser = parse_count)]
+	pub cpus: usize,
+	/// The queue lengths to add and remove a CPU at, and the most CPUs to use.
+	#[arg(long, value_name = "ADD_AT:REMOVE_BELOW:MAX_CPUS", value_parser = parse_autoscale)]
+	pub autoscale: Option<(usize, usize, usize)>,
+	/// The number of I/O devices, or none for I/O without contention.
+	#[arg(long, value_parser = parse_count)]
+	pub devices: Option<usize>,
+	/// The order queued I/O requests are served in: `fcfs` or `sstf`.
+	#[arg(long, default_value = "fcfs")]
+	pub io_policy: IoPolicy,
+	/// The number of accelerator units, or none for accelerator bursts without contention.
+	#[arg(long, value_parser = parse_count)]
+	pub accelerators: Option<usize>,
+	/// The order queued accelerator bursts are served in: `fcfs` or `sjf`.
+	#[arg(long, default_value = "fcfs")]
+	pub accelerator_policy: AcceleratorPolicy,
+	/// The chance of a process crashing at the end of each burst.
+	#[arg(long, value_name = "PROBABILITY", value_parser = parse_fraction)]
+	pub crash_rate: Option<f64>,
+
+	/// The MLFQ levels from the highest down, such as `rr:5,priority:aging=20,fcfs`.
+	#[arg(long, value_delimiter = ',', conflicts_with = "quanta")]
+	pub levels: Option<Vec<LevelPolicy>>,
+	/// Gives the rest of a round robin quanta to the next process in the level when a process yields it early.
+	#[arg(long)]
+	pub donate: bool,
+	/// Moves every process back to the first MLFQ level once per interval.
+	#[arg(long = "boost", value_name = "INTERVAL", value_parser = parse_positive_time)]
+	pub boost_interval: Option<SimTime>,
+	/// Runs the processes ready in the last MLFQ level back-to-back, without going back to higher levels until the batch is done or a boost.
+	#[arg(long)]
+	pub batch: bool,
+	/// Lets a higher priority process preempt the running one.
+	#[arg(long)]
+	pub preemptive: bool,
+	/// Raises the priority of waiting processes once per interval.
+	#[arg(long, value_name = "INTERVAL")]
+	pub aging: Option<SimTime>,
+	/// The time quanta of the round robin MLFQ levels, above a final FCFS level; the first one is also used by round robin, lottery, stride, and soft real-time scheduling.
+	#[arg(long, value_delimiter = ',', value_parser = parse_positive_time)]
+	pub quanta: Vec<SimTime>,
+	#[arg(long, default_value_t = 1)]
+	pub seed: u64,
+	/// Schedules shortest-job-first on predicted instead of actual burst lengths.
+	#[arg(long = "predict")]
+	pub predicted: bool,
+	/// The definition of each metric where textbooks differ, such as `response=per-burst,waiting=turnaround-less-cpu,turnaround=per-burst`.
+	#[arg(long, value_name = "DEFINITIONS")]
+	pub metrics: Option<MetricsPolicy>,
+	/// The weight of the latest burst in each burst prediction.
+	#[arg(long, value_parser = parse_fraction)]
+	pub alpha: Option<f64>,
+
+	/// Cancelled on Ctrl-C, stopping every engine created from the options.
+	#[arg(skip)]
+	pub cancellation: CancellationToken,
+}
+
+impl Options {
+	// Sets up logging and Ctrl-C for a command.
+	pub fn init(&self) {
+		self.init_logging();
+		self.handle_interrupts();
+	}
+
+	// Cancels the run on the first Ctrl-C, so it can stop and write its files, and exits on the second.
+	fn handle_interrupts(&self) {
+		let token = self.cancellation.clone();
+
+		let result = ctrlc::set_handler(move || {
+			if token.is_cancelled() {
+				std::process::exit(130);
+			}
+
+			token.cancel();
+		});
+
+		if let Err(error) = result {
+			eprintln!("Failed to handle Ctrl-C: {error}");
+		}
+	}
+
+	// Prints the log events of the selected components to stderr.
+	pub fn init_logging(&self) {
+		let targets = match &self.log {
+			Some(targets) => targets.clone(),
+			None if self.verbose => Targets::new().with_default(Level::DEBUG),
+			None => return,
+		};
+
+		tracing_subscriber::registry()
+			.with(fmt::layer().with_writer(io::stderr).without_time())
+			.with(targets)
+			.init();
+	}
+
+	// Loads the workload file or generates one, or uses the test processes if neither was asked for.
+	pub fn load_workload(&self) -> Workload {
+		let workload = if let Some(rate) = &self.arrivals {
+			Generator::new(rate.clone(), self.duration)
+				.with_seed(self.seed)
+				.generate()
+		} else {
+			match &self.workload {
+				Some(path) => return self.load_workload_file(path),
+				None => load_test_workload(),
+			}
+		};
+
+		self.predict(workload)
+	}
+
+	// Loads a workload file, exiting if it cannot be loaded.
+	pub fn load_workload_file(&self, path: &Path) -> Workload {
+		let workload = Workload::load(path).unwrap_or_else(|error| {
+			exit_with(&format!("Failed to load {}: {error}", path.display()))
+		});
+
+		self.predict(workload)
+	}
+
+	// Sets the burst predictor of `--alpha` on the workload, if it was given.
+	fn predict(&self, mut workload: Workload) -> Workload {
+		if let Some(alpha) = self.alpha {
+			workload.set_burst_predictor(BurstPredictor::new(alpha, DEFAULT_ESTIMATE));
+		}
+
+		workload
+	}
+
+	// Returns the time quanta of round robin, lottery, stride, and soft real-time scheduling: the first of `--quanta`, or 5.
+	pub fn quantum(&self) -> SimTime {
+		self.quanta.first().copied().unwrap_or(SimTime::new(5))
+	}
+
+	// Returns `--levels`, or a round robin level for each of `--quanta` above an FCFS level, or the default levels.
+	pub fn levels(&self) -> Vec<LevelPolicy> {
+		if let Some(levels) = &self.levels {
+			return levels.clone();
+		}
+
+		if self.quanta.is_empty() {
+			return DEFAULT_LEVELS.to_vec();
+		}
+
+		self.quanta
+			.iter()
+			.map(|&quanta| LevelPolicy::RoundRobin { quanta })
+			.chain([LevelPolicy::FirstComeFirstServe])
+			.collect()
+	}
+
+	// Creates the MLFQ with the selected levels, donation, batching, and boosting.
+	pub fn mlfq(&self, processes: Vec<Process>) -> MultiLevelFeedbackQueue {
+		let scheduler = MultiLevelFeedbackQueue::with_levels(processes.into(), &self.levels())
+			.with_donation(self.donate)
+			.with_batching(self.batch);
+
+		match self.boost_interval {
+			Some(boost_interval) => scheduler.with_boost_interval(boost_interval),
+			None => scheduler,
+		}
+	}
+
+	// Creates an engine for the scheduler with the selected CPUs, devices, accelerator, and autoscaling.
+	pub fn engine<S: Scheduler>(&self, scheduler: S) -> Engine<S> {
+		let mut engine = Engine::new(scheduler)
+			.with_cpus(self.cpus)
+			.with_metrics(self.metrics.unwrap_or_default())
+			.with_cancellation(self.cancellation.clone());
+
+		if let Some(count) = self.devices {
+			engine = engine.with_io(IoSubsystem::new(count, self.io_policy).with_seed(self.seed));
+		}
+
+		if let Some(count) = self.accelerators {
+			engine = engine.with_accelerator(Accelerator::new(count, self.accelerator_policy));
+		}
+
+		if self.trace.is_some() || self.inversions {
+			engine = engine.with_recorder(TraceRecorder::new());
+		}
+
+		if self.audit.is_some() {
+			engine = engine.with_audit(AuditLog::new());
+		}
+
+		if let Some(rate) = self.crash_rate {
+			engine = engine.with_faults(FaultInjector::new(rate, self.seed));
+		}
+
+		match self.autoscale {
+			Some((add_at, remove_below, max_cpus)) => engine.with_hook(Box::new(
+				AutoscalePolicy::new(add_at, remove_below, max_cpus),
+			)),
+			None => engine,
+		}
+	}
+
+	// Runs the engine in the selected mode, returning whether every process finished or the run was cancelled.
+	//
+	// When replaying a trace the engine is not run, and this returns false.
+	pub fn run<S: Scheduler>(&self, engine: &mut Engine<S>) -> io::Result<bool> {
+		if let Some(path) = &self.replay {
+			console::replay(&Trace::load(path)?);
+
+			return Ok(false);
+		}
+
+		if self.tui {
+			#[cfg(feature = "tui")]
+			crate::tui::run(engine, self.speed)?;
+
+			#[cfg(not(feature = "tui"))]
+			eprintln!("The terminal UI requires the `tui` feature");
+		} else if self.interactive {
+			interactive::run(engine)?;
+		} else if let Some(address) = self.serve {
+			let mut server = Server::bind(address)?.with_speed(self.speed);
+
+			eprintln!("Waiting for a client on {}", server.local_addr()?);
+			server.wait_for_client()?;
+			engine.run_with(&mut server);
+			server.finish(engine.summary());
+		} else if self.quiet {
+			engine.run();
+		} else {
+			console::run(engine);
+		}
+
+		if let (Some(path), Some(recorder)) = (&self.trace, engine.recorder()) {
+			recorder.trace().save(path)?;
+		}
+
+		if let (Some(path), Some(audit)) = (&self.audit, engine.audit()) {
+			audit.save(path)?;
+		}
+
+		self.save_json(engine.summary())?;
+
+		Ok(engine.is_stopped())
+	}
+
+	// Saves the results to `--output` as JSON, if it was given.
+	pub fn save_json(&self, results: &impl Serialize) -> io::Result<()> {
+		if let Some(path) = &self.output {
+			let file = io::BufWriter::new(std::fs::File::create(path)?);
+
+			serde_json::to_writer_pretty(file, results)?;
+		}
+
+		Ok(())
+	}
+
+	// Prints the priority inversions of the run, or of the replayed trace, if `--inversions` was given.
+	//
+	// The processes are the workload the run started from, which the trace does not record.
+	pub fn show_inversions<S: Scheduler>(
+		&self,
+		engine: &Engine<S>,
+		processes: &[Process],
+		ranking: Ranking,
+	) -> io::Result<()> {
+		if !self.inversions {
+			return Ok(());
+		}
+
+		let trace = match (&self.replay, engine.recorder()) {
+			(Some(path), _) => Trace::load(path)?,
+			(None, Some(recorder)) => recorder.trace(),
+			(None, None) => return Ok(()),
+		};
+
+		console::show_inversions(&priority_inversions(&trace, processes, ranking));
+
+		Ok(())
+	}
+}
+
+// Parses `<add at>:<remove below>:<max cpus>`.
+fn parse_autoscale(text: &str) -> Result<(usize, usize, usize), String> {
+	let limits: Result<Vec<usize>, _> = text.split(':').map(str::parse).collect();
+
+	match limits.as_deref() {
+		Ok(&[add_at, remove_below, max_cpus]) => Ok((add_at, remove_below, max_cpus)),
+		_ => Err("expected <add at>:<remove below>:<max cpus>".to_string()),
+	}
+}
+
+// Parses a time greater than zero.
+pub fn parse_positive_time(text: &str) -> Result<SimTime, String> {
+	match text.parse::<SimTime>() {
+		Ok(time) if !time.is_zero() => Ok(time),
+		_ => Err("expected a time greater than 0".to_string()),
+	}
+}
+
+// Parses a count of at least one.
+pub fn parse_count(text: &str) -> Result<usize, String> {
+	match text.parse() {
+		Ok(count) if count > 0 => Ok(count),
+		_ => Err("expected a count of at least 1".to_string()),
+	}
+}
+
+// Parses a number between 0 and 1.
+fn parse_fraction(text: &str) -> Result<f64, String> {
+	match text.parse() {
+		Ok(value) if (0.0..=1.0).contains(&value) => Ok(value),
+		_ => Err("expected a number between 0 and 1".to_string()),
+	}
+}
+
+fn exit_with(message: &str) -> ! {
+	eprintln!("{message}");
+
+	std::process::exit(2)
+}
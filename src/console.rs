@@ -0,0 +1,638 @@
+use crate::{
+	algorithms::{BatchStats, BoostRecord},
+	analysis::Inversion,
+	audit::{Decision, QueueSnapshot},
+	engine::{ClassSummary, Engine, Step, Summary},
+	experiments::WorkloadResult,
+	metrics::MetricsPolicy,
+	process::{PhaseSummary, Process},
+	schedulability::Schedulability,
+	scheduler::{Event, Scheduler},
+	sink::EventSink,
+	time::SimTime,
+	trace::{Slice, Trace, TraceEvent, TraceRecord},
+};
+
+// Prints the list of processes waiting for IO and CPU.
+pub fn show_lists<S: Scheduler>(scheduler: &S, current_time: SimTime) {
+	// Get the lists of processes that are waiting and sort them by process ID.
+	let mut io_list = scheduler.io_remaining(current_time);
+
+	io_list.sort_unstable_by_key(|data| data.0);
+
+	let mut cpu_list = scheduler.cpu_remaining(current_time);
+
+	cpu_list.sort_unstable_by_key(|data| data.0);
+
+	show_list("IO", &io_list);
+	show_list("CPU", &cpu_list);
+}
+
+// Prints a labelled list of processes if it is not empty.
+pub fn show_list(label: &str, list: &[(u32, SimTime)]) {
+	if !list.is_empty() {
+		print!("{label}: ");
+
+		for (id, time) in list {
+			print!("(P{id} {time}) ");
+		}
+
+		println!();
+	}
+}
+
+// Prints a scheduler event.
+pub fn show_event(event: &Event) {
+	match event {
+		Event::Boost { count } => println!("Boosted {count} processes to level 1"),
+		Event::Start { id, time } => println!("Start P{id} at {time}"),
+		Event::Downgrade { id, level } => println!("Downgraded P{id} to level {level}"),
+		Event::Alarm {
+			time,
+			length,
+			threshold,
+			rising,
+		} => {
+			if *rising {
+				println!("Ready queue reached {length} (threshold {threshold}) at {time}");
+			} else {
+				println!("Ready queue fell to {length} (below {threshold}) at {time}");
+			}
+		}
+		Event::CpuAdded { count } => println!("Added a CPU, now {count}"),
+		Event::CpuRemoved { count } => println!("Removed a CPU, now {count}"),
+		Event::Spawn { parent, id } => println!("P{parent} spawned P{id}"),
+		Event::Crash { id, wasted } => println!("P{id} crashed, wasting {wasted} of CPU time"),
+		Event::Timeout { id, waited, burst } => {
+			println!("P{id} timed out after waiting {waited}, retrying with a burst of {burst}")
+		}
+	}
+}
+
+// Prints the metrics of a finished process, by the given definitions.
+pub fn show_end(process: &Process, metrics: &MetricsPolicy) {
+	println!(
+		"End P{} with Turnaround Time: {}, Waiting Time: {}, Response Time: {}",
+		process.id,
+		metrics.turnaround_time(process),
+		metrics.waiting_time(process),
+		metrics.response_time(process)
+	);
+}
+
+// Prints how many deadlines a finished process met, and how much of its miss budget a soft real-time one used.
+pub fn show_deadlines(process: &Process) {
+	if process.relative_deadline().is_none() {
+		return;
+	}
+
+	print!(
+		"  Deadlines Met: {}, Missed: {}",
+		process.deadlines_met, process.deadlines_missed
+	);
+
+	match process.soft_deadline {
+		Some(soft_deadline) if soft_deadline.tolerance > 0 => println!(
+			", Budget Used: {:.0}% of {}%",
+			process.budget_used() * 100.0,
+			soft_deadline.tolerance
+		),
+		_ => println!(),
+	}
+}
+
+// Prints how many times a finished process with a timeout retried a burst.
+pub fn show_timeouts(process: &Process) {
+	if process.retry.is_some() {
+		println!("  Timeouts: {}", process.timeouts);
+	}
+}
+
+// Prints whether the periodic processes of a workload can meet every deadline under EDF and rate-monotonic scheduling.
+pub fn show_schedulability(schedulability: &Schedulability) {
+	let verdict = |schedulable| {
+		if schedulable {
+			"schedulable"
+		} else {
+			"not schedulable"
+		}
+	};
+
+	println!("Periodic Utilization: {:.2}", schedulability.utilization);
+	println!(
+		"Earliest Deadline First: {}",
+		verdict(schedulability.earliest_deadline_first)
+	);
+
+	let response_times: Vec<String> = schedulability
+		.response_times
+		.iter()
+		.map(|(id, response_time)| match response_time {
+			Some(time) => format!("P{id} {time}"),
+			None => format!("P{id} past its deadline"),
+		})
+		.collect();
+
+	println!(
+		"Rate Monotonic: {}, Response Times: {}",
+		verdict(schedulability.rate_monotonic()),
+		response_times.join(", ")
+	);
+}
+
+// Prints the time a finished process spent in each queue level, and how it was moved between them.
+pub fn show_level_history(process: &Process) {
+	for visit in &process.level_history {
+		println!(
+			"  Level {} from {}: Residency Time: {}, CPU Time: {}",
+			visit.level,
+			visit.entered_at,
+			visit.residency_time(),
+			visit.cpu_time
+		);
+	}
+
+	println!(
+		"  Longest Wait: {}, Demotions: {}, Boosts: {}",
+		process.longest_wait,
+		process.demotions(),
+		process.boosts()
+	);
+}
+
+// Formats an average to two decimals, or as n/a when there was nothing to average.
+fn average(value: f64) -> String {
+	if value.is_nan() {
+		String::from("n/a")
+	} else {
+		format!("{value:.2}")
+	}
+}
+
+// Prints the aggregate metrics of a simulation.
+pub fn show_summary(summary: &Summary) {
+	if let Some(time) = summary.cancelled_at {
+		println!(
+			"Cancelled at t={time}; the metrics cover the {} processes that finished",
+			summary.process_count
+		);
+	}
+
+	if summary.metrics != MetricsPolicy::default() {
+		println!("Metrics: {}", summary.metrics);
+	}
+
+	println!("Turnaround Time: {}", average(summary.turnaround_average()));
+	println!("Waiting Time: {}", average(summary.waiting_average()));
+	println!("Response Time: {}", average(summary.response_average()));
+	println!("CPU Utilization: {:.2}%", summary.cpu_utilization());
+
+	for device in 0..summary.device_busy_times.len() {
+		println!(
+			"Device {} Utilization: {:.2}%",
+			device + 1,
+			summary.device_utilization(device)
+		);
+	}
+
+	if let Some(accelerator) = &summary.accelerator {
+		println!(
+			"Accelerator Utilization: {:.2}%, Waiting Time: {}",
+			accelerator.utilization(summary.total_time),
+			average(accelerator.waiting_average())
+		);
+	}
+
+	for class in &summary.classes {
+		println!(
+			"Class {} ({} processes): Turnaround Time: {:.2}, Waiting Time: {:.2}, Response Time: {:.2}",
+			class.name,
+			class.process_count,
+			class.turnaround_average(),
+			class.waiting_average(),
+			class.response_average()
+		);
+	}
+
+	for phase in &summary.phases {
+		println!(
+			"Phase {} ({} bursts): Turnaround Time: {:.2}, Waiting Time: {:.2}",
+			phase.tag,
+			phase.burst_count,
+			phase.turnaround_average(),
+			phase.waiting_average()
+		);
+	}
+
+	if summary.crashed_count > 0 {
+		println!("Crashed Processes: {}", summary.crashed_count);
+		println!("Wasted CPU Time: {}", summary.wasted_time);
+	}
+
+	if summary.deadlines_met + summary.deadlines_missed > 0 {
+		println!(
+			"Deadlines Met: {}, Missed: {}",
+			summary.deadlines_met, summary.deadlines_missed
+		);
+	}
+
+	show_fairness(summary);
+}
+
+// Prints how evenly the processes were served: the longest waits, Jain's index, and for the MLFQ the time in each level.
+fn show_fairness(summary: &Summary) {
+	let fairness = &summary.fairness;
+
+	let (Some(longest), Some(most)) = (
+		fairness.longest_waiting_process,
+		fairness.most_waiting_process,
+	) else {
+		return;
+	};
+
+	println!("Fairness:");
+	println!("  Longest Wait: {} (P{longest})", fairness.longest_wait);
+	println!(
+		"  Most Waiting: {} (P{most}), {}x the average",
+		fairness.most_waiting,
+		average(fairness.most_waiting.as_f64() / fairness.waiting_average())
+	);
+	println!("  Jain's Index: {:.3}", fairness.jain_index());
+
+	if fairness.level_times.len() > 1 {
+		println!(
+			"  Demotions: {}, Boosts: {}",
+			fairness.demotions, fairness.boosts
+		);
+
+		for (index, time) in fairness.level_times.iter().enumerate() {
+			println!("  Time in Level {}: {time}", index + 1);
+		}
+	}
+}
+
+// Prints the header of the table comparing algorithms.
+pub fn show_comparison_header() {
+	println!(
+		"{:<30} {:>10} {:>10} {:>10} {:>10} {:>8} {:>10}",
+		"Algorithm", "Turnaround", "Waiting", "Response", "Total Time", "CPU", "Inversions"
+	);
+}
+
+// Prints the summary of one algorithm as a row of the comparison table, with its order inversions against FCFS.
+pub fn show_comparison_row(name: &str, summary: &Summary, inversions: u64) {
+	println!(
+		"{:<30} {:>10} {:>10} {:>10} {:>10} {:>7.2}% {:>10}",
+		name,
+		average(summary.turnaround_average()),
+		average(summary.waiting_average()),
+		average(summary.response_average()),
+		summary.total_time,
+		summary.cpu_utilization(),
+		inversions
+	);
+}
+
+// Prints the order the processes finished in, and how many pairs of them finished the other way around under FCFS.
+pub fn show_completion_order(order: &[u32], inversions: Option<u64>) {
+	print!("Completion Order:");
+
+	for id in order {
+		print!(" P{id}");
+	}
+
+	println!();
+
+	if let Some(inversions) = inversions {
+		println!("Order Inversions vs FCFS: {inversions}");
+	}
+}
+
+// Prints the header of the table ranking the workloads of a batch.
+pub fn show_workload_header() {
+	println!(
+		"{:<20} {:>11} {:>10} {:>10} {:>10}  Best / Worst Algorithm",
+		"Workload", "Sensitivity", "Turnaround", "Waiting", "Response"
+	);
+}
+
+// Prints one workload of a batch as a row of the ranking, with the metrics of the chosen algorithm.
+pub fn show_workload_row(result: &WorkloadResult) {
+	let name = |algorithm: Option<&(String, f64)>| {
+		algorithm.map_or("-".to_string(), |(name, _)| name.clone())
+	};
+
+	println!(
+		"{:<20} {:>10.2}% {:>10} {:>10} {:>10}  {} / {}",
+		result.name,
+		result.sensitivity(),
+		average(result.summary.turnaround_average()),
+		average(result.summary.waiting_average()),
+		average(result.summary.response_average()),
+		name(result.best()),
+		name(result.worst())
+	);
+}
+
+// Prints the header of the table comparing algorithms on each class of process.
+pub fn show_class_header() {
+	println!(
+		"{:<10} {:<30} {:>10} {:>10} {:>10}",
+		"Class", "Algorithm", "Turnaround", "Waiting", "Response"
+	);
+}
+
+// Prints the metrics of one class under one algorithm as a row of the class table.
+pub fn show_class_row(algorithm: &str, class: &ClassSummary) {
+	println!(
+		"{:<10} {:<30} {:>10.2} {:>10.2} {:>10.2}",
+		class.name,
+		algorithm,
+		class.turnaround_average(),
+		class.waiting_average(),
+		class.response_average()
+	);
+}
+
+// Prints the header of the table comparing the turnaround of each class with and without I/O priority.
+pub fn show_io_priority_header() {
+	println!(
+		"{:<10} {:<30} {:>10} {:>10} {:>10}",
+		"Class", "Algorithm", "With", "Without", "Change"
+	);
+}
+
+// Prints the average turnaround of one class under one algorithm with and without I/O priority.
+pub fn show_io_priority_row(class: &str, algorithm: &str, with: f64, without: f64) {
+	let change = with - without;
+	let change = if change.is_nan() {
+		String::from("n/a")
+	} else {
+		format!("{change:+.2}")
+	};
+
+	println!(
+		"{:<10} {:<30} {:>10} {:>10} {:>10}",
+		class,
+		algorithm,
+		average(with),
+		average(without),
+		change
+	);
+}
+
+// Prints the header of the table comparing algorithms on each tag of CPU burst.
+pub fn show_phase_header() {
+	println!(
+		"{:<10} {:<30} {:>10} {:>10}",
+		"Phase", "Algorithm", "Turnaround", "Waiting"
+	);
+}
+
+// Prints the metrics of the bursts of one tag under one algorithm as a row of the phase table.
+pub fn show_phase_row(algorithm: &str, phase: &PhaseSummary) {
+	println!(
+		"{:<10} {:<30} {:>10.2} {:>10.2}",
+		phase.tag,
+		algorithm,
+		phase.turnaround_average(),
+		phase.waiting_average()
+	);
+}
+
+// Prints the statistics of each priority boost.
+pub fn show_boosts(boosts: &[BoostRecord]) {
+	for boost in boosts {
+		println!(
+			"Boost at {}: Boosted: {}, Boosted Waiting: {} -> {} (drop {}), Level 1 Waiting: {} -> {} (cost {})",
+			boost.time,
+			boost.boosted.len(),
+			boost.boosted_waiting_before,
+			boost.boosted_waiting_after,
+			boost.waiting_drop(),
+			boost.resident_waiting_before,
+			boost.resident_waiting_after,
+			boost.level_1_cost()
+		);
+	}
+}
+
+// Prints how the last level ran in batches, and how long they held back the higher levels.
+pub fn show_batching(batching: &BatchStats) {
+	println!(
+		"Batches: {}, Batched Slices: {}, Higher Level Delay: {}",
+		batching.batches, batching.bursts, batching.delay
+	);
+}
+
+// Prints each priority inversion, followed by their number and total duration.
+pub fn show_inversions(inversions: &[Inversion]) {
+	for inversion in inversions {
+		println!(
+			"Inversion from {} to {}: P{} waited while P{} ran on CPU {} ({})",
+			inversion.start,
+			inversion.end,
+			inversion.waiting,
+			inversion.running,
+			inversion.cpu + 1,
+			inversion.duration()
+		);
+	}
+
+	println!(
+		"Priority Inversions: {}, Total Duration: {}",
+		inversions.len(),
+		inversions.iter().map(Inversion::duration).sum::<SimTime>()
+	);
+}
+
+/// A sink that prints each step to stdout, the way a run has always been printed.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct Stdout;
+
+impl EventSink for Stdout {
+	// Prints the processes waiting for the CPU and each device.
+	fn before_step<S: Scheduler>(&mut self, engine: &Engine<S>) {
+		show_lists(engine.scheduler(), engine.current_time());
+
+		if let Some(io) = engine.io() {
+			for (device, queue) in io.queues().iter().enumerate() {
+				show_list(&format!("Device {}", device + 1), queue);
+			}
+		}
+
+		if let Some(accelerator) = engine.accelerator() {
+			show_list("Accelerator", &accelerator.queue());
+		}
+	}
+
+	// Prints the events of the step and the process it finished.
+	fn on_step<S: Scheduler>(&mut self, engine: &Engine<S>, step: &Step) {
+		for event in &step.events {
+			match event {
+				Event::Start { id, time } if engine.cpu_count() > 1 => {
+					println!("Start P{id} at {time} on CPU {}", step.cpu + 1);
+				}
+				_ => show_event(event),
+			}
+		}
+
+		if let Some(process) = &step.finished {
+			show_end(process, &engine.summary().metrics);
+			show_deadlines(process);
+			show_timeouts(process);
+
+			if engine.scheduler().level_count() > 1 {
+				show_level_history(process);
+			}
+		}
+
+		println!();
+	}
+}
+
+// Steps the engine once, printing the step and returning the ID of the process that finished or crashed.
+pub fn step<S: Scheduler>(engine: &mut Engine<S>) -> Option<u32> {
+	let step = engine.step_with(&mut Stdout);
+
+	step.finished.map(|process| process.id).or_else(|| {
+		step.events.iter().find_map(|event| match *event {
+			Event::Crash { id, .. } => Some(id),
+			_ => None,
+		})
+	})
+}
+
+// Runs the engine to completion, printing each step.
+pub fn run<S: Scheduler>(engine: &mut Engine<S>) {
+	engine.run_with(&mut Stdout);
+}
+
+// Prints a recorded trace the way a live run is printed, followed by the averages of the finished processes.
+pub fn replay(trace: &Trace) {
+	let multi_cpu = trace.records.iter().any(|record| record.cpu > 0);
+
+	let mut finished = 0;
+	let mut totals = [SimTime::ZERO; 3];
+
+	for record in &trace.records {
+		show_record(record, multi_cpu);
+
+		if let TraceEvent::Finish {
+			turnaround_time,
+			waiting_time,
+			response_time,
+			..
+		} = record.event
+		{
+			finished += 1;
+			totals[0] += turnaround_time;
+			totals[1] += waiting_time;
+			totals[2] += response_time;
+		}
+	}
+
+	if finished > 0 {
+		let [turnaround, waiting, response] =
+			totals.map(|total| total.as_f64() / f64::from(finished));
+
+		println!();
+		println!("Turnaround Time: {turnaround:.2}");
+		println!("Waiting Time: {waiting:.2}");
+		println!("Response Time: {response:.2}");
+	}
+
+	if let Some(time) = trace.cancelled_at {
+		println!("Cancelled at t={time}");
+	}
+}
+
+// Prints a recorded event the way a live run prints it, naming the CPU of a dispatch if there are several.
+pub fn show_record(record: &TraceRecord, multi_cpu: bool) {
+	let time = record.time;
+
+	match record.event {
+		TraceEvent::Dispatch { id } if multi_cpu => {
+			println!("Start P{id} at {time} on CPU {}", record.cpu + 1);
+		}
+		TraceEvent::Dispatch { id } => show_event(&Event::Start { id, time }),
+		TraceEvent::Preempt { id } => println!("Preempted P{id} at {time}"),
+		TraceEvent::Demote { id, level } => show_event(&Event::Downgrade { id, level }),
+		TraceEvent::Boost { count } => show_event(&Event::Boost { count }),
+		TraceEvent::IoStart { id, length } => {
+			println!("P{id} started {length} of IO at {time}")
+		}
+		TraceEvent::IoComplete { id } => println!("P{id} finished IO at {time}"),
+		TraceEvent::Finish {
+			id,
+			turnaround_time,
+			waiting_time,
+			response_time,
+		} => println!(
+			"End P{id} at {time} with Turnaround Time: {turnaround_time}, Waiting Time: {waiting_time}, Response Time: {response_time}"
+		),
+		TraceEvent::Alarm {
+			length,
+			threshold,
+			rising,
+		} => show_event(&Event::Alarm {
+			time,
+			length,
+			threshold,
+			rising,
+		}),
+		TraceEvent::CpuAdded { count } => show_event(&Event::CpuAdded { count }),
+		TraceEvent::CpuRemoved { count } => show_event(&Event::CpuRemoved { count }),
+		TraceEvent::Spawn { parent, id } => show_event(&Event::Spawn { parent, id }),
+		TraceEvent::Crash { id, wasted } => show_event(&Event::Crash { id, wasted }),
+		TraceEvent::Timeout { id, waited, burst } => {
+			show_event(&Event::Timeout { id, waited, burst })
+		}
+	}
+}
+
+// Prints what each CPU was running at a time of a trace.
+pub fn show_running(time: SimTime, running: &[Slice]) {
+	if running.is_empty() {
+		println!("At {time} every CPU was idle");
+	}
+
+	for slice in running {
+		println!(
+			"At {time} CPU {} was running P{} from {} to {}",
+			slice.cpu + 1,
+			slice.id,
+			slice.start,
+			slice.end
+		);
+	}
+}
+
+// Prints a dispatch decision, followed by every queue it was made from if they are given.
+pub fn show_decision(index: usize, decision: &Decision, queues: Option<&QueueSnapshot>) {
+	println!(
+		"Decision {} at {} on CPU {}: P{} ran from {} to {}",
+		index + 1,
+		decision.time,
+		decision.cpu + 1,
+		decision.id,
+		decision.start,
+		decision.end
+	);
+
+	let Some(queues) = queues else {
+		return;
+	};
+
+	for (level, lists) in queues.levels.iter().enumerate() {
+		show_list(&format!("Level {} IO", level + 1), &lists.io);
+		show_list(&format!("Level {} CPU", level + 1), &lists.cpu);
+	}
+
+	for (device, queue) in queues.devices.iter().enumerate() {
+		show_list(&format!("Device {}", device + 1), queue);
+	}
+
+	show_list("Accelerator", &queues.accelerator);
+	println!();
+}
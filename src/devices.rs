@@ -0,0 +1,246 @@
+//! A finite number of I/O devices, each with its own request queue.
+//!
+//! Without an [`IoSubsystem`] every I/O burst completes after its own length,
+//! as if each process had a device to itself. With one, a process that starts
+//! an I/O burst waits in the queue of its device, so contention delays the
+//! time it is ready for the CPU again.
+
+use alloc::vec::Vec;
+use core::{fmt, str::FromStr};
+
+use crate::{process::Process, rng::Rng, time::SimTime};
+
+/// The number of tracks on each device.
+const TRACKS: u32 = 200;
+
+/// How many tracks the head crosses per time unit while seeking.
+const TRACKS_PER_TIME: u32 = 20;
+
+/// The order a device serves its queue in, within each I/O priority class.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum IoPolicy {
+	FirstComeFirstServe,
+	/// Serves the request closest to the head, which cuts seeking at the cost of fairness.
+	ShortestSeekTimeFirst,
+}
+
+/// The error returned when an I/O policy cannot be parsed.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseIoPolicyError;
+
+impl fmt::Display for ParseIoPolicyError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str("expected fcfs or sstf")
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseIoPolicyError {}
+
+impl FromStr for IoPolicy {
+	type Err = ParseIoPolicyError;
+
+	fn from_str(text: &str) -> Result<Self, Self::Err> {
+		match text {
+			"fcfs" => Ok(Self::FirstComeFirstServe),
+			"sstf" => Ok(Self::ShortestSeekTimeFirst),
+			_ => Err(ParseIoPolicyError),
+		}
+	}
+}
+
+/// An I/O burst waiting for its device.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Request {
+	process: Process,
+	issued_at: SimTime,
+	io_time: SimTime,
+	track: u32,
+	// Breaks ties between requests issued at the same time.
+	sequence: u64,
+}
+
+/// A single I/O device.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Device {
+	queue: Vec<Request>,
+	free_at: SimTime,
+	head: u32,
+	busy_time: SimTime,
+}
+
+impl Device {
+	// Returns the time the next request can start, if any are queued.
+	fn next_start(&self) -> Option<SimTime> {
+		self.queue
+			.iter()
+			.map(|request| request.issued_at)
+			.min()
+			.map(|issued_at| issued_at.max(self.free_at))
+	}
+}
+
+/// The I/O devices shared by every process.
+///
+/// A process always uses the same device, chosen by its ID. Requests are
+/// served by I/O priority class first, then in the order of the policy.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IoSubsystem {
+	devices: Vec<Device>,
+	policy: IoPolicy,
+	// Draws the track of each request.
+	rng: Rng,
+	sequence: u64,
+}
+
+impl IoSubsystem {
+	pub fn new(count: usize, policy: IoPolicy) -> Self {
+		let devices = (0..count.max(1))
+			.map(|_| Device {
+				queue: Vec::new(),
+				free_at: SimTime::ZERO,
+				head: 0,
+				busy_time: SimTime::ZERO,
+			})
+			.collect();
+
+		Self {
+			devices,
+			policy,
+			rng: Rng::new(1),
+			sequence: 0,
+		}
+	}
+
+	pub fn with_seed(mut self, seed: u64) -> Self {
+		self.rng = Rng::new(seed);
+		self
+	}
+
+	pub fn device_count(&self) -> usize {
+		self.devices.len()
+	}
+
+	// Returns whether no request is waiting for a device.
+	pub fn is_empty(&self) -> bool {
+		self.devices.iter().all(|device| device.queue.is_empty())
+	}
+
+	// Returns the time each device has spent seeking and transferring.
+	pub fn busy_times(&self) -> Vec<SimTime> {
+		self.devices.iter().map(|device| device.busy_time).collect()
+	}
+
+	// Returns the waiting processes of each device with the length of their I/O burst.
+	pub fn queues(&self) -> Vec<Vec<(u32, SimTime)>> {
+		self.devices
+			.iter()
+			.map(|device| {
+				device
+					.queue
+					.iter()
+					.map(|request| (request.process.id, request.io_time))
+					.collect()
+			})
+			.collect()
+	}
+
+	// Queues an I/O burst of the process, which starts once its device is free.
+	pub fn submit(&mut self, process: Process, issued_at: SimTime, io_time: SimTime) {
+		let device = process.id as usize % self.devices.len();
+		let track = self.rng.below(u64::from(TRACKS)) as u32;
+
+		self.sequence += 1;
+
+		tracing::debug!(
+			target: "io",
+			process = process.id,
+			device = device + 1,
+			track,
+			time = %issued_at,
+			length = %io_time,
+			"queued"
+		);
+
+		self.devices[device].queue.push(Request {
+			process,
+			issued_at,
+			io_time,
+			track,
+			sequence: self.sequence,
+		});
+	}
+
+	// Returns the earliest time a queued request can start.
+	pub fn next_start(&self) -> Option<SimTime> {
+		self.devices.iter().filter_map(Device::next_start).min()
+	}
+
+	// Starts the request that can start first, returning its process once it is ready for the CPU.
+	pub fn dispatch(&mut self) -> Option<Process> {
+		let (device_index, start) = self
+			.devices
+			.iter()
+			.enumerate()
+			.filter_map(|(index, device)| device.next_start().map(|start| (index, start)))
+			.min_by_key(|&(_, start)| start)?;
+
+		let policy = self.policy;
+		let device = &mut self.devices[device_index];
+		let head = device.head;
+
+		// Choose among the requests that have been issued by the time the device starts.
+		let position = device
+			.queue
+			.iter()
+			.enumerate()
+			.filter(|(_, request)| request.issued_at <= start)
+			.min_by_key(|(_, request)| {
+				let order = match policy {
+					IoPolicy::FirstComeFirstServe => 0,
+					IoPolicy::ShortestSeekTimeFirst => request.track.abs_diff(head),
+				};
+
+				(
+					request.process.io_priority,
+					order,
+					request.issued_at,
+					request.sequence,
+				)
+			})
+			.map(|(position, _)| position)?;
+
+		let request = device.queue.remove(position);
+		let service_time =
+			SimTime::from(request.track.abs_diff(head) / TRACKS_PER_TIME) + request.io_time;
+		let end = start + service_time;
+
+		tracing::debug!(
+			target: "io",
+			process = request.process.id,
+			device = device_index + 1,
+			start = %start,
+			end = %end,
+			waited = %(start - request.issued_at),
+			"started"
+		);
+
+		device.free_at = end;
+		device.head = request.track;
+		device.busy_time += service_time;
+
+		// The time spent queued and seeking is added on top of the burst already counted.
+		let mut process = request.process;
+
+		process.next_arrival = end;
+		process.release_at(end);
+		process.turnaround_time += end - request.issued_at - request.io_time;
+
+		Some(process)
+	}
+}
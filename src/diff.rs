@@ -0,0 +1,326 @@
+//! Comparing the reports of two simulations.
+//!
+//! A [`ReportDiff`] matches processes by ID and prints as a short changelog,
+//! such as "P3 finished 41 time units earlier; 2 fewer demotions.", that can
+//! be pasted into a lab report. Given the traces of both runs, it also finds
+//! the first time the schedules differ with a [`TraceIndex`].
+
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+
+use crate::{
+	engine::Summary,
+	report::{ProcessRecord, Report},
+	scheduler::Event,
+	time::SimTime,
+	trace::{Slice, TraceIndex},
+};
+
+/// How a single process changed between two runs.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ProcessDiff {
+	pub id: u32,
+	/// The change in each metric from the first run to the second.
+	pub finish_time: i64,
+	pub waiting_time: i64,
+	pub response_time: i64,
+	pub demotions: i64,
+}
+
+impl ProcessDiff {
+	pub fn is_unchanged(&self) -> bool {
+		self.finish_time == 0
+			&& self.waiting_time == 0
+			&& self.response_time == 0
+			&& self.demotions == 0
+	}
+}
+
+/// The differences between the reports of two runs.
+pub struct ReportDiff {
+	pub processes: Vec<ProcessDiff>,
+	/// The processes that only finished in the first or the second run.
+	pub only_before: Vec<u32>,
+	pub only_after: Vec<u32>,
+	pub before: Summary,
+	pub after: Summary,
+	pub boosts: i64,
+	/// The first time the two runs ran different processes, when their traces were given.
+	pub divergence: Option<Divergence>,
+}
+
+/// The first time two runs ran different processes.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Divergence {
+	pub time: SimTime,
+	/// The slices running at that time in each run.
+	pub before: Vec<Slice>,
+	pub after: Vec<Slice>,
+}
+
+impl ReportDiff {
+	pub fn new(before: &Report, after: &Report) -> Self {
+		let mut processes = Vec::new();
+		let mut only_before = Vec::new();
+
+		for old in &before.processes {
+			let Some(new) = after.process(old.id) else {
+				only_before.push(old.id);
+				continue;
+			};
+
+			processes.push(ProcessDiff {
+				id: old.id,
+				finish_time: change(old, new, |process| process.finish_time),
+				waiting_time: change(old, new, |process| process.waiting_time),
+				response_time: change(old, new, |process| process.response_time),
+				demotions: demotions(after, old.id) - demotions(before, old.id),
+			});
+		}
+
+		processes.sort_unstable_by_key(|process| process.id);
+		only_before.sort_unstable();
+
+		let mut only_after: Vec<_> = after
+			.processes
+			.iter()
+			.filter(|process| before.process(process.id).is_none())
+			.map(|process| process.id)
+			.collect();
+
+		only_after.sort_unstable();
+
+		Self {
+			processes,
+			only_before,
+			only_after,
+			before: before.summary.clone(),
+			after: after.summary.clone(),
+			boosts: boosts(after) - boosts(before),
+			divergence: None,
+		}
+	}
+
+	// Finds the first time the runs of the two traces ran different processes.
+	pub fn with_traces(mut self, before: &TraceIndex, after: &TraceIndex) -> Self {
+		self.divergence = first_divergence(before, after);
+		self
+	}
+}
+
+// Returns the first time the two traces ran different processes, or none if they always ran the same.
+pub fn first_divergence(before: &TraceIndex, after: &TraceIndex) -> Option<Divergence> {
+	// What is running can only change where a slice starts or ends.
+	let mut times: Vec<SimTime> = before
+		.slices()
+		.chain(after.slices())
+		.flat_map(|slice| [slice.start, slice.end])
+		.collect();
+
+	times.sort_unstable();
+	times.dedup();
+
+	times.into_iter().find_map(|time| {
+		let divergence = Divergence {
+			time,
+			before: before.running_at(time),
+			after: after.running_at(time),
+		};
+
+		(running_ids(&divergence.before) != running_ids(&divergence.after)).then_some(divergence)
+	})
+}
+
+fn running_ids(slices: &[Slice]) -> Vec<u32> {
+	let mut ids: Vec<u32> = slices.iter().map(|slice| slice.id).collect();
+
+	ids.sort_unstable();
+	ids
+}
+
+fn change(old: &ProcessRecord, new: &ProcessRecord, metric: fn(&ProcessRecord) -> SimTime) -> i64 {
+	metric(new).as_u64() as i64 - metric(old).as_u64() as i64
+}
+
+// Counts the times a process was moved down a level.
+fn demotions(report: &Report, id: u32) -> i64 {
+	report
+		.events
+		.iter()
+		.filter(|record| matches!(record.event, Event::Downgrade { id: other, .. } if other == id))
+		.count() as i64
+}
+
+fn boosts(report: &Report) -> i64 {
+	report
+		.events
+		.iter()
+		.filter(|record| matches!(record.event, Event::Boost { .. }))
+		.count() as i64
+}
+
+// Describes a change in time, such as "41 time units earlier".
+fn describe_time(change: i64, less: &str, more: &str) -> String {
+	let word = if change < 0 { less } else { more };
+
+	alloc::format!("{} time units {word}", change.unsigned_abs())
+}
+
+// Describes a change in a count, such as "2 fewer demotions".
+fn describe_count(change: i64, noun: &str) -> String {
+	let word = if change < 0 { "fewer" } else { "more" };
+	let plural = if change.unsigned_abs() == 1 { "" } else { "s" };
+
+	alloc::format!("{} {word} {noun}{plural}", change.unsigned_abs())
+}
+
+// Describes the processes running at a time, such as "P2 and P5", or "nothing".
+fn describe_running(slices: &[Slice]) -> String {
+	let ids: Vec<String> = running_ids(slices)
+		.into_iter()
+		.map(|id| alloc::format!("P{id}"))
+		.collect();
+
+	match ids.as_slice() {
+		[] => String::from("nothing"),
+		[id] => id.clone(),
+		[rest @ .., last] => alloc::format!("{} and {last}", rest.join(", ")),
+	}
+}
+
+// Describes the change in an average, such as "Average waiting down 12.0% (205.88 -> 181.20).".
+fn describe_average(name: &str, before: f64, after: f64) -> Option<String> {
+	if before == after || before.is_nan() || after.is_nan() {
+		return None;
+	}
+
+	let direction = if after < before { "down" } else { "up" };
+
+	Some(if before == 0.0 {
+		alloc::format!("Average {name} {direction} ({before:.2} -> {after:.2}).")
+	} else {
+		let percent = (after - before).abs() / before * 100.0;
+
+		alloc::format!("Average {name} {direction} {percent:.1}% ({before:.2} -> {after:.2}).")
+	})
+}
+
+impl ReportDiff {
+	// Returns the changelog, one sentence per line.
+	pub fn changelog(&self) -> Vec<String> {
+		let mut lines = Vec::new();
+
+		if let Some(divergence) = &self.divergence {
+			lines.push(alloc::format!(
+				"The schedules first differ at {}, running {} instead of {}.",
+				divergence.time,
+				describe_running(&divergence.after),
+				describe_running(&divergence.before)
+			));
+		}
+
+		for process in self
+			.processes
+			.iter()
+			.filter(|process| !process.is_unchanged())
+		{
+			let mut parts = Vec::new();
+
+			if process.finish_time != 0 {
+				parts.push(alloc::format!(
+					"finished {}",
+					describe_time(process.finish_time, "earlier", "later")
+				));
+			}
+
+			if process.waiting_time != 0 {
+				parts.push(alloc::format!(
+					"waited {}",
+					describe_time(process.waiting_time, "less", "more")
+				));
+			}
+
+			if process.response_time != 0 {
+				parts.push(alloc::format!(
+					"first ran {}",
+					describe_time(process.response_time, "sooner", "later")
+				));
+			}
+
+			if process.demotions != 0 {
+				parts.push(describe_count(process.demotions, "demotion"));
+			}
+
+			lines.push(alloc::format!("P{} {}.", process.id, parts.join("; ")));
+		}
+
+		for id in &self.only_before {
+			lines.push(alloc::format!("P{id} only finished in the first run."));
+		}
+
+		for id in &self.only_after {
+			lines.push(alloc::format!("P{id} only finished in the second run."));
+		}
+
+		let (before, after) = (&self.before, &self.after);
+
+		lines.extend(describe_average(
+			"turnaround",
+			before.turnaround_average(),
+			after.turnaround_average(),
+		));
+		lines.extend(describe_average(
+			"waiting",
+			before.waiting_average(),
+			after.waiting_average(),
+		));
+		lines.extend(describe_average(
+			"response",
+			before.response_average(),
+			after.response_average(),
+		));
+
+		if before.total_time != after.total_time {
+			lines.push(alloc::format!(
+				"Total time {} -> {}.",
+				before.total_time,
+				after.total_time
+			));
+		}
+
+		let utilization = after.cpu_utilization() - before.cpu_utilization();
+
+		if utilization.abs() >= 0.005 {
+			let direction = if utilization < 0.0 { "down" } else { "up" };
+
+			lines.push(alloc::format!(
+				"CPU utilization {direction} {:.2} points ({:.2}% -> {:.2}%).",
+				utilization.abs(),
+				before.cpu_utilization(),
+				after.cpu_utilization()
+			));
+		}
+
+		if self.boosts != 0 {
+			lines.push(alloc::format!("{}.", describe_count(self.boosts, "boost")));
+		}
+
+		lines
+	}
+}
+
+impl fmt::Display for ReportDiff {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let lines = self.changelog();
+
+		if lines.is_empty() {
+			return writeln!(f, "No differences.");
+		}
+
+		for line in lines {
+			writeln!(f, "{line}")?;
+		}
+
+		Ok(())
+	}
+}
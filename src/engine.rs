@@ -0,0 +1,1045 @@
+use alloc::{boxed::Box, string::String, vec, vec::Vec};
+
+use crate::{
+	accelerator::{Accelerator, AcceleratorSummary},
+	audit::{AuditLog, Decision},
+	autoscale::{Action, Alarm, QueueHook},
+	cancel::CancellationToken,
+	devices::IoSubsystem,
+	faults::FaultInjector,
+	metrics::MetricsPolicy,
+	process::{self, PhaseSummary, Process},
+	scheduler::{Event, Response, Scheduler},
+	sink::{EventSink, NullSink},
+	stats::{Distribution, OnlineStats},
+	time::SimTime,
+	trace::TraceRecorder,
+};
+
+/// What happened during a single step of the engine.
+pub struct Step {
+	pub cpu: usize,
+	pub process_id: u32,
+	pub start: SimTime,
+	pub end: SimTime,
+
+	pub events: Vec<Event>,
+	pub finished: Option<Process>,
+	/// The length of the I/O burst the process started when the step ended, or 0.
+	pub io_time: SimTime,
+	/// The processes whose I/O was scheduled during the step, with the time it completes.
+	pub io_completions: Vec<(u32, SimTime)>,
+}
+
+/// The aggregate metrics of a simulation.
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Summary {
+	pub process_count: u32,
+	/// The IDs of the finished processes, in the order they finished.
+	pub completion_order: Vec<u32>,
+	pub total_turnaround_time: SimTime,
+	pub total_waiting_time: SimTime,
+	pub total_response_time: SimTime,
+	/// The spread and percentiles of the turnaround, waiting, and response times.
+	pub turnaround: Distribution,
+	pub waiting: Distribution,
+	pub response: Distribution,
+	pub idle_time: SimTime,
+	pub total_time: SimTime,
+	/// The CPU time available across every CPU.
+	pub capacity_time: SimTime,
+	/// The time each I/O device spent busy, if the simulation has devices.
+	pub device_busy_times: Vec<SimTime>,
+	/// How much the accelerator was used, if the simulation has one.
+	pub accelerator: Option<AcceleratorSummary>,
+	pub crashed_count: u32,
+	/// The CPU time used by processes that crashed.
+	pub wasted_time: SimTime,
+	/// The metrics of each class of finished process, in the order the classes first finished.
+	pub classes: Vec<ClassSummary>,
+	/// The time the run was cancelled at, if it was stopped before every process finished.
+	pub cancelled_at: Option<SimTime>,
+	pub fairness: Fairness,
+	/// The CPU bursts of finished processes that met and missed their deadlines.
+	pub deadlines_met: u32,
+	pub deadlines_missed: u32,
+	/// The metrics of each tag of CPU burst across the finished processes, in the order the tags first finished.
+	pub phases: Vec<PhaseSummary>,
+	/// The definitions the turnaround, waiting, and response times follow.
+	pub metrics: MetricsPolicy,
+}
+
+impl Summary {
+	// Returns the average turnaround of the finished processes, or NaN if none finished.
+	pub fn turnaround_average(&self) -> f64 {
+		self.turnaround.mean()
+	}
+
+	pub fn waiting_average(&self) -> f64 {
+		self.waiting.mean()
+	}
+
+	pub fn response_average(&self) -> f64 {
+		self.response.mean()
+	}
+
+	pub fn cpu_utilization(&self) -> f64 {
+		(1.0 - self.idle_time.as_f64() / self.capacity_time.as_f64()) * 100.0
+	}
+
+	pub fn device_utilization(&self, device: usize) -> f64 {
+		self.device_busy_times[device].as_f64() / self.total_time.as_f64() * 100.0
+	}
+
+	pub fn class(&self, name: &str) -> Option<&ClassSummary> {
+		self.classes.iter().find(|class| class.name == name)
+	}
+
+	pub fn phase(&self, tag: &str) -> Option<&PhaseSummary> {
+		self.phases.iter().find(|phase| phase.tag == tag)
+	}
+
+	// Adds a finished process to the totals.
+	fn add(&mut self, process: &Process) {
+		let turnaround_time = self.metrics.turnaround_time(process);
+		let waiting_time = self.metrics.waiting_time(process);
+		let response_time = self.metrics.response_time(process);
+
+		self.process_count += 1;
+		self.completion_order.push(process.id);
+		self.total_turnaround_time += turnaround_time;
+		self.total_waiting_time += waiting_time;
+		self.total_response_time += response_time;
+		self.turnaround.add(turnaround_time.as_f64());
+		self.waiting.add(waiting_time.as_f64());
+		self.response.add(response_time.as_f64());
+		self.fairness.add(process);
+		self.deadlines_met += process.deadlines_met;
+		self.deadlines_missed += process.deadlines_missed;
+
+		for phase in &process.phases {
+			process::phase(&mut self.phases, &phase.tag).merge(phase);
+		}
+
+		let Some(name) = &process.class else {
+			return;
+		};
+
+		let index = match self.classes.iter().position(|class| &class.name == name) {
+			Some(index) => index,
+			None => {
+				self.classes.push(ClassSummary {
+					name: name.clone(),
+					..ClassSummary::default()
+				});
+
+				self.classes.len() - 1
+			}
+		};
+
+		let class = &mut self.classes[index];
+
+		class.process_count += 1;
+		class.total_turnaround_time += turnaround_time;
+		class.total_waiting_time += waiting_time;
+		class.total_response_time += response_time;
+		class.turnaround.add(turnaround_time.as_f64());
+		class.waiting.add(waiting_time.as_f64());
+		class.response.add(response_time.as_f64());
+	}
+}
+
+/// The metrics of the finished processes of one class.
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ClassSummary {
+	pub name: String,
+	pub process_count: u32,
+	pub total_turnaround_time: SimTime,
+	pub total_waiting_time: SimTime,
+	pub total_response_time: SimTime,
+	/// The spread and percentiles of the times of the class, as in the summary.
+	pub turnaround: Distribution,
+	pub waiting: Distribution,
+	pub response: Distribution,
+}
+
+impl ClassSummary {
+	pub fn turnaround_average(&self) -> f64 {
+		self.turnaround.mean()
+	}
+
+	pub fn waiting_average(&self) -> f64 {
+		self.waiting.mean()
+	}
+
+	pub fn response_average(&self) -> f64 {
+		self.response.mean()
+	}
+}
+
+/// How evenly the finished processes were served, to quantify starvation.
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Fairness {
+	/// The longest any process waited in a ready queue at once, and that process.
+	pub longest_wait: SimTime,
+	pub longest_waiting_process: Option<u32>,
+	/// The most waiting time of any one process, and that process.
+	pub most_waiting: SimTime,
+	pub most_waiting_process: Option<u32>,
+	pub demotions: u32,
+	pub boosts: u32,
+	/// The time processes spent in each queue level, from the first down.
+	pub level_times: Vec<SimTime>,
+	// The statistics of the time processes waited in ready queues, and of their progress rates.
+	waiting: OnlineStats,
+	progress: OnlineStats,
+}
+
+impl Fairness {
+	// Returns the average time processes waited in ready queues, whatever the definition of waiting time in the summary.
+	pub fn waiting_average(&self) -> f64 {
+		self.waiting.mean()
+	}
+
+	// Returns Jain's fairness index of the progress rates: 1 when every process progressed at the same rate, down to 1/n.
+	//
+	// A process's progress rate is the share of its turnaround time it spent not waiting for the CPU.
+	pub fn jain_index(&self) -> f64 {
+		// The squares of the rates average to the variance plus the square of the mean.
+		let mean = self.progress.mean();
+		let squares = self.progress.variance() + mean * mean;
+
+		// Every process had a progress rate of zero, or none finished.
+		if squares == 0.0 || self.progress.count() == 0 {
+			return 1.0;
+		}
+
+		mean * mean / squares
+	}
+
+	fn add(&mut self, process: &Process) {
+		if self.longest_waiting_process.is_none() || process.longest_wait > self.longest_wait {
+			self.longest_wait = process.longest_wait;
+			self.longest_waiting_process = Some(process.id);
+		}
+
+		if self.most_waiting_process.is_none() || process.waiting_time > self.most_waiting {
+			self.most_waiting = process.waiting_time;
+			self.most_waiting_process = Some(process.id);
+		}
+
+		self.demotions += process.demotions();
+		self.boosts += process.boosts();
+
+		for visit in &process.level_history {
+			let level = visit.level as usize;
+
+			if self.level_times.len() < level {
+				self.level_times.resize(level, SimTime::ZERO);
+			}
+
+			self.level_times[level - 1] += visit.residency_time();
+		}
+
+		let progress = if process.turnaround_time.is_zero() {
+			1.0
+		} else {
+			1.0 - process.waiting_time.as_f64() / process.turnaround_time.as_f64()
+		};
+
+		self.waiting.add(process.waiting_time.as_f64());
+		self.progress.add(progress);
+	}
+}
+
+/// A simulated CPU.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Cpu {
+	// The time the CPU is free to run the next process.
+	clock: SimTime,
+	added_at: SimTime,
+}
+
+/// Drives a scheduler until every process has finished.
+///
+/// With more than one CPU, each step runs on the CPU that is free first, so
+/// steps are still taken in time order.
+///
+/// Processes that arrive after the start are held back and only pushed to
+/// the scheduler once the simulation reaches their arrival time. Child
+/// processes are held back until their parent spawns them.
+///
+/// An engine keeps all of its state, including its random number generators,
+/// to itself and is `Send`, so separate simulations can run on separate
+/// threads or async tasks without affecting each other.
+pub struct Engine<S> {
+	scheduler: S,
+	summary: Summary,
+	// The processes that have not arrived yet, latest first.
+	pending: Vec<Process>,
+	// The child processes whose parent has not spawned them yet.
+	unspawned: Vec<Process>,
+
+	cpus: Vec<Cpu>,
+	// The capacity of CPUs that have been removed.
+	removed_capacity: SimTime,
+
+	hook: Option<Box<dyn QueueHook>>,
+	ready_length: usize,
+
+	io: Option<IoSubsystem>,
+	accelerator: Option<Accelerator>,
+	recorder: Option<TraceRecorder>,
+	audit: Option<AuditLog>,
+	faults: Option<FaultInjector>,
+	cancellation: Option<CancellationToken>,
+}
+
+/// The state of an engine at one point in a run, from which the run can be resumed.
+///
+/// A snapshot holds the scheduler with every queued process, the CPU clocks,
+/// the processes yet to arrive or be spawned, the I/O devices and accelerator,
+/// the trace and audit log so far, and the random number generators, so a restored engine
+/// continues exactly where the snapshot was taken. Restoring the same snapshot more than
+/// once branches the run into separate continuations. The queue hook and the
+/// cancellation token are not part of the snapshot and have to be added again.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Snapshot<S> {
+	scheduler: S,
+	summary: Summary,
+	pending: Vec<Process>,
+	unspawned: Vec<Process>,
+	cpus: Vec<Cpu>,
+	removed_capacity: SimTime,
+	ready_length: usize,
+	io: Option<IoSubsystem>,
+	accelerator: Option<Accelerator>,
+	recorder: Option<TraceRecorder>,
+	audit: Option<AuditLog>,
+	faults: Option<FaultInjector>,
+}
+
+impl<S> Snapshot<S> {
+	pub fn scheduler(&self) -> &S {
+		&self.scheduler
+	}
+
+	pub fn summary(&self) -> &Summary {
+		&self.summary
+	}
+
+	// Returns the time the next step starts at once the run is resumed.
+	pub fn current_time(&self) -> SimTime {
+		self.cpus
+			.iter()
+			.map(|cpu| cpu.clock)
+			.min()
+			.unwrap_or_default()
+	}
+}
+
+#[cfg(feature = "std")]
+impl<S: serde::Serialize + serde::de::DeserializeOwned> Snapshot<S> {
+	/// Saves the snapshot as JSON.
+	pub fn save(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+		let file = std::io::BufWriter::new(std::fs::File::create(path)?);
+
+		serde_json::to_writer(file, self)?;
+
+		Ok(())
+	}
+
+	/// Loads a snapshot saved as JSON.
+	pub fn load(path: impl AsRef<std::path::Path>) -> std::io::Result<Self> {
+		let file = std::io::BufReader::new(std::fs::File::open(path)?);
+
+		serde_json::from_reader(file)
+			.map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidData, error))
+	}
+}
+
+impl<S: Scheduler + Clone> Engine<S> {
+	// Captures the state of the run, leaving the engine untouched.
+	pub fn snapshot(&self) -> Snapshot<S> {
+		Snapshot {
+			scheduler: self.scheduler.clone(),
+			summary: self.summary.clone(),
+			pending: self.pending.clone(),
+			unspawned: self.unspawned.clone(),
+			cpus: self.cpus.clone(),
+			removed_capacity: self.removed_capacity,
+			ready_length: self.ready_length,
+			io: self.io.clone(),
+			accelerator: self.accelerator.clone(),
+			recorder: self.recorder.clone(),
+			audit: self.audit.clone(),
+			faults: self.faults.clone(),
+		}
+	}
+}
+
+impl<S: Scheduler> Engine<S> {
+	// Creates an engine that resumes the run the snapshot was taken from.
+	pub fn restore(snapshot: Snapshot<S>) -> Self {
+		Self {
+			scheduler: snapshot.scheduler,
+			summary: snapshot.summary,
+			pending: snapshot.pending,
+			unspawned: snapshot.unspawned,
+
+			cpus: snapshot.cpus,
+			removed_capacity: snapshot.removed_capacity,
+
+			hook: None,
+			ready_length: snapshot.ready_length,
+
+			io: snapshot.io,
+			accelerator: snapshot.accelerator,
+			recorder: snapshot.recorder,
+			audit: snapshot.audit,
+			faults: snapshot.faults,
+			cancellation: None,
+		}
+	}
+
+	pub fn new(mut scheduler: S) -> Self {
+		let (unspawned, processes): (Vec<_>, Vec<_>) = scheduler
+			.drain()
+			.into_iter()
+			.partition(|process| process.spawn.is_some());
+		// A scheduler that preempts for later arrivals looks ahead at them, so it gets them all up front.
+		let look_ahead = scheduler.preempts_on_arrival();
+		let (mut pending, arrived): (Vec<_>, Vec<_>) = processes
+			.into_iter()
+			.partition(|process| !look_ahead && !process.arrival_time.is_zero());
+
+		for process in arrived {
+			scheduler.push(process);
+		}
+
+		pending.sort_by_key(|process| core::cmp::Reverse(process.arrival_time));
+
+		Self {
+			scheduler,
+			summary: Summary::default(),
+			pending,
+			unspawned,
+
+			cpus: vec![Cpu {
+				clock: SimTime::ZERO,
+				added_at: SimTime::ZERO,
+			}],
+			removed_capacity: SimTime::ZERO,
+
+			hook: None,
+			ready_length: 0,
+
+			io: None,
+			accelerator: None,
+			recorder: None,
+			audit: None,
+			faults: None,
+			cancellation: None,
+		}
+	}
+
+	// Measures the finished processes by the given definitions of each metric.
+	pub fn with_metrics(mut self, metrics: MetricsPolicy) -> Self {
+		self.summary.metrics = metrics;
+		self
+	}
+
+	// Runs the simulation on the given number of CPUs.
+	pub fn with_cpus(mut self, count: usize) -> Self {
+		for _ in 1..count {
+			self.cpus.push(Cpu {
+				clock: SimTime::ZERO,
+				added_at: SimTime::ZERO,
+			});
+		}
+
+		self
+	}
+
+	// Calls the hook whenever the ready queue length crosses one of its thresholds.
+	pub fn with_hook(mut self, hook: Box<dyn QueueHook>) -> Self {
+		self.hook = Some(hook);
+		self
+	}
+
+	// Queues I/O bursts on the devices of the subsystem instead of completing them right away.
+	pub fn with_io(mut self, io: IoSubsystem) -> Self {
+		self.summary.device_busy_times = io.busy_times();
+		self.io = Some(io);
+		self
+	}
+
+	// Queues accelerator bursts on the units of the accelerator instead of running them right away.
+	pub fn with_accelerator(mut self, accelerator: Accelerator) -> Self {
+		self.summary.accelerator = Some(accelerator.summary().clone());
+		self.accelerator = Some(accelerator);
+		self
+	}
+
+	// Records every step in the trace recorder.
+	pub fn with_recorder(mut self, recorder: TraceRecorder) -> Self {
+		self.recorder = Some(recorder);
+		self
+	}
+
+	// Records every dispatch decision in the audit log, along with the queues it was made from.
+	pub fn with_audit(mut self, audit: AuditLog) -> Self {
+		self.audit = Some(audit);
+		self
+	}
+
+	// Crashes processes at the end of their bursts, as decided by the injector.
+	pub fn with_faults(mut self, faults: FaultInjector) -> Self {
+		self.faults = Some(faults);
+		self
+	}
+
+	// Stops the run after the step during which the token is cancelled.
+	pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+		self.cancellation = Some(token);
+		self
+	}
+
+	pub fn scheduler(&self) -> &S {
+		&self.scheduler
+	}
+
+	pub fn summary(&self) -> &Summary {
+		&self.summary
+	}
+
+	pub fn cpu_count(&self) -> usize {
+		self.cpus.len()
+	}
+
+	// Returns the time the next step starts at.
+	pub fn current_time(&self) -> SimTime {
+		self.cpus
+			.iter()
+			.map(|cpu| cpu.clock)
+			.min()
+			.unwrap_or_default()
+	}
+
+	pub fn is_finished(&self) -> bool {
+		self.scheduler.is_empty()
+			&& self.pending.is_empty()
+			&& self.io.as_ref().is_none_or(IoSubsystem::is_empty)
+			&& self.accelerator.as_ref().is_none_or(Accelerator::is_empty)
+	}
+
+	// Returns whether the run was cancelled before every process finished.
+	pub fn is_cancelled(&self) -> bool {
+		self.summary.cancelled_at.is_some()
+	}
+
+	// Returns whether the run is over, because every process finished or it was cancelled.
+	pub fn is_stopped(&self) -> bool {
+		self.is_finished() || self.is_cancelled()
+	}
+
+	// Returns the processes that have not arrived or been spawned yet.
+	pub fn unarrived(&self) -> impl Iterator<Item = &Process> {
+		self.pending.iter().chain(&self.unspawned)
+	}
+
+	pub fn io(&self) -> Option<&IoSubsystem> {
+		self.io.as_ref()
+	}
+
+	pub fn accelerator(&self) -> Option<&Accelerator> {
+		self.accelerator.as_ref()
+	}
+
+	pub fn recorder(&self) -> Option<&TraceRecorder> {
+		self.recorder.as_ref()
+	}
+
+	pub fn audit(&self) -> Option<&AuditLog> {
+		self.audit.as_ref()
+	}
+
+	// Pushes the processes that have arrived by the given time to the scheduler.
+	fn admit_arrivals(&mut self, current_time: SimTime) {
+		while self
+			.pending
+			.last()
+			.is_some_and(|process| process.arrival_time <= current_time)
+		{
+			let process = self.pending.pop().unwrap();
+
+			tracing::debug!(target: "engine", process = process.id, time = %process.arrival_time, "arrived");
+
+			self.scheduler.push(process);
+		}
+	}
+
+	// Returns the earliest time a process is ready for the CPU, whether queued or yet to arrive.
+	fn ready_at(&self) -> SimTime {
+		self.scheduler
+			.processes()
+			.map(|process| process.next_arrival)
+			.chain(self.pending.last().map(|process| process.arrival_time))
+			.min()
+			.unwrap_or(SimTime::MAX)
+	}
+
+	// Sends a process that finished an accelerator burst at the given time on to the I/O after it.
+	//
+	// With devices the I/O is queued on the process's device, and otherwise the process is ready
+	// for the CPU once it is done.
+	fn resume(
+		&mut self,
+		mut process: Process,
+		time: SimTime,
+		io_time: SimTime,
+		io_completions: &mut Vec<(u32, SimTime)>,
+	) {
+		let think_time = process.think_time.min(io_time);
+
+		process.next_arrival = time + io_time;
+		process.release_at(time + io_time);
+
+		match &mut self.io {
+			Some(io) if io_time > think_time => {
+				io.submit(process, time + think_time, io_time - think_time)
+			}
+			io => {
+				if io.is_none() && !io_time.is_zero() {
+					io_completions.push((process.id, time + io_time));
+				}
+
+				self.scheduler.push(process);
+			}
+		}
+	}
+
+	// Pushes the processes that have arrived or been sent to a device by the given time to the scheduler.
+	//
+	// Returns the processes that started I/O on a device, with the time it completes.
+	fn admit(&mut self, current_time: SimTime) -> Vec<(u32, SimTime)> {
+		let mut io_completions = Vec::new();
+
+		self.admit_arrivals(current_time);
+
+		// Start the accelerator bursts that begin by now, or before any process is ready for the CPU.
+		while let Some(start) = self.accelerator.as_ref().and_then(Accelerator::next_start) {
+			if start > current_time && start >= self.ready_at() {
+				break;
+			}
+
+			let (process, end, io_time) = self.accelerator.as_mut().unwrap().dispatch().unwrap();
+
+			self.resume(process, end, io_time, &mut io_completions);
+		}
+
+		self.summary.accelerator = self
+			.accelerator
+			.as_ref()
+			.map(|accelerator| accelerator.summary().clone());
+
+		// Start the I/O that begins by now, or before any process is ready for the CPU.
+		while let Some(start) = self.io.as_ref().and_then(IoSubsystem::next_start) {
+			if start > current_time && start >= self.ready_at() {
+				break;
+			}
+
+			let process = self.io.as_mut().unwrap().dispatch().unwrap();
+
+			io_completions.push((process.id, process.next_arrival));
+
+			self.scheduler.push(process);
+		}
+
+		// If nothing is left to run, the next process to arrive is admitted early so the CPU idles until then.
+		if self.scheduler.is_empty() {
+			if let Some(arrival_time) = self.pending.last().map(|process| process.arrival_time) {
+				self.admit_arrivals(arrival_time);
+			}
+		}
+
+		io_completions
+	}
+
+	// Pushes the children the parent has reached to the scheduler, as new arrivals at the given time.
+	//
+	// Without a burst count the parent crashed, so its children are never spawned.
+	fn spawn_children(
+		&mut self,
+		parent: u32,
+		bursts_completed: Option<u32>,
+		time: SimTime,
+		events: &mut Vec<Event>,
+	) {
+		let (children, unspawned) =
+			core::mem::take(&mut self.unspawned)
+				.into_iter()
+				.partition(|child| {
+					child.spawn.is_some_and(|spawn| {
+						spawn.parent == parent
+							&& bursts_completed.is_none_or(|bursts| spawn.after_bursts <= bursts)
+					})
+				});
+
+		self.unspawned = unspawned;
+
+		if bursts_completed.is_none() {
+			return;
+		}
+
+		for mut child in children {
+			tracing::debug!(target: "engine", parent, process = child.id, time = %time, "spawned");
+
+			child.arrive_at(time);
+
+			events.push(Event::Spawn {
+				parent,
+				id: child.id,
+			});
+
+			self.scheduler.push(child);
+		}
+	}
+
+	// Calls the hook for every threshold the ready queue crossed, applying its actions.
+	fn check_alarms(&mut self, events: &mut Vec<Event>) {
+		let Some(hook) = &mut self.hook else {
+			return;
+		};
+
+		let time = self
+			.cpus
+			.iter()
+			.map(|cpu| cpu.clock)
+			.min()
+			.unwrap_or_default();
+		let length = self.scheduler.cpu_remaining(time).len();
+		let previous = core::mem::replace(&mut self.ready_length, length);
+
+		let crossed: Vec<_> = hook
+			.thresholds()
+			.iter()
+			.filter_map(|&threshold| {
+				if previous < threshold && length >= threshold {
+					Some((threshold, true))
+				} else if previous >= threshold && length < threshold {
+					Some((threshold, false))
+				} else {
+					None
+				}
+			})
+			.collect();
+
+		for (threshold, rising) in crossed {
+			let alarm = Alarm {
+				time,
+				length,
+				threshold,
+				rising,
+				cpus: self.cpus.len(),
+			};
+
+			events.push(Event::Alarm {
+				time,
+				length,
+				threshold,
+				rising,
+			});
+
+			tracing::info!(target: "engine", time = %time, length, threshold, rising, "ready queue alarm");
+
+			match hook.on_alarm(&alarm) {
+				Action::Continue => {}
+				Action::AddCpu => {
+					self.cpus.push(Cpu {
+						clock: time,
+						added_at: time,
+					});
+
+					events.push(Event::CpuAdded {
+						count: self.cpus.len(),
+					});
+				}
+				// Remove the CPU that is busy the longest, once its current process is done.
+				Action::RemoveCpu if self.cpus.len() > 1 => {
+					let index = (0..self.cpus.len())
+						.max_by_key(|&index| self.cpus[index].clock)
+						.unwrap();
+					let cpu = self.cpus.remove(index);
+
+					self.removed_capacity += cpu.clock - cpu.added_at;
+
+					events.push(Event::CpuRemoved {
+						count: self.cpus.len(),
+					});
+				}
+				Action::RemoveCpu => {}
+			}
+		}
+	}
+
+	// Runs the simulation to completion, or until it is cancelled, without printing anything.
+	pub fn run(&mut self) {
+		self.run_with(&mut NullSink);
+	}
+
+	// Runs the simulation to completion, or until it is cancelled, handing each step to the sink.
+	pub fn run_with(&mut self, sink: &mut impl EventSink) {
+		while !self.is_stopped() {
+			self.step_with(sink);
+		}
+	}
+
+	// Steps the scheduler once, handing the step to the sink before returning it.
+	pub fn step_with(&mut self, sink: &mut impl EventSink) -> Step {
+		sink.before_step(self);
+
+		let step = self.step();
+
+		sink.on_step(self, &step);
+		step
+	}
+
+	// Steps the scheduler once.
+	pub fn step(&mut self) -> Step {
+		let cpu = (0..self.cpus.len())
+			.min_by_key(|&index| self.cpus[index].clock)
+			.unwrap();
+		let current_time = self.cpus[cpu].clock;
+
+		let mut io_completions = self.admit(current_time);
+
+		if let Some(audit) = &mut self.audit {
+			audit.capture(
+				&self.scheduler,
+				self.io.as_ref(),
+				self.accelerator.as_ref(),
+				current_time,
+			);
+		}
+
+		let data = self.scheduler.step(current_time);
+
+		let start = current_time + data.idle_time;
+		let end = start + data.cpu_time;
+
+		if let Some(audit) = &mut self.audit {
+			audit.record(Decision {
+				time: current_time,
+				cpu,
+				id: data.process_id,
+				start,
+				end,
+			});
+		}
+
+		self.cpus[cpu].clock = end;
+
+		tracing::debug!(target: "engine", cpu = cpu + 1, process = data.process_id, start = %start, end = %end, "ran");
+
+		self.summary.idle_time += data.idle_time;
+		self.summary.total_time = self.summary.total_time.max(end);
+
+		let mut events = data.events;
+
+		// A crash is rolled once per burst, when it is done, so preemption does not add rolls.
+		let burst_done = !data.cpu_time.is_zero()
+			&& match &data.response {
+				Response::Success(_) | Response::Failure(_) => true,
+				Response::Empty => self
+					.scheduler
+					.get(data.process_id)
+					.is_some_and(|process| !process.in_burst()),
+			};
+		let crashed = self
+			.faults
+			.as_mut()
+			.is_some_and(|faults| burst_done && faults.crashes());
+
+		// Handle the response from the scheduler.
+		let finished = match data.response {
+			// A crashed process leaves the simulation without counting towards the averages.
+			response if crashed => {
+				let process = match response {
+					Response::Success(process) | Response::Failure(process) => process,
+					Response::Empty => self.scheduler.remove(data.process_id).unwrap(),
+				};
+				let wasted = process.cpu_time_used();
+
+				tracing::warn!(target: "engine", process = process.id, time = %end, wasted = %wasted, "crashed");
+
+				self.summary.crashed_count += 1;
+				self.summary.wasted_time += wasted;
+
+				events.push(Event::Crash {
+					id: process.id,
+					wasted,
+				});
+
+				None
+			}
+			Response::Success(process) => {
+				tracing::info!(
+					target: "metrics",
+					process = process.id,
+					turnaround = %process.turnaround_time,
+					waiting = %process.waiting_time,
+					response = %process.response_time,
+					"finished"
+				);
+
+				self.summary.add(&process);
+
+				Some(process)
+			}
+			Response::Failure(process) => panic!("P{} failed", process.id),
+			Response::Empty => None,
+		};
+
+		let process = finished
+			.as_ref()
+			.or_else(|| self.scheduler.get(data.process_id));
+		let bursts_completed = process.map(Process::bursts_completed);
+
+		// A burst that waited past the process's timeout has just lengthened the next one.
+		if let Some(timed_out) = process
+			.and_then(|process| process.last_timeout)
+			.filter(|timed_out| timed_out.time == end)
+		{
+			tracing::debug!(target: "engine", process = data.process_id, waited = %timed_out.waited, "timed out");
+
+			events.push(Event::Timeout {
+				id: data.process_id,
+				waited: timed_out.waited,
+				burst: timed_out.burst,
+			});
+		}
+
+		self.spawn_children(data.process_id, bursts_completed, end, &mut events);
+
+		// A finished process has no I/O left, so it is not looked for in the queue.
+		let (io_time, think_time) = finished
+			.is_none()
+			.then(|| self.scheduler.get(data.process_id))
+			.flatten()
+			.map_or((SimTime::ZERO, SimTime::ZERO), |process| {
+				let io_time = process.next_arrival.saturating_sub(end);
+
+				// A periodic process waiting for its next release needs no device either.
+				if process.period.is_some() {
+					(io_time, io_time)
+				} else {
+					(io_time, process.think_time.min(io_time))
+				}
+			});
+
+		let accelerator_burst = finished
+			.is_none()
+			.then(|| self.scheduler.get(data.process_id))
+			.flatten()
+			.and_then(Process::accelerator_burst);
+
+		// Move a process that started an accelerator burst to the accelerator's queue, with its I/O
+		// left for after, or else a process that started an I/O burst to the queue of its device.
+		if let Some(length) = accelerator_burst {
+			let mut process = self.scheduler.remove(data.process_id).unwrap();
+
+			process.take_accelerator_burst();
+
+			match &mut self.accelerator {
+				Some(accelerator) => accelerator.submit(process, end, length, io_time),
+				None => {
+					process.turnaround_time += length;
+
+					self.resume(process, end + length, io_time, &mut io_completions);
+				}
+			}
+		} else if let Some(io) = &mut self.io {
+			// Thinking needs no device, so only the rest of the burst is queued, once the user is done.
+			if io_time > think_time {
+				let process = self.scheduler.remove(data.process_id).unwrap();
+
+				io.submit(process, end + think_time, io_time - think_time);
+			}
+		} else if !io_time.is_zero() {
+			tracing::debug!(target: "io", process = data.process_id, start = %end, end = %(end + io_time), "started");
+
+			io_completions.push((data.process_id, end + io_time));
+		}
+
+		if let Some(io) = &self.io {
+			self.summary.device_busy_times = io.busy_times();
+		}
+
+		self.check_alarms(&mut events);
+
+		let total_time = self.summary.total_time;
+		let cancelled = !self.is_finished()
+			&& self
+				.cancellation
+				.as_ref()
+				.is_some_and(CancellationToken::is_cancelled);
+
+		if cancelled {
+			tracing::warn!(target: "engine", time = %total_time, "cancelled");
+
+			self.summary.cancelled_at = Some(total_time);
+
+			if let Some(recorder) = &mut self.recorder {
+				recorder.cancel(total_time);
+			}
+		}
+
+		// The CPUs that finished early are idle until the last one is done.
+		if self.is_stopped() {
+			self.summary.idle_time += self
+				.cpus
+				.iter()
+				.map(|cpu| total_time - cpu.clock)
+				.sum::<SimTime>();
+
+			tracing::info!(
+				target: "metrics",
+				processes = self.summary.process_count,
+				total_time = %total_time,
+				idle = %self.summary.idle_time,
+				"finished every process"
+			);
+		}
+
+		self.summary.capacity_time = self.removed_capacity
+			+ self
+				.cpus
+				.iter()
+				.map(|cpu| total_time - cpu.added_at)
+				.sum::<SimTime>();
+
+		let step = Step {
+			cpu,
+			process_id: data.process_id,
+			start,
+			end,
+
+			events,
+			finished,
+			io_time,
+			io_completions,
+		};
+
+		if let Some(recorder) = &mut self.recorder {
+			recorder.record(&step, &self.summary.metrics);
+		}
+
+		step
+	}
+}
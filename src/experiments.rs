@@ -0,0 +1,233 @@
+//! Running the same workload across a grid of MLFQ parameters.
+//!
+//! A [`Sweep`] runs every combination of base quantum, boost interval, and
+//! number of levels, and the results can be written as CSV for plotting,
+//! instead of parsing the console output of many runs. A batch of
+//! [`WorkloadResult`]s runs one algorithm over many workloads instead, and
+//! ranks them by how much the choice of algorithm matters for each.
+
+use alloc::{string::String, vec, vec::Vec};
+use core::fmt::Write;
+
+use crate::{
+	algorithms::{LevelPolicy, MultiLevelFeedbackQueue},
+	engine::Summary,
+	time::SimTime,
+	workload::Workload,
+};
+
+/// The parameters of a single MLFQ configuration in a sweep.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Configuration {
+	/// The quantum of level 1, which doubles on every level below it.
+	pub quantum: SimTime,
+	pub boost_interval: Option<SimTime>,
+	pub level_count: usize,
+}
+
+impl Configuration {
+	// Returns the quantum of a level counting from 0, or `None` if doubling it that often overflows.
+	pub fn quantum_of(&self, level: usize) -> Option<SimTime> {
+		let factor = u32::try_from(level)
+			.ok()
+			.and_then(|level| 1u64.checked_shl(level))?;
+
+		self.quantum.as_u64().checked_mul(factor).map(SimTime::new)
+	}
+
+	// Returns whether the configuration has levels and the quantum of each fits in a time.
+	pub fn is_valid(&self) -> bool {
+		self.level_count > 0
+			&& !self.quantum.is_zero()
+			&& self.quantum_of(self.level_count - 1).is_some()
+	}
+
+	// Returns the levels of the configuration: round robin levels above a final FCFS level.
+	//
+	// Panics if the configuration is not valid.
+	pub fn levels(&self) -> Vec<LevelPolicy> {
+		assert!(self.is_valid(), "invalid sweep configuration {self:?}");
+
+		let mut levels: Vec<_> = (0..self.level_count - 1)
+			.map(|level| LevelPolicy::RoundRobin {
+				quanta: self.quantum_of(level).unwrap(),
+			})
+			.collect();
+
+		levels.push(LevelPolicy::FirstComeFirstServe);
+		levels
+	}
+
+	// Creates the MLFQ for the configuration with the processes of the workload.
+	pub fn mlfq(&self, workload: &Workload) -> MultiLevelFeedbackQueue {
+		let processes = workload.clone().into_processes();
+		let scheduler = MultiLevelFeedbackQueue::with_levels(processes.into(), &self.levels());
+
+		match self.boost_interval {
+			Some(boost_interval) => scheduler.with_boost_interval(boost_interval),
+			None => scheduler,
+		}
+	}
+}
+
+/// The aggregate metrics of one configuration.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SweepResult {
+	pub configuration: Configuration,
+	pub summary: Summary,
+}
+
+/// A grid of MLFQ parameters.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Sweep {
+	pub quanta: Vec<SimTime>,
+	pub boost_intervals: Vec<Option<SimTime>>,
+	pub level_counts: Vec<usize>,
+}
+
+impl Default for Sweep {
+	// The default levels: round robin with quanta 5 and 10, then FCFS, without boosting.
+	fn default() -> Self {
+		Self {
+			quanta: vec![SimTime::new(5)],
+			boost_intervals: vec![None],
+			level_counts: vec![3],
+		}
+	}
+}
+
+impl Sweep {
+	// Returns an error naming the first configuration without levels or whose quanta overflow.
+	pub fn check(&self) -> Result<(), String> {
+		match self
+			.configurations()
+			.into_iter()
+			.find(|configuration| !configuration.is_valid())
+		{
+			None => Ok(()),
+			Some(configuration) if configuration.level_count == 0 => {
+				Err(String::from("a configuration needs at least 1 level"))
+			}
+			Some(configuration) => Err(alloc::format!(
+				"the quantum {} overflows when doubled over {} levels",
+				configuration.quantum,
+				configuration.level_count
+			)),
+		}
+	}
+
+	// Returns every combination of the parameters.
+	pub fn configurations(&self) -> Vec<Configuration> {
+		let mut configurations = Vec::new();
+
+		for &quantum in &self.quanta {
+			for &boost_interval in &self.boost_intervals {
+				for &level_count in &self.level_counts {
+					configurations.push(Configuration {
+						quantum,
+						boost_interval,
+						level_count,
+					});
+				}
+			}
+		}
+
+		configurations
+	}
+
+	// Runs every configuration on the workload, using `run` to simulate each one to completion.
+	pub fn run(
+		&self,
+		workload: &Workload,
+		mut run: impl FnMut(MultiLevelFeedbackQueue) -> Summary,
+	) -> Vec<SweepResult> {
+		self.configurations()
+			.into_iter()
+			.map(|configuration| SweepResult {
+				configuration,
+				summary: run(configuration.mlfq(workload)),
+			})
+			.collect()
+	}
+}
+
+// Writes the results as CSV, one row per configuration.
+pub fn to_csv(results: &[SweepResult]) -> String {
+	let mut csv = String::from(
+		"quantum,boost_interval,levels,turnaround,waiting,response,turnaround_p90,waiting_p90,response_p90,total_time,cpu_utilization\n",
+	);
+
+	for result in results {
+		let configuration = &result.configuration;
+		let summary = &result.summary;
+
+		let boost_interval = configuration
+			.boost_interval
+			.map(|interval| alloc::format!("{interval}"))
+			.unwrap_or_default();
+
+		let _ = writeln!(
+			csv,
+			"{},{},{},{:.2},{:.2},{:.2},{:.2},{:.2},{:.2},{},{:.2}",
+			configuration.quantum,
+			boost_interval,
+			configuration.level_count,
+			summary.turnaround_average(),
+			summary.waiting_average(),
+			summary.response_average(),
+			summary.turnaround.p90(),
+			summary.waiting.p90(),
+			summary.response.p90(),
+			summary.total_time,
+			summary.cpu_utilization()
+		);
+	}
+
+	csv
+}
+
+/// The results of one workload of a batch.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WorkloadResult {
+	/// The name of the workload, such as its file name.
+	pub name: String,
+	/// The summary under the chosen algorithm.
+	pub summary: Summary,
+	/// The average turnaround time under each compared algorithm, by name.
+	pub turnaround_averages: Vec<(String, f64)>,
+}
+
+impl WorkloadResult {
+	// Returns how much the algorithms differ on the workload: how much longer the average turnaround
+	// time is under the worst algorithm than under the best, as a percentage.
+	pub fn sensitivity(&self) -> f64 {
+		match (self.best(), self.worst()) {
+			(Some((_, best)), Some((_, worst))) if *best > 0.0 => (worst - best) / best * 100.0,
+			_ => 0.0,
+		}
+	}
+
+	// Returns the algorithm with the shortest average turnaround time, and that time.
+	pub fn best(&self) -> Option<&(String, f64)> {
+		self.turnaround_averages
+			.iter()
+			.filter(|(_, average)| !average.is_nan())
+			.min_by(|first, second| first.1.total_cmp(&second.1))
+	}
+
+	// Returns the algorithm with the longest average turnaround time, and that time.
+	pub fn worst(&self) -> Option<&(String, f64)> {
+		self.turnaround_averages
+			.iter()
+			.filter(|(_, average)| !average.is_nan())
+			.max_by(|first, second| first.1.total_cmp(&second.1))
+	}
+}
+
+// Sorts the results from the workload the algorithms differ on most to the one they differ on least.
+pub fn rank_by_sensitivity(results: &mut [WorkloadResult]) {
+	results.sort_by(|first, second| second.sensitivity().total_cmp(&first.sensitivity()));
+}
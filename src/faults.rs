@@ -0,0 +1,35 @@
+//! Injecting process crashes into a simulation.
+//!
+//! With a [`FaultInjector`], the engine crashes the process that just ran with
+//! a fixed probability per burst. A crashed process is removed from the
+//! scheduler, and the CPU time it used is counted as wasted work instead of
+//! towards the metrics of finished processes.
+
+use crate::rng::Rng;
+
+/// Decides which bursts end in a crash.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FaultInjector {
+	probability: f64,
+	rng: Rng,
+}
+
+impl FaultInjector {
+	// Creates an injector that crashes a burst with the given probability, drawing from the seed.
+	pub fn new(probability: f64, seed: u64) -> Self {
+		Self {
+			probability,
+			rng: Rng::new(seed),
+		}
+	}
+
+	pub fn probability(&self) -> f64 {
+		self.probability
+	}
+
+	// Returns whether the burst that just ran crashed.
+	pub fn crashes(&mut self) -> bool {
+		self.rng.next_f64() < self.probability
+	}
+}
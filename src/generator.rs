@@ -0,0 +1,264 @@
+//! Random workloads whose arrival rate changes over time.
+//!
+//! Arrivals follow a Poisson process with a time-varying rate, sampled by
+//! thinning: candidate arrivals are drawn at the peak rate and each one is
+//! kept with probability `rate(t) / peak`.
+
+use std::{fmt, str::FromStr};
+
+use crate::{rng::Rng, time::SimTime, workload::Workload};
+
+/// One phase of a piecewise arrival rate.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Phase {
+	pub length: SimTime,
+	pub rate: f64,
+}
+
+/// The expected number of arrivals per time unit over the course of a run.
+#[derive(Clone, PartialEq, Debug)]
+pub enum ArrivalRate {
+	Constant(f64),
+	/// A rate that rises and falls around the mean once per period.
+	Sinusoidal {
+		mean: f64,
+		amplitude: f64,
+		period: SimTime,
+	},
+	/// Phases that repeat in order, such as a busy day and a quiet night.
+	Phases(Vec<Phase>),
+}
+
+impl ArrivalRate {
+	// Creates a rate that alternates between a day and a night phase.
+	pub fn day_night(
+		day_length: SimTime,
+		day_rate: f64,
+		night_length: SimTime,
+		night_rate: f64,
+	) -> Self {
+		Self::Phases(vec![
+			Phase {
+				length: day_length,
+				rate: day_rate,
+			},
+			Phase {
+				length: night_length,
+				rate: night_rate,
+			},
+		])
+	}
+
+	// Returns the rate at the given time, which is never negative.
+	pub fn at(&self, time: f64) -> f64 {
+		let rate = match self {
+			Self::Constant(rate) => *rate,
+			Self::Sinusoidal {
+				mean,
+				amplitude,
+				period,
+			} => mean + amplitude * (std::f64::consts::TAU * time / period.as_f64()).sin(),
+			Self::Phases(phases) => {
+				let cycle: SimTime = phases.iter().map(|phase| phase.length).sum();
+
+				if cycle.is_zero() {
+					return 0.0;
+				}
+
+				let mut offset = time % cycle.as_f64();
+
+				phases
+					.iter()
+					.find(|phase| {
+						let inside = offset < phase.length.as_f64();
+
+						offset -= phase.length.as_f64();
+
+						inside
+					})
+					.map_or(0.0, |phase| phase.rate)
+			}
+		};
+
+		rate.max(0.0)
+	}
+
+	// Returns the highest rate reached at any time.
+	pub fn peak(&self) -> f64 {
+		let peak = match self {
+			Self::Constant(rate) => *rate,
+			Self::Sinusoidal {
+				mean, amplitude, ..
+			} => mean + amplitude.abs(),
+			Self::Phases(phases) => phases.iter().map(|phase| phase.rate).fold(0.0, f64::max),
+		};
+
+		peak.max(0.0)
+	}
+}
+
+/// The error returned when an arrival rate cannot be parsed.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseArrivalRateError;
+
+impl fmt::Display for ParseArrivalRateError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str(
+			"expected constant:<rate>, sine:<mean>:<amplitude>:<period>, or phases:<length>=<rate>,...",
+		)
+	}
+}
+
+impl std::error::Error for ParseArrivalRateError {}
+
+impl FromStr for ArrivalRate {
+	type Err = ParseArrivalRateError;
+
+	// Parses rates such as `constant:0.05`, `sine:0.05:0.04:500`, or `phases:300=0.1,300=0.01`.
+	fn from_str(text: &str) -> Result<Self, Self::Err> {
+		let (kind, value) = text.split_once(':').ok_or(ParseArrivalRateError)?;
+		let number = |value: &str| value.parse::<f64>().ok().filter(|value| value.is_finite());
+
+		match kind {
+			"constant" => number(value)
+				.map(Self::Constant)
+				.ok_or(ParseArrivalRateError),
+			"sine" => {
+				let parts: Vec<_> = value.split(':').collect();
+
+				let [mean, amplitude, period] = parts[..] else {
+					return Err(ParseArrivalRateError);
+				};
+
+				match (number(mean), number(amplitude), period.parse::<SimTime>()) {
+					(Some(mean), Some(amplitude), Ok(period)) if !period.is_zero() => {
+						Ok(Self::Sinusoidal {
+							mean,
+							amplitude,
+							period,
+						})
+					}
+					_ => Err(ParseArrivalRateError),
+				}
+			}
+			"phases" => value
+				.split(',')
+				.map(|phase| {
+					let (length, rate) = phase.split_once('=')?;
+
+					Some(Phase {
+						length: length.parse().ok()?,
+						rate: number(rate)?,
+					})
+				})
+				.collect::<Option<Vec<_>>>()
+				.map(Self::Phases)
+				.ok_or(ParseArrivalRateError),
+			_ => Err(ParseArrivalRateError),
+		}
+	}
+}
+
+/// Generates random workloads with processes arriving over a span of time.
+pub struct Generator {
+	rate: ArrivalRate,
+	duration: SimTime,
+	seed: u64,
+	// The inclusive ranges of CPU bursts per process and of each burst time.
+	bursts: (u32, u32),
+	cpu_time: (SimTime, SimTime),
+	io_time: (SimTime, SimTime),
+}
+
+impl Generator {
+	// Creates a generator for arrivals from time 0 until the duration.
+	pub fn new(rate: ArrivalRate, duration: SimTime) -> Self {
+		Self {
+			rate,
+			duration,
+			seed: 1,
+			bursts: (1, 6),
+			cpu_time: (SimTime::new(2), SimTime::new(20)),
+			io_time: (SimTime::new(10), SimTime::new(60)),
+		}
+	}
+
+	pub fn with_seed(mut self, seed: u64) -> Self {
+		self.seed = seed;
+		self
+	}
+
+	// Sets the range of CPU bursts each process has.
+	pub fn with_bursts(mut self, min: u32, max: u32) -> Self {
+		self.bursts = (min.max(1), max.max(min).max(1));
+		self
+	}
+
+	// Sets the range of each CPU burst time.
+	pub fn with_cpu_time(mut self, min: SimTime, max: SimTime) -> Self {
+		let one = SimTime::new(1);
+
+		self.cpu_time = (min.max(one), max.max(min).max(one));
+		self
+	}
+
+	// Sets the range of each IO burst time.
+	pub fn with_io_time(mut self, min: SimTime, max: SimTime) -> Self {
+		self.io_time = (min, max.max(min));
+		self
+	}
+
+	// Returns the arrival times of the processes, in order.
+	fn arrivals(&self, rng: &mut Rng) -> Vec<SimTime> {
+		let peak = self.rate.peak();
+		let mut arrivals = Vec::new();
+
+		if peak <= 0.0 {
+			return arrivals;
+		}
+
+		let mut time = 0.0;
+
+		loop {
+			time += -(1.0 - rng.next_f64()).ln() / peak;
+
+			if time >= self.duration.as_f64() {
+				return arrivals;
+			}
+
+			if rng.next_f64() * peak < self.rate.at(time) {
+				arrivals.push(SimTime::new(time as u64));
+			}
+		}
+	}
+
+	pub fn generate(&self) -> Workload {
+		let mut rng = Rng::new(self.seed);
+		let mut workload = Workload::new();
+
+		for arrival_time in self.arrivals(&mut rng) {
+			let (min, max) = self.bursts;
+			let bursts = draw(&mut rng, (u64::from(min), u64::from(max)));
+			let (min, max) = self.cpu_time;
+			let cpu_times: Vec<_> = (0..bursts)
+				.map(|_| draw(&mut rng, (min.as_u64(), max.as_u64())))
+				.collect();
+			let (min, max) = self.io_time;
+			let io_times: Vec<_> = (1..bursts)
+				.map(|_| draw(&mut rng, (min.as_u64(), max.as_u64())))
+				.collect();
+
+			workload
+				.process(io_times, cpu_times)
+				.arrival_time(arrival_time)
+				.add();
+		}
+
+		workload
+	}
+}
+
+// Draws a number from an inclusive range.
+fn draw(rng: &mut Rng, (min, max): (u64, u64)) -> u64 {
+	min + rng.below(max - min + 1)
+}
@@ -0,0 +1,128 @@
+use std::io::{self, BufRead, Write};
+
+use crate::{
+	accelerator::Accelerator, console, devices::IoSubsystem, engine::Engine, scheduler::Scheduler,
+	time::SimTime,
+};
+
+const HELP: &str = "Commands: step, run <count>, until P<id> done, queues, quit";
+
+/// A command entered at the interactive prompt.
+enum Command {
+	Run(u32),
+	Until(u32),
+	Queues,
+	Help,
+	Quit,
+}
+
+impl Command {
+	fn parse(line: &str) -> Option<Self> {
+		let words: Vec<_> = line.split_whitespace().collect();
+
+		match words.as_slice() {
+			[] | ["step"] => Some(Self::Run(1)),
+			["run", count] => count.parse().ok().map(Self::Run),
+			["until", id, "done"] => id
+				.strip_prefix('P')
+				.unwrap_or(id)
+				.parse()
+				.ok()
+				.map(Self::Until),
+			["queues"] => Some(Self::Queues),
+			["help"] => Some(Self::Help),
+			["quit"] => Some(Self::Quit),
+			_ => None,
+		}
+	}
+}
+
+// Prints the IO and CPU lists of every queue level.
+pub fn show_levels<S: Scheduler>(scheduler: &S, current_time: SimTime) {
+	println!("Time: {current_time}");
+
+	for (index, mut level) in scheduler.levels(current_time).into_iter().enumerate() {
+		let level_number = index + 1;
+
+		if level.io.is_empty() && level.cpu.is_empty() {
+			println!("Level {level_number}: empty");
+
+			continue;
+		}
+
+		level.io.sort_unstable_by_key(|data| data.0);
+		level.cpu.sort_unstable_by_key(|data| data.0);
+
+		console::show_list(&format!("Level {level_number} IO"), &level.io);
+		console::show_list(&format!("Level {level_number} CPU"), &level.cpu);
+	}
+}
+
+// Returns whether the process is still waiting, in one of the scheduler's queues, for a device or
+// the accelerator, or to arrive.
+fn is_waiting<S: Scheduler>(engine: &Engine<S>, id: u32) -> bool {
+	let scheduler = engine.scheduler();
+	let current_time = engine.current_time();
+	let devices = engine.io().map(IoSubsystem::queues).unwrap_or_default();
+	let accelerator = engine
+		.accelerator()
+		.map(Accelerator::queue)
+		.unwrap_or_default();
+
+	scheduler
+		.io_remaining(current_time)
+		.into_iter()
+		.chain(scheduler.cpu_remaining(current_time))
+		.chain(devices.into_iter().flatten())
+		.chain(accelerator)
+		.any(|data| data.0 == id)
+		|| engine.unarrived().any(|process| process.id == id)
+}
+
+// Runs the engine, pausing after each dispatch for commands read from standard input.
+pub fn run<S: Scheduler>(engine: &mut Engine<S>) -> io::Result<()> {
+	let mut lines = io::stdin().lock().lines();
+
+	println!("{HELP}");
+
+	while !engine.is_stopped() {
+		show_levels(engine.scheduler(), engine.current_time());
+
+		print!("> ");
+		io::stdout().flush()?;
+
+		// Stop when the input is closed.
+		let Some(line) = lines.next().transpose()? else {
+			break;
+		};
+
+		println!();
+
+		match Command::parse(&line) {
+			Some(Command::Run(count)) => {
+				for _ in 0..count {
+					if engine.is_stopped() {
+						break;
+					}
+
+					console::step(engine);
+				}
+			}
+			Some(Command::Until(id)) => {
+				if !is_waiting(engine, id) {
+					println!("P{id} is not in the simulation\n");
+
+					continue;
+				}
+
+				while !engine.is_stopped() && console::step(engine) != Some(id) {}
+			}
+			Some(Command::Queues) => {}
+			Some(Command::Help) => println!("{HELP}\n"),
+			Some(Command::Quit) => break,
+			None => println!("Unknown command. {HELP}\n"),
+		}
+	}
+
+	Ok(())
+}
@@ -0,0 +1,50 @@
+//! Scheduling algorithms and the simulation engine.
+//!
+//! Without the default `std` feature the crate is `no_std + alloc`, leaving
+//! out the console output so the core can run on bare-metal targets.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+pub mod accelerator;
+pub mod algorithms;
+pub mod analysis;
+pub mod audit;
+pub mod autoscale;
+pub mod cancel;
+#[cfg(feature = "std")]
+pub mod cli;
+#[cfg(feature = "std")]
+pub mod console;
+pub mod devices;
+pub mod diff;
+pub mod engine;
+pub mod experiments;
+pub mod faults;
+#[cfg(feature = "std")]
+pub mod generator;
+#[cfg(feature = "std")]
+pub mod interactive;
+pub mod metrics;
+pub mod pipeline;
+pub mod process;
+pub mod report;
+pub mod rng;
+pub mod schedulability;
+pub mod scheduler;
+#[cfg(feature = "std")]
+pub mod serve;
+pub mod sink;
+pub mod stats;
+// Scaffolding for the integration tests, which can only reach public items.
+#[doc(hidden)]
+pub mod testing;
+pub mod time;
+pub mod top;
+pub mod trace;
+#[cfg(feature = "tui")]
+pub mod tui;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+pub mod workload;
@@ -0,0 +1,164 @@
+//! Which definition of each per-process metric is reported.
+//!
+//! Textbooks disagree on how response, waiting, and turnaround time are
+//! measured once processes arrive at different times or alternate CPU and I/O
+//! bursts. By default every process is measured from its own arrival over its
+//! whole run, and a [`MetricsPolicy`] picks another convention per metric.
+
+use alloc::{format, string::String};
+use core::{fmt, str::FromStr};
+
+use crate::{process::Process, time::SimTime};
+
+/// How response time is measured.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(rename_all = "kebab-case")
+)]
+pub enum ResponseTime {
+	/// From the arrival of the process to its first run.
+	#[default]
+	FromArrival,
+	/// The time of the first run itself, which matches the time from arrival only when every process arrives at 0.
+	FirstRun,
+	/// From each CPU burst becoming ready to its first run, averaged over the bursts.
+	PerBurst,
+}
+
+/// How waiting time is measured.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(rename_all = "kebab-case")
+)]
+pub enum WaitingTime {
+	/// The time spent in ready queues.
+	#[default]
+	ReadyQueue,
+	/// The time from arrival to the end less the CPU time used, which also counts I/O as waiting.
+	TurnaroundLessCpu,
+}
+
+/// How turnaround time is measured.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(rename_all = "kebab-case")
+)]
+pub enum TurnaroundTime {
+	/// From the arrival of the process to its end.
+	#[default]
+	PerJob,
+	/// From each CPU burst becoming ready to it finishing, averaged over the bursts.
+	PerBurst,
+}
+
+/// The definition of each metric that summaries, traces, and reports use.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MetricsPolicy {
+	pub response: ResponseTime,
+	pub waiting: WaitingTime,
+	pub turnaround: TurnaroundTime,
+}
+
+impl MetricsPolicy {
+	pub fn response_time(&self, process: &Process) -> SimTime {
+		match self.response {
+			ResponseTime::FromArrival => process.response_time,
+			ResponseTime::FirstRun => process.arrival_time + process.response_time,
+			ResponseTime::PerBurst => process.burst_response_average(),
+		}
+	}
+
+	pub fn waiting_time(&self, process: &Process) -> SimTime {
+		match self.waiting {
+			WaitingTime::ReadyQueue => process.waiting_time,
+			WaitingTime::TurnaroundLessCpu => process.turnaround_time - process.cpu_time_used(),
+		}
+	}
+
+	pub fn turnaround_time(&self, process: &Process) -> SimTime {
+		match self.turnaround {
+			TurnaroundTime::PerJob => process.turnaround_time,
+			TurnaroundTime::PerBurst => process.burst_turnaround_average(),
+		}
+	}
+}
+
+impl fmt::Display for MetricsPolicy {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let response = match self.response {
+			ResponseTime::FromArrival => "from-arrival",
+			ResponseTime::FirstRun => "first-run",
+			ResponseTime::PerBurst => "per-burst",
+		};
+		let waiting = match self.waiting {
+			WaitingTime::ReadyQueue => "ready-queue",
+			WaitingTime::TurnaroundLessCpu => "turnaround-less-cpu",
+		};
+		let turnaround = match self.turnaround {
+			TurnaroundTime::PerJob => "per-job",
+			TurnaroundTime::PerBurst => "per-burst",
+		};
+
+		write!(
+			f,
+			"response={response},waiting={waiting},turnaround={turnaround}"
+		)
+	}
+}
+
+/// The error returned when a metrics policy cannot be parsed.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseMetricsPolicyError(String);
+
+impl fmt::Display for ParseMetricsPolicyError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str(&self.0)
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseMetricsPolicyError {}
+
+impl FromStr for MetricsPolicy {
+	type Err = ParseMetricsPolicyError;
+
+	// Parses a list of definitions such as `response=per-burst,turnaround=per-burst`, where missing ones keep the default.
+	fn from_str(text: &str) -> Result<Self, Self::Err> {
+		let mut policy = Self::default();
+
+		for definition in text.split(',').filter(|definition| !definition.is_empty()) {
+			let error = |message: &str| ParseMetricsPolicyError(format!("{definition}: {message}"));
+
+			let Some((metric, value)) = definition.split_once('=') else {
+				return Err(error("expected metric=definition"));
+			};
+
+			match (metric, value) {
+				("response", "from-arrival") => policy.response = ResponseTime::FromArrival,
+				("response", "first-run") => policy.response = ResponseTime::FirstRun,
+				("response", "per-burst") => policy.response = ResponseTime::PerBurst,
+				("response", _) => {
+					return Err(error("expected from-arrival, first-run, or per-burst"))
+				}
+				("waiting", "ready-queue") => policy.waiting = WaitingTime::ReadyQueue,
+				("waiting", "turnaround-less-cpu") => {
+					policy.waiting = WaitingTime::TurnaroundLessCpu
+				}
+				("waiting", _) => return Err(error("expected ready-queue or turnaround-less-cpu")),
+				("turnaround", "per-job") => policy.turnaround = TurnaroundTime::PerJob,
+				("turnaround", "per-burst") => policy.turnaround = TurnaroundTime::PerBurst,
+				("turnaround", _) => return Err(error("expected per-job or per-burst")),
+				_ => return Err(error("expected response, waiting, or turnaround")),
+			}
+		}
+
+		Ok(policy)
+	}
+}
@@ -0,0 +1,119 @@
+//! Simulations composed into a pipeline of stages.
+//!
+//! Each stage is its own engine with its own scheduler, such as a CPU stage
+//! feeding an accelerator stage. A process that finishes one stage arrives at
+//! the next when it finished, running the bursts it was given for that stage,
+//! so the stages share one timeline and a process is only ever in one of them.
+
+use alloc::{collections::BTreeMap, vec::Vec};
+
+use crate::{
+	engine::{Engine, Step, Summary},
+	process::Process,
+	scheduler::Scheduler,
+	sink::{CollectingSink, EventSink, NullSink},
+	stats::OnlineStats,
+	time::SimTime,
+};
+
+/// The stages of a pipeline run so far, and when each process entered and left them.
+#[derive(Clone, Default)]
+pub struct Pipeline {
+	/// The summary of each stage, in the order they ran.
+	pub stages: Vec<Summary>,
+	/// The time each process arrived at the first stage.
+	arrivals: BTreeMap<u32, SimTime>,
+	/// The time each process finished the last stage, which is the time it arrives at the next.
+	finished: BTreeMap<u32, SimTime>,
+}
+
+impl Pipeline {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	// Runs the next stage on the given processes, built into an engine by `build`.
+	//
+	// The processes of the first stage arrive as given. After that, a process arrives when it
+	// finished the stage before, and a process that did not finish it, or has no bursts in this
+	// stage, leaves the pipeline.
+	pub fn run<S: Scheduler>(
+		&mut self,
+		processes: Vec<Process>,
+		build: impl FnOnce(Vec<Process>) -> Engine<S>,
+	) -> &Summary {
+		self.run_with(processes, build, &mut NullSink)
+	}
+
+	// Runs the next stage like `run`, handing each of its steps to the sink.
+	pub fn run_with<S: Scheduler>(
+		&mut self,
+		processes: Vec<Process>,
+		build: impl FnOnce(Vec<Process>) -> Engine<S>,
+		sink: &mut impl EventSink,
+	) -> &Summary {
+		let processes: Vec<Process> = if self.stages.is_empty() {
+			self.arrivals = processes
+				.iter()
+				.map(|process| (process.id, process.arrival_time))
+				.collect();
+
+			processes
+		} else {
+			processes
+				.into_iter()
+				.filter_map(|mut process| {
+					let finished = *self.finished.get(&process.id)?;
+
+					process.arrive_at(finished);
+
+					Some(process)
+				})
+				.collect()
+		};
+
+		let mut engine = build(processes);
+		let mut collected = CollectingSink::new();
+
+		engine.run_with(&mut Tee(sink, &mut collected));
+
+		self.finished = collected.finished.into_iter().collect();
+		self.stages.push(engine.summary().clone());
+
+		self.stages.last().unwrap()
+	}
+
+	// Returns the time from each process arriving at the first stage to it finishing the last
+	// stage run so far, for the processes that made it through every stage.
+	pub fn turnaround_times(&self) -> Vec<(u32, SimTime)> {
+		self.finished
+			.iter()
+			.filter_map(|(&id, &finished)| Some((id, finished - *self.arrivals.get(&id)?)))
+			.collect()
+	}
+
+	pub fn turnaround_average(&self) -> f64 {
+		let mut stats = OnlineStats::new();
+
+		for (_, time) in self.turnaround_times() {
+			stats.add(time.as_f64());
+		}
+
+		stats.mean()
+	}
+}
+
+/// A sink that hands each step to two sinks.
+struct Tee<'a, A, B>(&'a mut A, &'a mut B);
+
+impl<A: EventSink, B: EventSink> EventSink for Tee<'_, A, B> {
+	fn before_step<S: Scheduler>(&mut self, engine: &Engine<S>) {
+		self.0.before_step(engine);
+		self.1.before_step(engine);
+	}
+
+	fn on_step<S: Scheduler>(&mut self, engine: &Engine<S>, step: &Step) {
+		self.0.on_step(engine, step);
+		self.1.on_step(engine, step);
+	}
+}
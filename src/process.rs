@@ -0,0 +1,500 @@
+use alloc::{
+	collections::VecDeque,
+	string::{String, ToString},
+	vec,
+	vec::Vec,
+};
+
+use crate::{stats::OnlineStats, time::SimTime};
+
+/// The I/O priority class of a process, in the style of `ionice`.
+///
+/// The class only takes effect when the simulation has an I/O subsystem,
+/// where queued requests of a higher class are served first.
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum IoPriority {
+	#[cfg_attr(feature = "serde", serde(rename = "realtime"))]
+	RealTime,
+	#[default]
+	BestEffort,
+	Idle,
+}
+
+/// The tickets a process has unless the workload gives it others.
+pub const DEFAULT_TICKETS: u32 = 100;
+
+/// The estimate used for a process's first CPU burst.
+pub const DEFAULT_ESTIMATE: f64 = 10.0;
+
+/// Predicts the next CPU burst of a process from the ones before it.
+///
+/// Each finished burst `t` updates the estimate `τ` to `α t + (1 - α) τ`, so a
+/// larger `α` weighs recent bursts more heavily.
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BurstPredictor {
+	pub alpha: f64,
+	pub estimate: f64,
+}
+
+impl BurstPredictor {
+	pub fn new(alpha: f64, estimate: f64) -> Self {
+		Self { alpha, estimate }
+	}
+
+	// Returns the predicted length of the next burst, rounded to a whole time unit.
+	pub fn predict(&self) -> SimTime {
+		SimTime((self.estimate + 0.5) as u64)
+	}
+
+	pub fn update(&mut self, burst: SimTime) {
+		self.estimate = self.alpha * burst.as_f64() + (1.0 - self.alpha) * self.estimate;
+	}
+}
+
+impl Default for BurstPredictor {
+	fn default() -> Self {
+		Self::new(0.5, DEFAULT_ESTIMATE)
+	}
+}
+
+/// Where in the run of its parent a child process is spawned.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Spawn {
+	pub parent: u32,
+	/// The number of CPU bursts the parent finishes before the child starts.
+	pub after_bursts: u32,
+}
+
+/// A soft real-time deadline on every CPU burst of a process.
+///
+/// Missing a deadline is not fatal, but the process only tolerates missing a
+/// share of them, which is the budget the soft real-time scheduler protects.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SoftDeadline {
+	/// The time each CPU burst has to finish in, from when it becomes ready.
+	pub deadline: SimTime,
+	/// The percentage of its deadlines the process can miss.
+	pub tolerance: u32,
+}
+
+/// Timeout and retry logic in a process, reacting to how long it waited.
+///
+/// When a CPU burst waited longer than the timeout in ready queues, the
+/// process gives up on it and retries, making its next burst `factor` times
+/// as long, so slow scheduling feeds back into more work.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RetryPolicy {
+	pub timeout: SimTime,
+	pub factor: u64,
+}
+
+/// A retry set off by a timeout, as reported by the engine.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TimedOut {
+	/// The time the burst that waited too long finished.
+	pub time: SimTime,
+	/// The time that burst waited in ready queues.
+	pub waited: SimTime,
+	/// The length of the next burst after the retry.
+	pub burst: SimTime,
+}
+
+/// The metrics of the CPU bursts with one tag, such as the startup phase of each process.
+#[derive(Clone, Default, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PhaseSummary {
+	pub tag: String,
+	pub burst_count: u32,
+	/// The time the bursts waited in ready queues.
+	pub total_waiting_time: SimTime,
+	/// The time from each burst becoming ready to it finishing.
+	pub total_turnaround_time: SimTime,
+	/// The statistics of the times of each burst, which merge along with the totals.
+	pub waiting: OnlineStats,
+	pub turnaround: OnlineStats,
+}
+
+impl PhaseSummary {
+	pub fn turnaround_average(&self) -> f64 {
+		self.turnaround.mean()
+	}
+
+	pub fn waiting_average(&self) -> f64 {
+		self.waiting.mean()
+	}
+
+	// Adds the bursts of another summary of the same tag.
+	pub fn merge(&mut self, other: &Self) {
+		self.burst_count += other.burst_count;
+		self.total_waiting_time += other.total_waiting_time;
+		self.total_turnaround_time += other.total_turnaround_time;
+		self.waiting.merge(&other.waiting);
+		self.turnaround.merge(&other.turnaround);
+	}
+}
+
+// Returns the metrics of the tag in the list, adding empty ones if the tag is new.
+pub(crate) fn phase<'a>(phases: &'a mut Vec<PhaseSummary>, tag: &str) -> &'a mut PhaseSummary {
+	let index = match phases.iter().position(|phase| phase.tag == tag) {
+		Some(index) => index,
+		None => {
+			phases.push(PhaseSummary {
+				tag: tag.to_string(),
+				..PhaseSummary::default()
+			});
+
+			phases.len() - 1
+		}
+	};
+
+	&mut phases[index]
+}
+
+// Returns the average of a total over a count, rounded to the nearest unit, or 0 for a count of 0.
+fn average(total: SimTime, count: u32) -> SimTime {
+	match u64::from(count) {
+		0 => SimTime::ZERO,
+		count => SimTime::new((total.as_u64() + count / 2) / count),
+	}
+}
+
+/// The time a process spent in one queue level.
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LevelVisit {
+	pub level: u32,
+	pub entered_at: SimTime,
+	pub left_at: SimTime,
+	pub cpu_time: SimTime,
+}
+
+impl LevelVisit {
+	pub fn residency_time(&self) -> SimTime {
+		self.left_at - self.entered_at
+	}
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Process {
+	pub id: u32,
+	/// The time the process first arrives.
+	pub arrival_time: SimTime,
+	pub next_arrival: SimTime,
+	pub io_times: VecDeque<SimTime>,
+	pub cpu_times: VecDeque<SimTime>,
+	/// The accelerator burst after each CPU burst but the last, run before the I/O of that gap, or zero for none.
+	pub accelerator_times: VecDeque<SimTime>,
+	// The accelerator burst after the CPU burst just finished, until the engine starts it.
+	accelerator_burst: Option<SimTime>,
+	pub io_priority: IoPriority,
+	/// The scheduling priority, where larger values run first.
+	pub priority: u32,
+	/// The share of the CPU for proportional-share schedulers.
+	pub tickets: u32,
+	/// The kind of program the process models, for per-class metrics.
+	pub class: Option<String>,
+	/// The time spent waiting on the user at the start of every I/O burst, which needs no device.
+	pub think_time: SimTime,
+	/// The process that spawns this one, if it does not arrive on its own.
+	pub spawn: Option<Spawn>,
+	/// The deadline of every CPU burst, for soft real-time processes.
+	pub soft_deadline: Option<SoftDeadline>,
+	/// The time between the releases of the CPU bursts of a periodic process, each burst being one job.
+	pub period: Option<SimTime>,
+	/// How the process reacts to waiting too long for a CPU burst.
+	pub retry: Option<RetryPolicy>,
+	/// The tag of each CPU burst in order, such as the phase of the program it is in, or `None` if it has none.
+	pub burst_tags: Vec<Option<String>>,
+	/// The metrics of the finished CPU bursts of each tag, in the order the tags first finished.
+	pub phases: Vec<PhaseSummary>,
+	pub burst_predictor: BurstPredictor,
+	// The CPU time the current burst has run for so far.
+	burst_run_time: SimTime,
+	bursts_completed: u32,
+	// The time the current CPU burst became ready.
+	released_at: SimTime,
+	pub deadlines_met: u32,
+	pub deadlines_missed: u32,
+	// The time the current CPU burst has waited in ready queues so far.
+	burst_waiting_time: SimTime,
+	// Whether the current CPU burst has run yet.
+	burst_started: bool,
+	// The time from each finished CPU burst becoming ready to its first run, and to it finishing.
+	total_burst_response_time: SimTime,
+	total_burst_turnaround_time: SimTime,
+	/// The last retry the process's timeout set off.
+	pub last_timeout: Option<TimedOut>,
+	pub timeouts: u32,
+
+	pub turnaround_time: SimTime,
+	pub waiting_time: SimTime,
+	pub response_time: SimTime,
+	/// The longest the process waited in a ready queue at once.
+	pub longest_wait: SimTime,
+
+	pub level_history: Vec<LevelVisit>,
+}
+
+impl Process {
+	pub fn new(id: u32, io_times: VecDeque<SimTime>, cpu_times: VecDeque<SimTime>) -> Self {
+		Self {
+			id,
+			arrival_time: SimTime::ZERO,
+			next_arrival: SimTime::ZERO,
+			io_times,
+			cpu_times,
+			accelerator_times: VecDeque::new(),
+			accelerator_burst: None,
+			io_priority: IoPriority::default(),
+			priority: 0,
+			tickets: DEFAULT_TICKETS,
+			class: None,
+			think_time: SimTime::ZERO,
+			spawn: None,
+			soft_deadline: None,
+			period: None,
+			retry: None,
+			burst_tags: Vec::new(),
+			phases: Vec::new(),
+			burst_predictor: BurstPredictor::default(),
+			burst_run_time: SimTime::ZERO,
+			bursts_completed: 0,
+			released_at: SimTime::ZERO,
+			deadlines_met: 0,
+			deadlines_missed: 0,
+			burst_waiting_time: SimTime::ZERO,
+			burst_started: false,
+			total_burst_response_time: SimTime::ZERO,
+			total_burst_turnaround_time: SimTime::ZERO,
+			last_timeout: None,
+			timeouts: 0,
+
+			turnaround_time: SimTime::ZERO,
+			waiting_time: SimTime::ZERO,
+			response_time: SimTime::MAX,
+			longest_wait: SimTime::ZERO,
+
+			level_history: vec![LevelVisit {
+				level: 1,
+				entered_at: SimTime::ZERO,
+				left_at: SimTime::ZERO,
+				cpu_time: SimTime::ZERO,
+			}],
+		}
+	}
+
+	// Makes the process arrive at the given time instead of at the start.
+	pub fn arrive_at(&mut self, time: SimTime) {
+		self.arrival_time = time;
+		self.next_arrival = time;
+		self.released_at = time;
+
+		for visit in &mut self.level_history {
+			visit.entered_at = time;
+			visit.left_at = time;
+		}
+	}
+
+	// Records a wait in a ready queue that ended with the process being dispatched.
+	pub fn record_wait(&mut self, waiting_time: SimTime) {
+		self.waiting_time += waiting_time;
+		self.burst_waiting_time += waiting_time;
+		self.longest_wait = self.longest_wait.max(waiting_time);
+	}
+
+	// Records that the process started running, keeping the response time from its first run.
+	pub fn record_start(&mut self, time: SimTime) {
+		self.response_time = self.response_time.min(time - self.arrival_time);
+
+		if !self.burst_started {
+			self.burst_started = true;
+			self.total_burst_response_time += time - self.released_at;
+		}
+	}
+
+	// Records a run of the process at its current level.
+	pub fn record_run(&mut self, cpu_time: SimTime, end_time: SimTime) {
+		self.burst_run_time += cpu_time;
+
+		if let Some(visit) = self.level_history.last_mut() {
+			visit.cpu_time += cpu_time;
+			visit.left_at = end_time;
+		}
+	}
+
+	// Records that the current CPU burst is done at the given time, updating the prediction of the next one.
+	//
+	// The scheduler sets the next arrival first, which is when the next burst becomes ready. The next
+	// burst of a periodic process is instead released a period after the last, and waits for it.
+	pub fn complete_burst(&mut self, end_time: SimTime) {
+		if let Some(deadline) = self.deadline() {
+			if end_time > deadline {
+				self.deadlines_missed += 1;
+			} else {
+				self.deadlines_met += 1;
+			}
+		}
+
+		let burst_turnaround_time = end_time - self.released_at;
+
+		if let Some(Some(tag)) = self.burst_tags.get(self.bursts_completed as usize) {
+			let phase = phase(&mut self.phases, tag);
+
+			phase.burst_count += 1;
+			phase.total_waiting_time += self.burst_waiting_time;
+			phase.total_turnaround_time += burst_turnaround_time;
+			phase.waiting.add(self.burst_waiting_time.as_f64());
+			phase.turnaround.add(burst_turnaround_time.as_f64());
+		}
+
+		self.total_burst_turnaround_time += burst_turnaround_time;
+		self.burst_started = false;
+
+		self.retry_after(end_time);
+
+		self.burst_predictor.update(self.burst_run_time);
+		self.burst_run_time = SimTime::ZERO;
+		self.burst_waiting_time = SimTime::ZERO;
+		self.bursts_completed += 1;
+		self.released_at = self.next_arrival;
+		self.accelerator_burst = self
+			.accelerator_times
+			.pop_front()
+			.filter(|length| !length.is_zero());
+
+		if let Some(period) = self.period.filter(|_| !self.cpu_times.is_empty()) {
+			self.released_at = self.arrival_time + period * u64::from(self.bursts_completed);
+
+			if self.released_at > self.next_arrival {
+				self.turnaround_time += self.released_at - self.next_arrival;
+				self.next_arrival = self.released_at;
+			}
+		}
+	}
+
+	// Lengthens the next CPU burst if the one that just finished waited past the timeout.
+	fn retry_after(&mut self, end_time: SimTime) {
+		let Some(retry) = self.retry else {
+			return;
+		};
+
+		if self.burst_waiting_time <= retry.timeout {
+			return;
+		}
+
+		if let Some(burst) = self.cpu_times.front_mut() {
+			*burst = *burst * retry.factor;
+
+			self.timeouts += 1;
+			self.last_timeout = Some(TimedOut {
+				time: end_time,
+				waited: self.burst_waiting_time,
+				burst: *burst,
+			});
+		}
+	}
+
+	// Moves the time the next CPU burst becomes ready, when its I/O finishes later than the scheduler planned.
+	pub fn release_at(&mut self, time: SimTime) {
+		self.released_at = time;
+	}
+
+	// Returns the time each CPU burst has to finish in once released: its deadline, or else its period.
+	pub fn relative_deadline(&self) -> Option<SimTime> {
+		self.soft_deadline
+			.map(|soft_deadline| soft_deadline.deadline)
+			.or(self.period)
+	}
+
+	// Returns the time the current CPU burst has to finish by, for processes with a deadline or period.
+	pub fn deadline(&self) -> Option<SimTime> {
+		self.relative_deadline()
+			.map(|relative_deadline| self.released_at + relative_deadline)
+	}
+
+	// Returns the share of the misses the process tolerates that it has used, where 1 is the whole budget.
+	pub fn budget_used(&self) -> f64 {
+		let Some(soft_deadline) = self.soft_deadline else {
+			return 0.0;
+		};
+
+		let bursts = self.bursts_completed as usize + self.cpu_times.len();
+		let budget = f64::from(soft_deadline.tolerance) / 100.0 * bursts as f64;
+
+		match self.deadlines_missed {
+			0 => 0.0,
+			_ if budget == 0.0 => f64::INFINITY,
+			missed => f64::from(missed) / budget,
+		}
+	}
+
+	// Returns the average time from each finished CPU burst becoming ready to its first run, rounded to the nearest unit.
+	pub fn burst_response_average(&self) -> SimTime {
+		average(self.total_burst_response_time, self.bursts_completed)
+	}
+
+	// Returns the average time from each finished CPU burst becoming ready to it finishing, rounded to the nearest unit.
+	pub fn burst_turnaround_average(&self) -> SimTime {
+		average(self.total_burst_turnaround_time, self.bursts_completed)
+	}
+
+	// Returns the number of CPU bursts the process has finished.
+	pub fn bursts_completed(&self) -> u32 {
+		self.bursts_completed
+	}
+
+	// Returns whether the current CPU burst has run for a while but is not done yet.
+	pub fn in_burst(&self) -> bool {
+		!self.burst_run_time.is_zero()
+	}
+
+	// Returns the accelerator burst the process has to run before it is ready for the CPU again, if any.
+	pub fn accelerator_burst(&self) -> Option<SimTime> {
+		self.accelerator_burst
+	}
+
+	// Takes the accelerator burst the process is about to run.
+	pub(crate) fn take_accelerator_burst(&mut self) -> Option<SimTime> {
+		self.accelerator_burst.take()
+	}
+
+	// Returns the CPU time the process has run for so far.
+	pub fn cpu_time_used(&self) -> SimTime {
+		self.level_history.iter().map(|visit| visit.cpu_time).sum()
+	}
+
+	// Returns the number of times the process moved down a level.
+	pub fn demotions(&self) -> u32 {
+		self.level_moves(|from, to| to > from)
+	}
+
+	// Returns the number of times the process was boosted up to a higher level.
+	pub fn boosts(&self) -> u32 {
+		self.level_moves(|from, to| to < from)
+	}
+
+	fn level_moves(&self, matches: impl Fn(u32, u32) -> bool) -> u32 {
+		self.level_history
+			.windows(2)
+			.filter(|visits| matches(visits[0].level, visits[1].level))
+			.count() as u32
+	}
+
+	// Moves the process to a new level at the given time.
+	pub fn enter_level(&mut self, level: u32, time: SimTime) {
+		self.level_history.push(LevelVisit {
+			level,
+			entered_at: time,
+			left_at: time,
+			cpu_time: SimTime::ZERO,
+		});
+	}
+}
@@ -0,0 +1,301 @@
+//! The recorded results of a simulation, for analysis after the run.
+
+use alloc::vec::Vec;
+
+use crate::{
+	engine::{Engine, Step, Summary},
+	metrics::MetricsPolicy,
+	process::Process,
+	scheduler::{Event, Scheduler},
+	time::SimTime,
+};
+
+/// The metrics of a finished process.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProcessRecord {
+	pub id: u32,
+	pub arrival_time: SimTime,
+	pub finish_time: SimTime,
+	pub cpu_time: SimTime,
+	pub turnaround_time: SimTime,
+	pub waiting_time: SimTime,
+	pub response_time: SimTime,
+	pub longest_wait: SimTime,
+	pub demotions: u32,
+	pub boosts: u32,
+	/// The deadlines a soft real-time process met and missed, or 0 for other processes.
+	pub deadlines_met: u32,
+	pub deadlines_missed: u32,
+	/// The times waiting too long made the process retry a burst.
+	pub timeouts: u32,
+	pub priority: u32,
+	pub tickets: u32,
+}
+
+impl ProcessRecord {
+	pub(crate) fn new(process: &Process, finish_time: SimTime, metrics: &MetricsPolicy) -> Self {
+		Self {
+			id: process.id,
+			arrival_time: process.arrival_time,
+			finish_time,
+			cpu_time: process.cpu_time_used(),
+			turnaround_time: metrics.turnaround_time(process),
+			waiting_time: metrics.waiting_time(process),
+			response_time: metrics.response_time(process),
+			longest_wait: process.longest_wait,
+			demotions: process.demotions(),
+			boosts: process.boosts(),
+			deadlines_met: process.deadlines_met,
+			deadlines_missed: process.deadlines_missed,
+			timeouts: process.timeouts,
+			priority: process.priority,
+			tickets: process.tickets,
+		}
+	}
+}
+
+/// An event and the step it happened in.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EventRecord {
+	pub time: SimTime,
+	pub cpu: usize,
+	/// The time the step's run ended, which is when a started process stops.
+	pub end: SimTime,
+	pub event: Event,
+}
+
+impl EventRecord {
+	// Returns the ID of the process the event is about, if any.
+	pub fn process_id(&self) -> Option<u32> {
+		match self.event {
+			Event::Start { id, .. }
+			| Event::Downgrade { id, .. }
+			| Event::Spawn { id, .. }
+			| Event::Crash { id, .. }
+			| Event::Timeout { id, .. } => Some(id),
+			_ => None,
+		}
+	}
+}
+
+/// Every finished process and event of a simulation.
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Report {
+	pub summary: Summary,
+	/// The finished processes, in the order they finished.
+	pub processes: Vec<ProcessRecord>,
+	pub events: Vec<EventRecord>,
+}
+
+impl Report {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	// Records the events and finished process of a step, measuring the process by the definitions of the summary.
+	pub fn record(&mut self, step: &Step) {
+		for &event in &step.events {
+			// Boosts happen before the step runs, and the rest once it has.
+			let time = match event {
+				Event::Start { time, .. } | Event::Alarm { time, .. } => time,
+				Event::Boost { .. } => step.start,
+				_ => step.end,
+			};
+
+			self.events.push(EventRecord {
+				time,
+				cpu: step.cpu,
+				end: step.end,
+				event,
+			});
+		}
+
+		if let Some(process) = &step.finished {
+			self.processes
+				.push(ProcessRecord::new(process, step.end, &self.summary.metrics));
+		}
+	}
+
+	// Runs the engine to completion, recording every step.
+	pub fn run<S: Scheduler>(engine: &mut Engine<S>) -> Self {
+		let mut report = Self::new();
+
+		report.summary.metrics = engine.summary().metrics;
+
+		while !engine.is_stopped() {
+			let step = engine.step();
+
+			report.record(&step);
+		}
+
+		report.summary = engine.summary().clone();
+		report
+	}
+
+	// Returns the record of a finished process.
+	pub fn process(&self, id: u32) -> Option<&ProcessRecord> {
+		self.processes.iter().find(|process| process.id == id)
+	}
+}
+
+// Returns the name of the kind of event, as used in dataframes.
+#[cfg(feature = "polars")]
+fn event_kind(event: &Event) -> &'static str {
+	match event {
+		Event::Boost { .. } => "boost",
+		Event::Start { .. } => "start",
+		Event::Downgrade { .. } => "downgrade",
+		Event::Alarm { rising: true, .. } => "alarm_rising",
+		Event::Alarm { rising: false, .. } => "alarm_falling",
+		Event::CpuAdded { .. } => "cpu_added",
+		Event::CpuRemoved { .. } => "cpu_removed",
+		Event::Spawn { .. } => "spawn",
+		Event::Crash { .. } => "crash",
+		Event::Timeout { .. } => "timeout",
+	}
+}
+
+#[cfg(feature = "polars")]
+impl Report {
+	/// Returns one row per event, with the metrics of the process it is about.
+	///
+	/// Columns that do not apply to an event, such as the level of a start, are
+	/// null. Start rows carry the time the run ended, so they can be plotted
+	/// directly as a Gantt chart.
+	pub fn to_dataframe(&self) -> polars::prelude::PolarsResult<polars::prelude::DataFrame> {
+		use polars::prelude::{Column, DataFrame};
+
+		let processes: Vec<_> = self
+			.events
+			.iter()
+			.map(|record| record.process_id().and_then(|id| self.process(id)))
+			.collect();
+
+		let process_column = |name: &str, value: fn(&ProcessRecord) -> SimTime| {
+			let value = |process: &ProcessRecord| value(process).as_u64();
+			let values: Vec<_> = processes.iter().map(|process| process.map(value)).collect();
+
+			Column::new(name.into(), values)
+		};
+
+		let end: Vec<_> = self
+			.events
+			.iter()
+			.map(|record| {
+				matches!(record.event, Event::Start { .. }).then_some(record.end.as_u64())
+			})
+			.collect();
+
+		let level: Vec<_> = self
+			.events
+			.iter()
+			.map(|record| match record.event {
+				Event::Downgrade { level, .. } => Some(level),
+				_ => None,
+			})
+			.collect();
+
+		let count: Vec<_> = self
+			.events
+			.iter()
+			.map(|record| match record.event {
+				Event::Boost { count }
+				| Event::CpuAdded { count }
+				| Event::CpuRemoved { count } => Some(count as u32),
+				Event::Alarm { length, .. } => Some(length as u32),
+				_ => None,
+			})
+			.collect();
+
+		let threshold: Vec<_> = self
+			.events
+			.iter()
+			.map(|record| match record.event {
+				Event::Alarm { threshold, .. } => Some(threshold as u32),
+				_ => None,
+			})
+			.collect();
+
+		DataFrame::new(vec![
+			Column::new(
+				"time".into(),
+				self.events
+					.iter()
+					.map(|record| record.time.as_u64())
+					.collect::<Vec<_>>(),
+			),
+			Column::new(
+				"cpu".into(),
+				self.events
+					.iter()
+					.map(|record| record.cpu as u32)
+					.collect::<Vec<_>>(),
+			),
+			Column::new(
+				"kind".into(),
+				self.events
+					.iter()
+					.map(|record| event_kind(&record.event))
+					.collect::<Vec<_>>(),
+			),
+			Column::new(
+				"process_id".into(),
+				self.events
+					.iter()
+					.map(EventRecord::process_id)
+					.collect::<Vec<_>>(),
+			),
+			Column::new("end".into(), end),
+			Column::new("level".into(), level),
+			Column::new("count".into(), count),
+			Column::new("threshold".into(), threshold),
+			process_column("arrival_time", |process| process.arrival_time),
+			process_column("finish_time", |process| process.finish_time),
+			process_column("turnaround_time", |process| process.turnaround_time),
+			process_column("waiting_time", |process| process.waiting_time),
+			process_column("response_time", |process| process.response_time),
+		])
+	}
+
+	/// Returns one row per finished process, in the order they finished.
+	pub fn processes_dataframe(&self) -> polars::prelude::PolarsResult<polars::prelude::DataFrame> {
+		use polars::prelude::{Column, DataFrame};
+
+		let column = |name: &str, value: fn(&ProcessRecord) -> u32| {
+			Column::new(
+				name.into(),
+				self.processes.iter().map(value).collect::<Vec<_>>(),
+			)
+		};
+		let time_column = |name: &str, value: fn(&ProcessRecord) -> SimTime| {
+			Column::new(
+				name.into(),
+				self.processes
+					.iter()
+					.map(|process| value(process).as_u64())
+					.collect::<Vec<_>>(),
+			)
+		};
+
+		DataFrame::new(vec![
+			column("id", |process| process.id),
+			time_column("arrival_time", |process| process.arrival_time),
+			time_column("finish_time", |process| process.finish_time),
+			time_column("cpu_time", |process| process.cpu_time),
+			time_column("turnaround_time", |process| process.turnaround_time),
+			time_column("waiting_time", |process| process.waiting_time),
+			time_column("response_time", |process| process.response_time),
+			time_column("longest_wait", |process| process.longest_wait),
+			column("demotions", |process| process.demotions),
+			column("boosts", |process| process.boosts),
+			column("deadlines_met", |process| process.deadlines_met),
+			column("deadlines_missed", |process| process.deadlines_missed),
+			column("timeouts", |process| process.timeouts),
+			column("priority", |process| process.priority),
+			column("tickets", |process| process.tickets),
+		])
+	}
+}
@@ -0,0 +1,36 @@
+/// A small seeded random number generator (SplitMix64).
+///
+/// Runs with the same seed always make the same draws, so randomized
+/// schedules can be reproduced.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rng {
+	state: u64,
+}
+
+impl Rng {
+	pub fn new(seed: u64) -> Self {
+		Self { state: seed }
+	}
+
+	pub fn next_u64(&mut self) -> u64 {
+		self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+
+		let mut value = self.state;
+
+		value = (value ^ (value >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+		value = (value ^ (value >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+
+		value ^ (value >> 31)
+	}
+
+	// Returns a number in `0..bound`, which must not be zero.
+	pub fn below(&mut self, bound: u64) -> u64 {
+		self.next_u64() % bound
+	}
+
+	// Returns a number in `0.0..1.0`.
+	pub fn next_f64(&mut self) -> f64 {
+		(self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+	}
+}
@@ -0,0 +1,110 @@
+//! Whether the periodic processes of a workload can meet every deadline,
+//! decided before running it.
+//!
+//! Each CPU burst of a periodic process is one job, released once per period,
+//! and the longest burst is taken as the cost of every job. Processes without
+//! a period are left out, since both real-time schedulers preempt them for
+//! any periodic job.
+
+use alloc::vec::Vec;
+
+use crate::{process::Process, time::SimTime};
+
+/// A periodic process, as far as the analysis is concerned.
+struct Task {
+	id: u32,
+	cost: SimTime,
+	period: SimTime,
+	deadline: SimTime,
+}
+
+/// The result of the schedulability tests of a workload.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Schedulability {
+	/// The share of the CPU the periodic processes need, the sum of each cost over its period.
+	pub utilization: f64,
+	/// Whether EDF meets every deadline, by the density test, which is exact when deadlines equal periods.
+	pub earliest_deadline_first: bool,
+	/// The worst-case response time of each periodic process under rate-monotonic priorities,
+	/// shortest period first, or `None` if it can exceed its deadline.
+	pub response_times: Vec<(u32, Option<SimTime>)>,
+}
+
+impl Schedulability {
+	// Analyzes the periodic processes, returning `None` if there are none.
+	pub fn analyze(processes: &[Process]) -> Option<Self> {
+		let mut tasks: Vec<Task> = processes
+			.iter()
+			.filter_map(|process| {
+				let period = process.period.filter(|period| !period.is_zero())?;
+
+				Some(Task {
+					id: process.id,
+					cost: process.cpu_times.iter().copied().max().unwrap_or_default(),
+					period,
+					deadline: process.relative_deadline().unwrap_or(period),
+				})
+			})
+			.collect();
+
+		if tasks.is_empty() {
+			return None;
+		}
+
+		let utilization = tasks
+			.iter()
+			.map(|task| task.cost.as_f64() / task.period.as_f64())
+			.sum();
+
+		let density: f64 = tasks
+			.iter()
+			.map(|task| task.cost.as_f64() / task.deadline.min(task.period).as_f64())
+			.sum();
+
+		tasks.sort_by_key(|task| (task.period, task.id));
+
+		let response_times = (0..tasks.len())
+			.map(|index| (tasks[index].id, response_time(&tasks, index)))
+			.collect();
+
+		Some(Self {
+			utilization,
+			earliest_deadline_first: density <= 1.0,
+			response_times,
+		})
+	}
+
+	// Returns whether every periodic process meets its deadlines under rate-monotonic scheduling.
+	pub fn rate_monotonic(&self) -> bool {
+		self.response_times
+			.iter()
+			.all(|(_, response_time)| response_time.is_some())
+	}
+}
+
+// Returns the worst-case response time of a task, which is preempted by every task before it.
+//
+// Starting from its cost, the response time grows by each job of a higher priority task
+// released before it ends, until it settles or passes the deadline.
+fn response_time(tasks: &[Task], index: usize) -> Option<SimTime> {
+	let task = &tasks[index];
+	let mut response_time = task.cost;
+
+	loop {
+		let interference: SimTime = tasks[..index]
+			.iter()
+			.map(|other| other.cost * response_time.as_u64().div_ceil(other.period.as_u64()))
+			.sum();
+		let next = task.cost + interference;
+
+		if next > task.deadline {
+			return None;
+		}
+
+		if next == response_time {
+			return Some(response_time);
+		}
+
+		response_time = next;
+	}
+}
@@ -0,0 +1,207 @@
+use alloc::{boxed::Box, vec, vec::Vec};
+
+use crate::{process::Process, time::SimTime};
+
+/// The response of the scheduler after a step.
+#[derive(Default)]
+pub enum Response {
+	Success(Process),
+	Failure(Process),
+
+	#[default]
+	Empty,
+}
+
+/// Something that happened during a step.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(tag = "event", rename_all = "kebab-case")
+)]
+pub enum Event {
+	Boost {
+		count: usize,
+	},
+	Start {
+		id: u32,
+		time: SimTime,
+	},
+	Downgrade {
+		id: u32,
+		level: u32,
+	},
+	Alarm {
+		time: SimTime,
+		length: usize,
+		threshold: usize,
+		rising: bool,
+	},
+	CpuAdded {
+		count: usize,
+	},
+	CpuRemoved {
+		count: usize,
+	},
+	/// The process started a child, which joins the highest queue level.
+	Spawn {
+		parent: u32,
+		id: u32,
+	},
+	/// The process crashed, throwing away the CPU time it had used.
+	Crash {
+		id: u32,
+		wasted: SimTime,
+	},
+	/// The burst that just finished waited past the process's timeout, so its next burst is retried longer.
+	Timeout {
+		id: u32,
+		waited: SimTime,
+		burst: SimTime,
+	},
+}
+
+/// The data returned by the scheduler after a step.
+pub struct Data {
+	pub process_id: u32,
+	pub cpu_time: SimTime,
+	pub idle_time: SimTime,
+
+	pub response: Response,
+	/// The events of the step, in the order they happened.
+	pub events: Vec<Event>,
+}
+
+/// The processes waiting in a single queue level.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LevelLists {
+	pub io: Vec<(u32, SimTime)>,
+	pub cpu: Vec<(u32, SimTime)>,
+}
+
+/// A scheduling algorithm that can be driven by the engine.
+///
+/// Every scheduler can also serve as a single level of the multi-level feedback queue.
+/// Schedulers are `Send`, so an engine can be moved to another thread or held
+/// across `.await` points.
+pub trait Scheduler: Send {
+	// Adds a process to the back of the queue.
+	fn push(&mut self, process: Process);
+
+	// Removes every process from the queue, in queue order.
+	fn drain(&mut self) -> Vec<Process>;
+
+	// Removes a process from the queue, wherever it is.
+	fn remove(&mut self, id: u32) -> Option<Process>;
+
+	// Returns a process in the queue by ID.
+	fn get(&self, id: u32) -> Option<&Process> {
+		self.processes().find(|process| process.id == id)
+	}
+
+	// Returns the processes in the queue, in queue order.
+	fn processes(&self) -> Box<dyn Iterator<Item = &Process> + '_>;
+
+	fn is_empty(&self) -> bool {
+		self.processes().next().is_none()
+	}
+
+	// Returns whether any process has arrived and is waiting for CPU.
+	fn is_ready(&self, current_time: SimTime) -> bool {
+		self.processes()
+			.any(|process| process.next_arrival <= current_time)
+	}
+
+	// Returns a list of processes that are waiting for IO.
+	fn io_remaining(&self, current_time: SimTime) -> Vec<(u32, SimTime)> {
+		self.processes()
+			.filter(|&process| process.next_arrival > current_time)
+			.map(|process| (process.id, process.next_arrival - current_time))
+			.collect()
+	}
+
+	// Returns a list of processes that are waiting for CPU.
+	fn cpu_remaining(&self, current_time: SimTime) -> Vec<(u32, SimTime)> {
+		self.processes()
+			.filter(|&process| process.next_arrival <= current_time)
+			.map(|process| (process.id, process.cpu_times.front().copied().unwrap()))
+			.collect()
+	}
+
+	// Returns the number of queue levels.
+	fn level_count(&self) -> usize {
+		1
+	}
+
+	// Returns the lists of each queue level, from the highest priority down.
+	fn levels(&self, current_time: SimTime) -> Vec<LevelLists> {
+		vec![LevelLists {
+			io: self.io_remaining(current_time),
+			cpu: self.cpu_remaining(current_time),
+		}]
+	}
+
+	// Returns whether the scheduler preempts a running process for one that arrives later, and so
+	// needs every process from the start to see its arrival coming.
+	fn preempts_on_arrival(&self) -> bool {
+		false
+	}
+
+	// Steps the scheduler forward by one time unit.
+	fn step(&mut self, current_time: SimTime) -> Data;
+}
+
+impl<S: Scheduler + ?Sized> Scheduler for Box<S> {
+	fn push(&mut self, process: Process) {
+		(**self).push(process);
+	}
+
+	fn drain(&mut self) -> Vec<Process> {
+		(**self).drain()
+	}
+
+	fn remove(&mut self, id: u32) -> Option<Process> {
+		(**self).remove(id)
+	}
+
+	fn get(&self, id: u32) -> Option<&Process> {
+		(**self).get(id)
+	}
+
+	fn processes(&self) -> Box<dyn Iterator<Item = &Process> + '_> {
+		(**self).processes()
+	}
+
+	fn is_empty(&self) -> bool {
+		(**self).is_empty()
+	}
+
+	fn is_ready(&self, current_time: SimTime) -> bool {
+		(**self).is_ready(current_time)
+	}
+
+	fn io_remaining(&self, current_time: SimTime) -> Vec<(u32, SimTime)> {
+		(**self).io_remaining(current_time)
+	}
+
+	fn cpu_remaining(&self, current_time: SimTime) -> Vec<(u32, SimTime)> {
+		(**self).cpu_remaining(current_time)
+	}
+
+	fn level_count(&self) -> usize {
+		(**self).level_count()
+	}
+
+	fn levels(&self, current_time: SimTime) -> Vec<LevelLists> {
+		(**self).levels(current_time)
+	}
+
+	fn preempts_on_arrival(&self) -> bool {
+		(**self).preempts_on_arrival()
+	}
+
+	fn step(&mut self, current_time: SimTime) -> Data {
+		(**self).step(current_time)
+	}
+}
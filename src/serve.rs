@@ -0,0 +1,214 @@
+//! Streams a run over TCP as newline-delimited JSON, for external dashboards.
+//!
+//! Every client that connects to a [`Server`] receives one JSON object per
+//! line: a `step` message for each step of the engine, carrying its events and
+//! the metrics of the run so far, and a `summary` message once the run ends.
+
+use std::{
+	io::{self, Write},
+	net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+	thread,
+	time::{Duration, Instant},
+};
+
+use serde::Serialize;
+
+use crate::{
+	engine::{Engine, Step, Summary},
+	report::ProcessRecord,
+	scheduler::{Event, Scheduler},
+	sink::EventSink,
+	time::SimTime,
+};
+
+/// The metrics of a run up to the end of a step.
+#[derive(Serialize)]
+struct Metrics {
+	/// The time the next step starts at.
+	time: SimTime,
+	/// The number of processes ready to run in each level, from the first down.
+	ready: Vec<usize>,
+	/// The number of processes waiting for I/O or their arrival in each level.
+	io: Vec<usize>,
+	finished: u32,
+	cpu_utilization: f64,
+	turnaround_average: f64,
+	waiting_average: f64,
+	response_average: f64,
+	/// The 90th percentiles so far, estimated without keeping the samples.
+	turnaround_p90: f64,
+	waiting_p90: f64,
+	response_p90: f64,
+}
+
+/// A message sent to every client.
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+enum Message<'a> {
+	Step {
+		cpu: usize,
+		process_id: u32,
+		start: SimTime,
+		end: SimTime,
+		events: &'a [Event],
+		finished: Option<&'a ProcessRecord>,
+		io_time: SimTime,
+		io_completions: &'a [(u32, SimTime)],
+		metrics: &'a Metrics,
+	},
+	Summary {
+		summary: &'a Summary,
+	},
+}
+
+/// A sink that sends each step to the clients connected to a TCP listener.
+///
+/// Clients may connect at any point of the run, and only see the steps after
+/// they connect. A client that stops reading is dropped once a write to it
+/// has waited for the write timeout.
+pub struct Server {
+	listener: TcpListener,
+	clients: Vec<TcpStream>,
+	write_timeout: Duration,
+	/// Simulated time units per second, or `None` to run as fast as possible.
+	speed: Option<f64>,
+	started: Instant,
+}
+
+impl Server {
+	// Listens on the address without blocking the run.
+	pub fn bind(address: impl ToSocketAddrs) -> io::Result<Self> {
+		let listener = TcpListener::bind(address)?;
+
+		listener.set_nonblocking(true)?;
+
+		Ok(Self {
+			listener,
+			clients: Vec::new(),
+			write_timeout: Duration::from_secs(1),
+			speed: None,
+			started: Instant::now(),
+		})
+	}
+
+	// Paces the run to the number of simulated time units per second, so it can be watched live.
+	pub fn with_speed(mut self, speed: f64) -> Self {
+		self.speed = (speed > 0.0).then_some(speed);
+		self
+	}
+
+	// Sets how long a write may wait on a client that is not reading before the client is dropped.
+	pub fn with_write_timeout(mut self, timeout: Duration) -> Self {
+		// A zero timeout is not allowed by the socket, and would drop every client at once.
+		self.write_timeout = timeout.max(Duration::from_millis(1));
+		self
+	}
+
+	pub fn local_addr(&self) -> io::Result<SocketAddr> {
+		self.listener.local_addr()
+	}
+
+	// Blocks until the first client connects, so it sees the run from the start.
+	pub fn wait_for_client(&mut self) -> io::Result<()> {
+		self.listener.set_nonblocking(false)?;
+		let (client, _) = self.listener.accept()?;
+		self.listener.set_nonblocking(true)?;
+
+		self.add(client);
+		self.started = Instant::now();
+
+		Ok(())
+	}
+
+	// Sends the summary of the finished run to every client.
+	pub fn finish(&mut self, summary: &Summary) {
+		self.send(&Message::Summary { summary });
+	}
+
+	pub fn client_count(&self) -> usize {
+		self.clients.len()
+	}
+
+	fn add(&mut self, client: TcpStream) {
+		// Writes block for up to the timeout, so a slow client never misses a line but a stalled one
+		// cannot hold up the run.
+		let blocking = client.set_nonblocking(false).is_ok()
+			&& client.set_write_timeout(Some(self.write_timeout)).is_ok();
+
+		if blocking {
+			let _ = client.set_nodelay(true);
+			self.clients.push(client);
+		}
+	}
+
+	// Accepts every client that has connected since the last step.
+	fn accept(&mut self) {
+		while let Ok((client, _)) = self.listener.accept() {
+			self.add(client);
+		}
+	}
+
+	fn send(&mut self, message: &Message) {
+		let mut line = serde_json::to_vec(message).expect("messages serialize to JSON");
+		line.push(b'\n');
+
+		// A write that timed out may have sent part of the line, so the client is dropped either way.
+		self.clients
+			.retain_mut(|client| client.write_all(&line).is_ok());
+	}
+
+	// Sleeps until the wall clock catches up with the simulated time.
+	fn pace(&self, time: SimTime) {
+		if let Some(speed) = self.speed {
+			let due = self.started + Duration::from_secs_f64(time.as_f64() / speed);
+
+			thread::sleep(due.saturating_duration_since(Instant::now()));
+		}
+	}
+}
+
+impl EventSink for Server {
+	fn on_step<S: Scheduler>(&mut self, engine: &Engine<S>, step: &Step) {
+		self.pace(step.end);
+		self.accept();
+
+		if self.clients.is_empty() {
+			return;
+		}
+
+		let summary = engine.summary();
+		let time = engine.current_time();
+		let levels = engine.scheduler().levels(time);
+
+		let metrics = Metrics {
+			time,
+			ready: levels.iter().map(|level| level.cpu.len()).collect(),
+			io: levels.iter().map(|level| level.io.len()).collect(),
+			finished: summary.process_count,
+			cpu_utilization: summary.cpu_utilization(),
+			turnaround_average: summary.turnaround_average(),
+			waiting_average: summary.waiting_average(),
+			response_average: summary.response_average(),
+			turnaround_p90: summary.turnaround.p90(),
+			waiting_p90: summary.waiting.p90(),
+			response_p90: summary.response.p90(),
+		};
+
+		let finished = step
+			.finished
+			.as_ref()
+			.map(|process| ProcessRecord::new(process, step.end, &summary.metrics));
+
+		self.send(&Message::Step {
+			cpu: step.cpu,
+			process_id: step.process_id,
+			start: step.start,
+			end: step.end,
+			events: &step.events,
+			finished: finished.as_ref(),
+			io_time: step.io_time,
+			io_completions: &step.io_completions,
+			metrics: &metrics,
+		});
+	}
+}
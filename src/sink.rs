@@ -0,0 +1,60 @@
+//! Where the steps of a run go as the engine takes them.
+//!
+//! The engine hands every step to an [`EventSink`] instead of printing it, so
+//! the console, the TUI, tests, and benchmarks each decide what to do with it.
+
+use alloc::vec::Vec;
+
+use crate::{
+	engine::{Engine, Step},
+	scheduler::{Event, Scheduler},
+	time::SimTime,
+	top::Top,
+};
+
+/// Receives each step of a run, along with the engine that took it.
+pub trait EventSink {
+	// Called before each step, with the engine as the last step left it.
+	fn before_step<S: Scheduler>(&mut self, _engine: &Engine<S>) {}
+
+	// Called after each step, with the engine as the step left it.
+	fn on_step<S: Scheduler>(&mut self, engine: &Engine<S>, step: &Step);
+}
+
+/// A sink that ignores every step.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct NullSink;
+
+impl EventSink for NullSink {
+	fn on_step<S: Scheduler>(&mut self, _engine: &Engine<S>, _step: &Step) {}
+}
+
+/// A sink that keeps the events of every step, for checking them after the run.
+#[derive(Default, Debug)]
+pub struct CollectingSink {
+	pub events: Vec<Event>,
+	/// The processes that finished, with the time they finished at.
+	pub finished: Vec<(u32, SimTime)>,
+}
+
+impl CollectingSink {
+	pub fn new() -> Self {
+		Self::default()
+	}
+}
+
+impl EventSink for CollectingSink {
+	fn on_step<S: Scheduler>(&mut self, _engine: &Engine<S>, step: &Step) {
+		self.events.extend_from_slice(&step.events);
+
+		if let Some(process) = &step.finished {
+			self.finished.push((process.id, step.end));
+		}
+	}
+}
+
+impl EventSink for Top {
+	fn on_step<S: Scheduler>(&mut self, _engine: &Engine<S>, step: &Step) {
+		self.observe(step);
+	}
+}
@@ -0,0 +1,291 @@
+//! Statistics computed online, one sample at a time.
+//!
+//! A long run finishes a great many processes, so the metrics never keep
+//! their samples: [`OnlineStats`] keeps the sum and the variance with Welford's
+//! algorithm, and [`Quantile`] estimates a percentile with the P² algorithm
+//! from five markers. A [`Distribution`] combines the two for the statistics
+//! reports show of a metric. Every average of the summary, its classes and
+//! phases, the accelerator, and the pipeline comes from these.
+
+/// The count, mean, variance, and range of the samples so far.
+#[derive(Clone, Copy, Default, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OnlineStats {
+	count: u64,
+	/// The sum of the samples, which gives the mean exactly for whole-number samples.
+	sum: f64,
+	mean: f64,
+	/// The sum of the squared differences from the mean.
+	squares: f64,
+	min: f64,
+	max: f64,
+}
+
+impl OnlineStats {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	pub fn add(&mut self, sample: f64) {
+		if self.count == 0 {
+			self.min = sample;
+			self.max = sample;
+		}
+
+		self.count += 1;
+		self.sum += sample;
+		self.min = self.min.min(sample);
+		self.max = self.max.max(sample);
+
+		let delta = sample - self.mean;
+
+		self.mean += delta / self.count as f64;
+		self.squares += delta * (sample - self.mean);
+	}
+
+	// Adds the samples of another set, as if they had been added one by one.
+	pub fn merge(&mut self, other: &Self) {
+		if other.count == 0 {
+			return;
+		}
+
+		if self.count == 0 {
+			*self = *other;
+			return;
+		}
+
+		let count = self.count + other.count;
+		let delta = other.mean - self.mean;
+
+		self.squares +=
+			other.squares + delta * delta * (self.count as f64 * other.count as f64 / count as f64);
+		self.mean += delta * other.count as f64 / count as f64;
+		self.count = count;
+		self.sum += other.sum;
+		self.min = self.min.min(other.min);
+		self.max = self.max.max(other.max);
+	}
+
+	pub fn count(&self) -> u64 {
+		self.count
+	}
+
+	// Returns the mean, or NaN without samples.
+	pub fn mean(&self) -> f64 {
+		if self.count == 0 {
+			return f64::NAN;
+		}
+
+		self.sum / self.count as f64
+	}
+
+	// Returns the population variance, or NaN without samples.
+	pub fn variance(&self) -> f64 {
+		self.squares / self.count as f64
+	}
+
+	// Returns the population standard deviation, or NaN without samples.
+	#[cfg(feature = "std")]
+	pub fn std_dev(&self) -> f64 {
+		self.variance().sqrt()
+	}
+
+	pub fn min(&self) -> Option<f64> {
+		(self.count > 0).then_some(self.min)
+	}
+
+	pub fn max(&self) -> Option<f64> {
+		(self.count > 0).then_some(self.max)
+	}
+}
+
+/// An estimate of one quantile of the samples so far, by the P² algorithm.
+///
+/// Five markers track the minimum, the quantile, the maximum, and the points
+/// halfway between, moving towards their ideal positions as samples arrive, so
+/// the estimate takes constant memory however many samples there are.
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Quantile {
+	/// The quantile to estimate, from 0 to 1.
+	probability: f64,
+	count: u64,
+	/// The height of each marker, which holds the first samples until there are five.
+	heights: [f64; 5],
+	/// The position of each marker among the sorted samples, counting from 1.
+	positions: [f64; 5],
+	/// The position each marker should be at.
+	desired: [f64; 5],
+}
+
+impl Quantile {
+	pub fn new(probability: f64) -> Self {
+		let p = probability.clamp(0.0, 1.0);
+
+		Self {
+			probability: p,
+			count: 0,
+			heights: [0.0; 5],
+			positions: [1.0, 2.0, 3.0, 4.0, 5.0],
+			desired: [1.0, 1.0 + 2.0 * p, 1.0 + 4.0 * p, 3.0 + 2.0 * p, 5.0],
+		}
+	}
+
+	pub fn probability(&self) -> f64 {
+		self.probability
+	}
+
+	pub fn count(&self) -> u64 {
+		self.count
+	}
+
+	pub fn add(&mut self, sample: f64) {
+		if self.count < 5 {
+			self.heights[self.count as usize] = sample;
+			self.count += 1;
+
+			if self.count == 5 {
+				self.heights.sort_by(f64::total_cmp);
+			}
+
+			return;
+		}
+
+		self.count += 1;
+
+		// Find the cell the sample falls in, stretching the ends to include it.
+		let cell = if sample < self.heights[0] {
+			self.heights[0] = sample;
+			0
+		} else if sample >= self.heights[4] {
+			self.heights[4] = sample;
+			3
+		} else {
+			(1..5)
+				.find(|&marker| sample < self.heights[marker])
+				.unwrap() - 1
+		};
+
+		for position in &mut self.positions[cell + 1..] {
+			*position += 1.0;
+		}
+
+		let p = self.probability;
+
+		for (desired, increment) in
+			self.desired
+				.iter_mut()
+				.zip([0.0, p / 2.0, p, (1.0 + p) / 2.0, 1.0])
+		{
+			*desired += increment;
+		}
+
+		for marker in 1..4 {
+			self.adjust(marker);
+		}
+	}
+
+	// Moves a middle marker by one position if it is off from where it should be and has room.
+	fn adjust(&mut self, marker: usize) {
+		let offset = self.desired[marker] - self.positions[marker];
+		let room_above = self.positions[marker + 1] - self.positions[marker];
+		let room_below = self.positions[marker - 1] - self.positions[marker];
+
+		if !(offset >= 1.0 && room_above > 1.0 || offset <= -1.0 && room_below < -1.0) {
+			return;
+		}
+
+		let step = if offset > 0.0 { 1.0 } else { -1.0 };
+		let [below, at, above] = [marker - 1, marker, marker + 1];
+		let (q, n) = (&self.heights, &self.positions);
+
+		// The piecewise-parabolic prediction, unless it breaks the order of the markers.
+		let parabolic = q[at]
+			+ step / (n[above] - n[below])
+				* ((n[at] - n[below] + step) * (q[above] - q[at]) / (n[above] - n[at])
+					+ (n[above] - n[at] - step) * (q[at] - q[below]) / (n[at] - n[below]));
+
+		self.heights[at] = if q[below] < parabolic && parabolic < q[above] {
+			parabolic
+		} else {
+			let next = if step > 0.0 { above } else { below };
+
+			q[at] + step * (q[next] - q[at]) / (n[next] - n[at])
+		};
+		self.positions[at] += step;
+	}
+
+	// Returns the estimate, exact below five samples, or NaN without samples.
+	pub fn estimate(&self) -> f64 {
+		match self.count {
+			0 => f64::NAN,
+			1..=4 => {
+				let mut samples = self.heights;
+				let samples = &mut samples[..self.count as usize];
+
+				samples.sort_by(f64::total_cmp);
+
+				// The nearest rank, the smallest sample with at least that share of the samples up to it.
+				let rank = self.probability * self.count as f64;
+				let index = rank as usize + usize::from((rank as usize as f64) < rank);
+
+				samples[index.saturating_sub(1).min(samples.len() - 1)]
+			}
+			_ => self.heights[2],
+		}
+	}
+}
+
+/// The statistics of a metric across the samples so far: its mean, spread, and percentiles.
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Distribution {
+	pub stats: OnlineStats,
+	median: Quantile,
+	p90: Quantile,
+	p99: Quantile,
+}
+
+impl Default for Distribution {
+	fn default() -> Self {
+		Self {
+			stats: OnlineStats::new(),
+			median: Quantile::new(0.5),
+			p90: Quantile::new(0.9),
+			p99: Quantile::new(0.99),
+		}
+	}
+}
+
+impl Distribution {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	pub fn add(&mut self, sample: f64) {
+		self.stats.add(sample);
+		self.median.add(sample);
+		self.p90.add(sample);
+		self.p99.add(sample);
+	}
+
+	pub fn count(&self) -> u64 {
+		self.stats.count()
+	}
+
+	pub fn mean(&self) -> f64 {
+		self.stats.mean()
+	}
+
+	pub fn median(&self) -> f64 {
+		self.median.estimate()
+	}
+
+	pub fn p90(&self) -> f64 {
+		self.p90.estimate()
+	}
+
+	pub fn p99(&self) -> f64 {
+		self.p99.estimate()
+	}
+}
@@ -0,0 +1,202 @@
+//! Scaffolding for precise scheduling tests.
+//!
+//! The [`schedule_test!`](crate::schedule_test) macro runs a workload under an
+//! algorithm and asserts the exact slices each process ran in, written the way
+//! a Gantt chart reads:
+//!
+//! ```
+//! use mlfq_scheduler_simulator::schedule_test;
+//!
+//! schedule_test! {
+//!     workload: ["cpu=8", "cpu=8"],
+//!     algorithm: rr(4),
+//!     expect: [P1@0..4, P2@4..8, P1@8..12, P2@12..16],
+//! }
+//! ```
+//!
+//! The workload lines use the workload file format, and the algorithm is one of
+//! the constructors in this module, called with its arguments.
+
+use alloc::{collections::VecDeque, format, string::String, vec::Vec};
+
+use crate::{
+	algorithms::{
+		EarliestDeadlineFirst, FirstComeFirstServe, LevelPolicy, Lottery, MultiLevelFeedbackQueue,
+		PriorityScheduler, RateMonotonic, ShortestJobFirst, SoftRealTime, Stride,
+	},
+	engine::{Engine, Summary},
+	process::Process,
+	scheduler::Scheduler,
+	time::SimTime,
+	trace::{Trace, TraceRecorder},
+	workload::Workload,
+};
+
+/// Runs a workload under an algorithm and asserts the slices each process ran in.
+///
+/// The fields are `workload`, a list of lines in the workload file format;
+/// `algorithm`, a constructor from [`testing`](crate::testing) such as `fcfs`
+/// or `rr(4)`; optionally `cpus`; and `expect`, the slices in dispatch order
+/// as `P<id>@<start>..<end>`.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! schedule_test {
+	(
+		workload: [$($line:expr),* $(,)?],
+		algorithm: $algorithm:ident $(($($argument:expr),* $(,)?))?,
+		$(cpus: $cpus:expr,)?
+		expect: [$($id:ident @ $start:literal .. $end:literal),* $(,)?] $(,)?
+	) => {{
+		#[allow(unused_mut)]
+		let mut cpus = 1;
+		$(cpus = $cpus;)?
+
+		let trace = $crate::testing::record(
+			&[$($line),*],
+			$crate::testing::$algorithm($($($argument),*)?),
+			cpus,
+		);
+
+		$crate::testing::assert_slices(
+			&trace,
+			&[$((stringify!($id), $start, $end)),*],
+		);
+	}};
+}
+
+// Parses the workload lines and runs them on the scheduler built by the algorithm, recording a trace.
+pub fn record<S: Scheduler>(
+	lines: &[&str],
+	algorithm: impl FnOnce(Vec<Process>) -> S,
+	cpus: usize,
+) -> Trace {
+	let mut engine = engine(lines, algorithm)
+		.with_cpus(cpus)
+		.with_recorder(TraceRecorder::new());
+
+	engine.run();
+
+	engine.recorder().unwrap().trace()
+}
+
+// Builds an engine for the workload lines under an algorithm, panicking if the workload is invalid.
+pub fn engine<S: Scheduler>(
+	lines: &[&str],
+	algorithm: impl FnOnce(Vec<Process>) -> S,
+) -> Engine<S> {
+	let workload = match Workload::parse(&lines.join("\n")) {
+		Ok(workload) => workload,
+		Err(error) => panic!("invalid workload: {error}"),
+	};
+
+	Engine::new(algorithm(workload.into_processes()))
+}
+
+// Runs the workload lines to the end under an algorithm and returns the summary.
+pub fn summary<S: Scheduler>(lines: &[&str], algorithm: impl FnOnce(Vec<Process>) -> S) -> Summary {
+	let mut engine = engine(lines, algorithm);
+
+	engine.run();
+	engine.summary().clone()
+}
+
+// Asserts that the trace ran exactly the expected slices, given as the name, start, and end of each.
+pub fn assert_slices(trace: &Trace, expected: &[(&str, u64, u64)]) {
+	let actual: Vec<String> = trace
+		.slices()
+		.iter()
+		.map(|slice| format!("P{}@{}..{}", slice.id, slice.start, slice.end))
+		.collect();
+	let expected: Vec<String> = expected
+		.iter()
+		.map(|(name, start, end)| format!("{name}@{start}..{end}"))
+		.collect();
+
+	if actual != expected {
+		let first = actual
+			.iter()
+			.zip(&expected)
+			.position(|(actual, expected)| actual != expected)
+			.unwrap_or(actual.len().min(expected.len()));
+
+		panic!(
+			"schedules differ at slice {}\n  expected: [{}]\n    actual: [{}]",
+			first + 1,
+			expected.join(", "),
+			actual.join(", "),
+		);
+	}
+}
+
+pub fn fcfs() -> impl FnOnce(Vec<Process>) -> FirstComeFirstServe {
+	FirstComeFirstServe::from_processes
+}
+
+pub fn sjf() -> impl FnOnce(Vec<Process>) -> ShortestJobFirst {
+	ShortestJobFirst::from_processes
+}
+
+// Orders by the burst lengths predicted from each process's history.
+pub fn sjf_predicted() -> impl FnOnce(Vec<Process>) -> ShortestJobFirst {
+	|processes| ShortestJobFirst::from_processes(processes).with_prediction(true)
+}
+
+pub fn priority(preemptive: bool) -> impl FnOnce(Vec<Process>) -> PriorityScheduler {
+	move |processes| PriorityScheduler::from_processes(processes, preemptive)
+}
+
+// Round robin, as a single level MLFQ so processes can run past their quantum.
+pub fn rr(quantum: u64) -> impl FnOnce(Vec<Process>) -> MultiLevelFeedbackQueue {
+	move |processes| {
+		MultiLevelFeedbackQueue::with_levels(
+			VecDeque::from(processes),
+			&[LevelPolicy::RoundRobin {
+				quanta: SimTime::new(quantum),
+			}],
+		)
+	}
+}
+
+// Round robin where a process that yields early donates the rest of its quantum to the next one.
+pub fn rr_donating(quantum: u64) -> impl FnOnce(Vec<Process>) -> MultiLevelFeedbackQueue {
+	move |processes| rr(quantum)(processes).with_donation(true)
+}
+
+// The MLFQ with the default levels.
+pub fn mlfq() -> impl FnOnce(Vec<Process>) -> MultiLevelFeedbackQueue {
+	|processes| MultiLevelFeedbackQueue::from_processes(VecDeque::from(processes))
+}
+
+// The MLFQ with levels parsed from a policy list such as "rr:5,rr:10,fcfs".
+pub fn mlfq_levels(levels: &str) -> impl FnOnce(Vec<Process>) -> MultiLevelFeedbackQueue {
+	let policies: Vec<LevelPolicy> = levels
+		.split(',')
+		.map(|policy| {
+			policy
+				.parse()
+				.unwrap_or_else(|error| panic!("invalid level {policy:?}: {error}"))
+		})
+		.collect();
+
+	move |processes| MultiLevelFeedbackQueue::with_levels(VecDeque::from(processes), &policies)
+}
+
+pub fn lottery(quantum: u64, seed: u64) -> impl FnOnce(Vec<Process>) -> Lottery {
+	move |processes| Lottery::from_processes(processes, SimTime::new(quantum), seed)
+}
+
+pub fn stride(quantum: u64) -> impl FnOnce(Vec<Process>) -> Stride {
+	move |processes| Stride::from_processes(processes, SimTime::new(quantum))
+}
+
+pub fn edf() -> impl FnOnce(Vec<Process>) -> EarliestDeadlineFirst {
+	EarliestDeadlineFirst::from_processes
+}
+
+pub fn rm() -> impl FnOnce(Vec<Process>) -> RateMonotonic {
+	RateMonotonic::from_processes
+}
+
+pub fn soft_rt(quantum: u64) -> impl FnOnce(Vec<Process>) -> SoftRealTime {
+	move |processes| SoftRealTime::from_processes(processes, SimTime::new(quantum))
+}
@@ -0,0 +1,137 @@
+//! The simulation clock.
+//!
+//! Every time and duration in a simulation is a [`SimTime`]. Its arithmetic
+//! saturates instead of overflowing, so long runs cannot wrap around or panic,
+//! and a difference that would be negative, such as the time until an arrival
+//! that has already happened, is zero.
+
+use core::{
+	fmt,
+	iter::Sum,
+	num::ParseIntError,
+	ops::{Add, AddAssign, Mul, Sub, SubAssign},
+	str::FromStr,
+};
+
+/// A point in simulated time, or the length of a span of it.
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(transparent)
+)]
+pub struct SimTime(pub u64);
+
+impl SimTime {
+	pub const ZERO: Self = Self(0);
+	pub const MAX: Self = Self(u64::MAX);
+
+	pub const fn new(time: u64) -> Self {
+		Self(time)
+	}
+
+	pub const fn as_u64(self) -> u64 {
+		self.0
+	}
+
+	// Returns the time as a float, for averages and ratios.
+	pub fn as_f64(self) -> f64 {
+		self.0 as f64
+	}
+
+	pub fn is_zero(self) -> bool {
+		self.0 == 0
+	}
+
+	pub fn checked_add(self, other: Self) -> Option<Self> {
+		self.0.checked_add(other.0).map(Self)
+	}
+
+	pub fn checked_sub(self, other: Self) -> Option<Self> {
+		self.0.checked_sub(other.0).map(Self)
+	}
+
+	pub fn saturating_sub(self, other: Self) -> Self {
+		Self(self.0.saturating_sub(other.0))
+	}
+
+	// Returns the distance between two times, whichever comes first.
+	pub fn abs_diff(self, other: Self) -> Self {
+		Self(self.0.abs_diff(other.0))
+	}
+}
+
+impl From<u32> for SimTime {
+	fn from(time: u32) -> Self {
+		Self(u64::from(time))
+	}
+}
+
+impl From<u64> for SimTime {
+	fn from(time: u64) -> Self {
+		Self(time)
+	}
+}
+
+impl Add for SimTime {
+	type Output = Self;
+
+	fn add(self, other: Self) -> Self {
+		Self(self.0.saturating_add(other.0))
+	}
+}
+
+impl AddAssign for SimTime {
+	fn add_assign(&mut self, other: Self) {
+		*self = *self + other;
+	}
+}
+
+// Repeats a span of time, such as a period, the given number of times.
+impl Mul<u64> for SimTime {
+	type Output = Self;
+
+	fn mul(self, count: u64) -> Self {
+		Self(self.0.saturating_mul(count))
+	}
+}
+
+impl Sub for SimTime {
+	type Output = Self;
+
+	fn sub(self, other: Self) -> Self {
+		self.saturating_sub(other)
+	}
+}
+
+impl SubAssign for SimTime {
+	fn sub_assign(&mut self, other: Self) {
+		*self = *self - other;
+	}
+}
+
+impl Sum for SimTime {
+	fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+		iter.fold(Self::ZERO, Add::add)
+	}
+}
+
+impl<'a> Sum<&'a SimTime> for SimTime {
+	fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+		iter.copied().sum()
+	}
+}
+
+impl fmt::Display for SimTime {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.0.fmt(f)
+	}
+}
+
+impl FromStr for SimTime {
+	type Err = ParseIntError;
+
+	fn from_str(text: &str) -> Result<Self, Self::Err> {
+		text.parse().map(Self)
+	}
+}
@@ -0,0 +1,199 @@
+//! A live table of the processes in a run, in the style of `top`.
+//!
+//! A [`Top`] is fed each step of the engine as it happens and keeps the CPU
+//! time of every process along with the runs in its sliding window, so the
+//! table can be read at any point of the run without replaying it.
+
+use alloc::{
+	collections::{BTreeMap, BTreeSet, VecDeque},
+	vec::Vec,
+};
+
+use crate::{
+	engine::{Engine, Step},
+	scheduler::{Event, Scheduler},
+	time::SimTime,
+};
+
+/// What a process is doing at the time of the table.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum State {
+	Running,
+	/// Waiting in a ready queue.
+	Ready,
+	/// Waiting for I/O, the user, or its arrival.
+	Blocked,
+	Finished,
+	Crashed,
+}
+
+/// One line of the table.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Row {
+	pub id: u32,
+	pub state: State,
+	/// The queue level the process is in, counting from 1.
+	pub level: u32,
+	/// The CPU time the process has run for so far.
+	pub cpu_time: SimTime,
+	/// The share of one CPU the process ran for over the window, from 0 to 1.
+	pub recent_share: f64,
+}
+
+/// A run of a process on a CPU.
+struct Run {
+	id: u32,
+	start: SimTime,
+	end: SimTime,
+}
+
+/// What is known about a process from the steps so far.
+struct Entry {
+	cpu_time: SimTime,
+	level: u32,
+	// Set once the process finishes or crashes.
+	ended: Option<State>,
+}
+
+impl Default for Entry {
+	// Every process starts in the first level.
+	fn default() -> Self {
+		Self {
+			cpu_time: SimTime::ZERO,
+			level: 1,
+			ended: None,
+		}
+	}
+}
+
+/// The per-process metrics of a run, updated one step at a time.
+pub struct Top {
+	window: SimTime,
+	entries: BTreeMap<u32, Entry>,
+	// The runs that may still overlap the window, in the order they started.
+	runs: VecDeque<Run>,
+}
+
+impl Top {
+	// Creates an empty table whose recent share covers the given window of time.
+	pub fn new(window: SimTime) -> Self {
+		Self {
+			window,
+			entries: BTreeMap::new(),
+			runs: VecDeque::new(),
+		}
+	}
+
+	// Adds the run of a step, and the processes it finished, crashed, or moved between levels.
+	pub fn observe(&mut self, step: &Step) {
+		// Steps start in time order, so older runs can no longer reach into the window.
+		while self
+			.runs
+			.front()
+			.is_some_and(|run| run.end + self.window <= step.start)
+		{
+			self.runs.pop_front();
+		}
+
+		let entry = self.entries.entry(step.process_id).or_default();
+
+		entry.cpu_time += step.end - step.start;
+
+		if step.finished.is_some() {
+			entry.ended = Some(State::Finished);
+		}
+
+		for event in &step.events {
+			match *event {
+				Event::Crash { id, .. } => {
+					self.entries.entry(id).or_default().ended = Some(State::Crashed);
+				}
+				Event::Downgrade { id, level } => self.entries.entry(id).or_default().level = level,
+				// A boost moves every process in the lower levels back to the first.
+				Event::Boost { .. } => {
+					for entry in self.entries.values_mut() {
+						entry.level = 1;
+					}
+				}
+				_ => {}
+			}
+		}
+
+		self.runs.push_back(Run {
+			id: step.process_id,
+			start: step.start,
+			end: step.end,
+		});
+	}
+
+	// Returns the table at the current time of the engine, busiest processes first.
+	pub fn rows<S: Scheduler>(&self, engine: &Engine<S>) -> Vec<Row> {
+		let now = engine.current_time();
+		let window_start = now.saturating_sub(self.window);
+
+		// The processes in the ready queues, including those that have not run yet.
+		let ready: Vec<u32> = engine
+			.scheduler()
+			.cpu_remaining(now)
+			.into_iter()
+			.map(|(id, _)| id)
+			.collect();
+
+		let mut recent: BTreeMap<u32, SimTime> = BTreeMap::new();
+		let mut ahead: BTreeMap<u32, SimTime> = BTreeMap::new();
+		let mut running: Vec<u32> = Vec::new();
+
+		for run in &self.runs {
+			// Only the part of each run up to now has happened yet.
+			let start = run.start.max(window_start).min(now);
+			let end = run.end.min(now);
+
+			*recent.entry(run.id).or_default() += end - start;
+			*ahead.entry(run.id).or_default() += run.end - run.start.max(now);
+
+			if run.start <= now && now < run.end {
+				running.push(run.id);
+			}
+		}
+
+		// Early in the run, the share is of the time that has passed so far.
+		let span = (now - window_start).max(SimTime::new(1));
+
+		let default = Entry::default();
+		let ids = self.entries.keys().chain(&ready).copied();
+
+		let mut rows: Vec<Row> = ids
+			.collect::<BTreeSet<u32>>()
+			.into_iter()
+			.map(|id| {
+				let entry = self.entries.get(&id).unwrap_or(&default);
+				let state = if running.contains(&id) {
+					State::Running
+				} else if ready.contains(&id) {
+					State::Ready
+				} else {
+					entry.ended.unwrap_or(State::Blocked)
+				};
+
+				Row {
+					id,
+					state,
+					level: entry.level,
+					cpu_time: entry.cpu_time - ahead.get(&id).copied().unwrap_or_default(),
+					recent_share: recent.get(&id).copied().unwrap_or_default().as_f64()
+						/ span.as_f64(),
+				}
+			})
+			.collect();
+
+		rows.sort_by(|first, second| {
+			second
+				.recent_share
+				.total_cmp(&first.recent_share)
+				.then(second.cpu_time.cmp(&first.cpu_time))
+				.then(first.id.cmp(&second.id))
+		});
+
+		rows
+	}
+}
@@ -0,0 +1,410 @@
+//! Recording every event of a run, so it can be saved and replayed.
+//!
+//! A trace holds one record per event, such as a dispatch, an I/O burst, or a
+//! finished process, in time order. With the `std` feature traces are saved
+//! as JSON, and `--replay` prints a saved trace the way the console prints a
+//! live run, which makes it easy to diff the behavior of two versions. A
+//! [`TraceIndex`] answers queries about a trace, such as what was running at
+//! a time, by binary search instead of scanning every record.
+
+use alloc::{collections::BTreeMap, vec::Vec};
+use core::ops::Range;
+
+use crate::{engine::Step, metrics::MetricsPolicy, scheduler::Event, time::SimTime};
+
+/// Something that happened to a process or the simulation.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(tag = "event", rename_all = "kebab-case")
+)]
+pub enum TraceEvent {
+	Dispatch {
+		id: u32,
+	},
+	/// The process stopped before finishing its burst.
+	Preempt {
+		id: u32,
+	},
+	Demote {
+		id: u32,
+		level: u32,
+	},
+	Boost {
+		count: usize,
+	},
+	IoStart {
+		id: u32,
+		length: SimTime,
+	},
+	IoComplete {
+		id: u32,
+	},
+	Finish {
+		id: u32,
+		turnaround_time: SimTime,
+		waiting_time: SimTime,
+		response_time: SimTime,
+	},
+	Alarm {
+		length: usize,
+		threshold: usize,
+		rising: bool,
+	},
+	CpuAdded {
+		count: usize,
+	},
+	CpuRemoved {
+		count: usize,
+	},
+	Spawn {
+		parent: u32,
+		id: u32,
+	},
+	Crash {
+		id: u32,
+		wasted: SimTime,
+	},
+	Timeout {
+		id: u32,
+		waited: SimTime,
+		burst: SimTime,
+	},
+}
+
+impl TraceEvent {
+	// Returns the ID of the process the event is about, if any, which is the child for a spawn.
+	pub fn process_id(&self) -> Option<u32> {
+		match *self {
+			Self::Dispatch { id }
+			| Self::Preempt { id }
+			| Self::Demote { id, .. }
+			| Self::IoStart { id, .. }
+			| Self::IoComplete { id }
+			| Self::Finish { id, .. }
+			| Self::Spawn { id, .. }
+			| Self::Crash { id, .. }
+			| Self::Timeout { id, .. } => Some(id),
+			Self::Boost { .. }
+			| Self::Alarm { .. }
+			| Self::CpuAdded { .. }
+			| Self::CpuRemoved { .. } => None,
+		}
+	}
+}
+
+/// An event and when and where it happened.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TraceRecord {
+	pub time: SimTime,
+	pub cpu: usize,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub event: TraceEvent,
+}
+
+/// Every event of a run, in time order.
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Trace {
+	pub records: Vec<TraceRecord>,
+	/// The time the run was cancelled at, if it stopped before every process finished.
+	#[cfg_attr(
+		feature = "serde",
+		serde(default, skip_serializing_if = "Option::is_none")
+	)]
+	pub cancelled_at: Option<SimTime>,
+}
+
+/// A process that ran on a CPU between two points in time.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Slice {
+	pub cpu: usize,
+	pub id: u32,
+	pub start: SimTime,
+	pub end: SimTime,
+}
+
+impl Trace {
+	// Returns when each process ran, from its dispatch until it stopped, in dispatch order.
+	pub fn slices(&self) -> Vec<Slice> {
+		let mut slices: Vec<Slice> = Vec::new();
+		// The indices of the slices still running on each CPU.
+		let mut running: Vec<usize> = Vec::new();
+
+		for record in &self.records {
+			let id = match record.event {
+				TraceEvent::Dispatch { id } => {
+					running.push(slices.len());
+					slices.push(Slice {
+						cpu: record.cpu,
+						id,
+						start: record.time,
+						end: record.time,
+					});
+
+					continue;
+				}
+				TraceEvent::Preempt { id }
+				| TraceEvent::IoStart { id, .. }
+				| TraceEvent::Finish { id, .. }
+				| TraceEvent::Crash { id, .. } => id,
+				_ => continue,
+			};
+
+			if let Some(position) = running
+				.iter()
+				.position(|&index| slices[index].cpu == record.cpu && slices[index].id == id)
+			{
+				slices[running.remove(position)].end = record.time;
+			}
+		}
+
+		slices
+	}
+}
+
+/// A trace indexed by time and by process.
+///
+/// The records of the trace must be in time order, as recorded traces are.
+pub struct TraceIndex<'a> {
+	trace: &'a Trace,
+	/// The slices of each CPU, in the order they started, which never overlap.
+	cpus: Vec<Vec<Slice>>,
+	/// The indices of the records about each process, in time order.
+	processes: BTreeMap<u32, Vec<usize>>,
+}
+
+impl<'a> TraceIndex<'a> {
+	pub fn new(trace: &'a Trace) -> Self {
+		let mut cpus: Vec<Vec<Slice>> = Vec::new();
+
+		for slice in trace.slices() {
+			if slice.cpu >= cpus.len() {
+				cpus.resize(slice.cpu + 1, Vec::new());
+			}
+
+			cpus[slice.cpu].push(slice);
+		}
+
+		for slices in &mut cpus {
+			slices.sort_by_key(|slice| slice.start);
+		}
+
+		let mut processes: BTreeMap<u32, Vec<usize>> = BTreeMap::new();
+
+		for (index, record) in trace.records.iter().enumerate() {
+			// A spawn is about the parent as well as the child.
+			if let TraceEvent::Spawn { parent, .. } = record.event {
+				processes.entry(parent).or_default().push(index);
+			}
+
+			if let Some(id) = record.event.process_id() {
+				processes.entry(id).or_default().push(index);
+			}
+		}
+
+		Self {
+			trace,
+			cpus,
+			processes,
+		}
+	}
+
+	pub fn trace(&self) -> &'a Trace {
+		self.trace
+	}
+
+	// Returns every slice, CPU by CPU.
+	pub fn slices(&self) -> impl Iterator<Item = &Slice> + '_ {
+		self.cpus.iter().flatten()
+	}
+
+	// Returns the slices running at the time, at most one per CPU.
+	pub fn running_at(&self, time: SimTime) -> Vec<Slice> {
+		self.cpus
+			.iter()
+			.filter_map(|slices| {
+				// The last slice started by then is the only one that can still be running.
+				let started = slices.partition_point(|slice| slice.start <= time);
+				let slice = slices[..started].last()?;
+
+				(time < slice.end).then_some(*slice)
+			})
+			.collect()
+	}
+
+	// Returns the records in the time range.
+	pub fn between(&self, range: Range<SimTime>) -> &'a [TraceRecord] {
+		let records = &self.trace.records;
+		let start = records.partition_point(|record| record.time < range.start);
+		let end = records.partition_point(|record| record.time < range.end);
+
+		&records[start..end.max(start)]
+	}
+
+	// Returns the records about a process in the time range.
+	pub fn process_events(&self, id: u32, range: Range<SimTime>) -> Vec<&'a TraceRecord> {
+		let Some(indices) = self.processes.get(&id) else {
+			return Vec::new();
+		};
+
+		let records = &self.trace.records;
+		let start = indices.partition_point(|&index| records[index].time < range.start);
+		let end = indices.partition_point(|&index| records[index].time < range.end);
+
+		indices[start..end.max(start)]
+			.iter()
+			.map(|&index| &records[index])
+			.collect()
+	}
+
+	// Returns when the process ran, in the order it started.
+	pub fn process_slices(&self, id: u32) -> Vec<Slice> {
+		let mut slices: Vec<Slice> = self
+			.cpus
+			.iter()
+			.flatten()
+			.filter(|slice| slice.id == id)
+			.copied()
+			.collect();
+
+		slices.sort_by_key(|slice| (slice.start, slice.cpu));
+		slices
+	}
+}
+
+/// Builds a trace from the steps of an engine.
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TraceRecorder {
+	records: Vec<TraceRecord>,
+	cancelled_at: Option<SimTime>,
+}
+
+impl TraceRecorder {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	fn push(&mut self, time: SimTime, cpu: usize, event: TraceEvent) {
+		self.records.push(TraceRecord { time, cpu, event });
+	}
+
+	// Records the events of a step, measuring a finished process by the given definitions.
+	pub fn record(&mut self, step: &Step, metrics: &MetricsPolicy) {
+		let cpu = step.cpu;
+
+		for event in &step.events {
+			match *event {
+				Event::Boost { count } => self.push(step.start, cpu, TraceEvent::Boost { count }),
+				Event::Start { id, time } => self.push(time, cpu, TraceEvent::Dispatch { id }),
+				Event::Downgrade { id, level } => {
+					self.push(step.end, cpu, TraceEvent::Preempt { id });
+					self.push(step.end, cpu, TraceEvent::Demote { id, level });
+				}
+				Event::Alarm {
+					time,
+					length,
+					threshold,
+					rising,
+				} => self.push(
+					time,
+					cpu,
+					TraceEvent::Alarm {
+						length,
+						threshold,
+						rising,
+					},
+				),
+				Event::CpuAdded { count } => {
+					self.push(step.end, cpu, TraceEvent::CpuAdded { count })
+				}
+				Event::CpuRemoved { count } => {
+					self.push(step.end, cpu, TraceEvent::CpuRemoved { count })
+				}
+				Event::Spawn { parent, id } => {
+					self.push(step.end, cpu, TraceEvent::Spawn { parent, id })
+				}
+				Event::Crash { id, wasted } => {
+					self.push(step.end, cpu, TraceEvent::Crash { id, wasted })
+				}
+				Event::Timeout { id, waited, burst } => {
+					self.push(step.end, cpu, TraceEvent::Timeout { id, waited, burst })
+				}
+			}
+		}
+
+		let id = step.process_id;
+		let stopped = step
+			.events
+			.iter()
+			.any(|event| matches!(event, Event::Downgrade { .. } | Event::Crash { .. }));
+
+		if let Some(process) = &step.finished {
+			self.push(
+				step.end,
+				cpu,
+				TraceEvent::Finish {
+					id,
+					turnaround_time: metrics.turnaround_time(process),
+					waiting_time: metrics.waiting_time(process),
+					response_time: metrics.response_time(process),
+				},
+			);
+		} else if !step.io_time.is_zero() {
+			self.push(
+				step.end,
+				cpu,
+				TraceEvent::IoStart {
+					id,
+					length: step.io_time,
+				},
+			);
+		} else if !stopped {
+			self.push(step.end, cpu, TraceEvent::Preempt { id });
+		}
+
+		for &(id, time) in &step.io_completions {
+			self.push(time, cpu, TraceEvent::IoComplete { id });
+		}
+	}
+
+	// Marks the trace as cancelled at the given time.
+	pub fn cancel(&mut self, time: SimTime) {
+		self.cancelled_at = Some(time);
+	}
+
+	// Returns the records so far, sorted by time.
+	pub fn trace(&self) -> Trace {
+		let mut records = self.records.clone();
+
+		records.sort_by_key(|record| record.time);
+
+		Trace {
+			records,
+			cancelled_at: self.cancelled_at,
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl Trace {
+	/// Saves the trace as JSON.
+	pub fn save(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+		let file = std::io::BufWriter::new(std::fs::File::create(path)?);
+
+		serde_json::to_writer_pretty(file, self)?;
+
+		Ok(())
+	}
+
+	/// Loads a trace saved as JSON.
+	pub fn load(path: impl AsRef<std::path::Path>) -> std::io::Result<Self> {
+		let file = std::io::BufReader::new(std::fs::File::open(path)?);
+
+		serde_json::from_reader(file)
+			.map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidData, error))
+	}
+}
@@ -0,0 +1,374 @@
+use std::{
+	io,
+	time::{Duration, Instant},
+};
+
+use ratatui::{
+	crossterm::event::{self, Event as InputEvent, KeyCode, KeyEventKind},
+	layout::{Constraint, Layout, Rect},
+	style::{Color, Modifier, Style},
+	text::{Line, Span},
+	widgets::{Block, Cell, Paragraph, Row, Table},
+	DefaultTerminal, Frame,
+};
+
+use crate::{
+	engine::Engine,
+	scheduler::Scheduler,
+	time::SimTime,
+	top::{State, Top},
+	trace::{self, TraceIndex, TraceRecorder},
+};
+
+/// How often the screen is redrawn.
+const FRAME_TIME: Duration = Duration::from_millis(50);
+
+/// The time the recent CPU share of the top view is measured over.
+const TOP_WINDOW: SimTime = SimTime::new(100);
+
+/// The colors used to tell processes apart in the Gantt strip.
+const PALETTE: [Color; 8] = [
+	Color::Red,
+	Color::Green,
+	Color::Yellow,
+	Color::Blue,
+	Color::Magenta,
+	Color::Cyan,
+	Color::LightRed,
+	Color::LightGreen,
+];
+
+/// A process that ran on the CPU between two points in time.
+struct Slice {
+	id: u32,
+	start: SimTime,
+	end: SimTime,
+}
+
+/// The state of the terminal UI around the engine.
+struct App {
+	// Simulated time units per second of real time.
+	speed: f64,
+	paused: bool,
+	// The simulated time the display has caught up to.
+	clock: f64,
+	slices: Vec<Slice>,
+	// The trace of the run so far, which scrubbing looks back through.
+	recorder: TraceRecorder,
+	// The time scrubbed back to while paused, with the slices running then.
+	cursor: Option<(SimTime, Vec<trace::Slice>)>,
+	top: Top,
+	// Whether the top view is shown instead of the queue levels.
+	show_top: bool,
+}
+
+impl App {
+	// Advances the simulation by the real time that has passed.
+	fn advance<S: Scheduler>(&mut self, engine: &mut Engine<S>, elapsed: Duration) {
+		if self.paused {
+			return;
+		}
+
+		self.clock += elapsed.as_secs_f64() * self.speed;
+
+		while !engine.is_stopped() && engine.current_time().as_f64() <= self.clock {
+			let step = engine.step();
+
+			self.top.observe(&step);
+			self.recorder.record(&step, &engine.summary().metrics);
+			self.slices.push(Slice {
+				id: step.process_id,
+				start: step.start,
+				end: step.end,
+			});
+		}
+	}
+
+	// Pauses and moves the cursor by the offset, within the time run so far, looking up what was running then.
+	fn scrub<S: Scheduler>(&mut self, engine: &Engine<S>, offset: i64) {
+		let last = engine.current_time().saturating_sub(SimTime::new(1));
+		let time = self.cursor.as_ref().map_or(last, |(time, _)| *time);
+		let time = SimTime::new(time.as_u64().saturating_add_signed(offset)).min(last);
+		let trace = self.recorder.trace();
+		let running = TraceIndex::new(&trace).running_at(time);
+
+		self.paused = true;
+		self.cursor = Some((time, running));
+	}
+
+	fn draw<S: Scheduler>(&self, frame: &mut Frame, engine: &Engine<S>) {
+		let [header, body, gantt] = Layout::vertical([
+			Constraint::Length(1),
+			Constraint::Min(5),
+			Constraint::Length(4),
+		])
+		.areas(frame.area());
+
+		let [levels, side] =
+			Layout::horizontal([Constraint::Percentage(60), Constraint::Percentage(40)])
+				.areas(body);
+
+		let state = if engine.is_finished() {
+			"finished"
+		} else if self.paused {
+			"paused"
+		} else {
+			"running"
+		};
+
+		frame.render_widget(
+			Paragraph::new(format!(
+				"Time: {}  Speed: {}/s  ({state})  [space] pause  [+/-] speed  [left/right] scrub  [t] top  [q] quit",
+				engine.current_time(),
+				self.speed
+			)),
+			header,
+		);
+
+		if self.show_top {
+			self.draw_top(frame, levels, engine);
+		} else {
+			self.draw_levels(frame, levels, engine);
+		}
+		self.draw_side(frame, side, engine);
+		self.draw_gantt(frame, gantt, engine);
+	}
+
+	fn draw_levels<S: Scheduler>(&self, frame: &mut Frame, area: Rect, engine: &Engine<S>) {
+		let current_time = engine.current_time();
+		let levels = engine.scheduler().levels(current_time);
+
+		let areas = Layout::vertical(levels.iter().map(|_| Constraint::Fill(1))).split(area);
+
+		for (index, (mut level, area)) in levels.into_iter().zip(areas.iter()).enumerate() {
+			level.cpu.sort_unstable_by_key(|data| data.0);
+
+			let spans: Vec<_> = level
+				.cpu
+				.iter()
+				.map(|&(id, time)| Span::styled(format!("P{id} {time}  "), process_style(id)))
+				.collect();
+
+			let block = Block::bordered().title(format!(" Level {} ", index + 1));
+
+			frame.render_widget(Paragraph::new(Line::from(spans)).block(block), *area);
+		}
+	}
+
+	// Draws a row for each process with its state, level, CPU time, and recent share of a CPU, busiest first.
+	fn draw_top<S: Scheduler>(&self, frame: &mut Frame, area: Rect, engine: &Engine<S>) {
+		let header = Row::new(["PID", "STATE", "LEVEL", "TIME", "%CPU"]);
+
+		let rows = self.top.rows(engine).into_iter().map(|row| {
+			let state = match row.state {
+				State::Running => "running",
+				State::Ready => "ready",
+				State::Blocked => "blocked",
+				State::Finished => "finished",
+				State::Crashed => "crashed",
+			};
+
+			Row::new([
+				Cell::from(format!("P{}", row.id)),
+				Cell::from(state),
+				Cell::from(row.level.to_string()),
+				Cell::from(row.cpu_time.to_string()),
+				Cell::from(format!("{:.1}", row.recent_share * 100.0)),
+			])
+			.style(process_style(row.id))
+		});
+
+		let widths = [
+			Constraint::Length(6),
+			Constraint::Length(9),
+			Constraint::Length(6),
+			Constraint::Length(8),
+			Constraint::Length(6),
+		];
+
+		frame.render_widget(
+			Table::new(rows, widths)
+				.header(header)
+				.block(Block::bordered().title(format!(" Top (last {TOP_WINDOW}) "))),
+			area,
+		);
+	}
+
+	fn draw_side<S: Scheduler>(&self, frame: &mut Frame, area: Rect, engine: &Engine<S>) {
+		let (title, mut lines): (String, Vec<Line>) = match &self.cursor {
+			Some((time, slices)) => (
+				format!(" Running at {time} "),
+				slices
+					.iter()
+					.map(|slice| {
+						Line::from(vec![
+							Span::styled(format!("P{}", slice.id), process_style(slice.id)),
+							Span::raw(format!(
+								" on CPU {} from {} to {}",
+								slice.cpu + 1,
+								slice.start,
+								slice.end
+							)),
+						])
+					})
+					.collect(),
+			),
+			None => (
+				" Running ".to_string(),
+				self.slices
+					.last()
+					.map(|slice| {
+						Line::from(vec![
+							Span::styled(format!("P{}", slice.id), process_style(slice.id)),
+							Span::raw(format!(" from {} to {}", slice.start, slice.end)),
+						])
+					})
+					.into_iter()
+					.collect(),
+			),
+		};
+
+		if lines.is_empty() {
+			lines.push(Line::from("Idle"));
+		}
+
+		// Scrubbing a run on several CPUs lists each of them.
+		let height = lines.len() as u16 + 2;
+		let [running, io] =
+			Layout::vertical([Constraint::Length(height), Constraint::Min(3)]).areas(area);
+
+		frame.render_widget(
+			Paragraph::new(lines).block(Block::bordered().title(title)),
+			running,
+		);
+
+		let mut io_list = engine.scheduler().io_remaining(engine.current_time());
+
+		io_list.sort_unstable_by_key(|data| data.0);
+
+		let lines: Vec<_> = io_list
+			.iter()
+			.map(|&(id, time)| Line::styled(format!("P{id} {time}"), process_style(id)))
+			.collect();
+
+		frame.render_widget(
+			Paragraph::new(lines).block(Block::bordered().title(" IO ")),
+			io,
+		);
+	}
+
+	// Draws the most recent time units that fit in the area, one cell each, scrolling back to the cursor.
+	fn draw_gantt<S: Scheduler>(&self, frame: &mut Frame, area: Rect, engine: &Engine<S>) {
+		let width = u64::from(area.width.saturating_sub(2));
+		let cursor = self.cursor.as_ref().map(|(time, _)| *time);
+		let mut end = engine.current_time();
+		let mut start = end.saturating_sub(SimTime::new(width));
+
+		if let Some(time) = cursor.filter(|&time| time < start) {
+			start = time;
+			end = time + SimTime::new(width);
+		}
+
+		let mut slices = self
+			.slices
+			.iter()
+			.skip_while(|slice| slice.end <= start)
+			.peekable();
+
+		let mut cells = Vec::new();
+
+		for time in (start.as_u64()..end.as_u64()).map(SimTime::new) {
+			while slices.next_if(|slice| slice.end <= time).is_some() {}
+
+			let mut cell = match slices.peek() {
+				Some(slice) if slice.start <= time => Span::styled(
+					char::from_digit(slice.id % 10, 10).unwrap().to_string(),
+					process_style(slice.id),
+				),
+				_ => Span::raw("."),
+			};
+
+			if cursor == Some(time) {
+				cell.style = cell.style.add_modifier(Modifier::REVERSED);
+			}
+
+			cells.push(cell);
+		}
+
+		let axis = format!(
+			"{start:<width$}",
+			width = (width as usize).saturating_sub(6)
+		) + &format!("{end:>6}");
+
+		frame.render_widget(
+			Paragraph::new(vec![Line::from(cells), Line::from(axis)])
+				.block(Block::bordered().title(" Gantt ")),
+			area,
+		);
+	}
+}
+
+// Returns the style used for a process.
+fn process_style(id: u32) -> Style {
+	Style::new().fg(PALETTE[id as usize % PALETTE.len()])
+}
+
+fn run_app<S: Scheduler>(
+	terminal: &mut DefaultTerminal,
+	engine: &mut Engine<S>,
+	speed: f64,
+) -> io::Result<()> {
+	let mut app = App {
+		speed,
+		paused: false,
+		clock: 0.0,
+		slices: Vec::new(),
+		recorder: TraceRecorder::new(),
+		cursor: None,
+		top: Top::new(TOP_WINDOW),
+		show_top: false,
+	};
+
+	let mut last_frame = Instant::now();
+
+	loop {
+		terminal.draw(|frame| app.draw(frame, engine))?;
+
+		if event::poll(FRAME_TIME)? {
+			if let InputEvent::Key(key) = event::read()? {
+				if key.kind == KeyEventKind::Press {
+					match key.code {
+						KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+						KeyCode::Char(' ') => {
+							app.paused = !app.paused;
+							app.cursor = None;
+						}
+						KeyCode::Left => app.scrub(engine, -1),
+						KeyCode::Right => app.scrub(engine, 1),
+						KeyCode::Char('t') => app.show_top = !app.show_top,
+						KeyCode::Char('+') => app.speed *= 2.0,
+						KeyCode::Char('-') => app.speed = (app.speed / 2.0).max(1.0),
+						_ => {}
+					}
+				}
+			}
+		}
+
+		let now = Instant::now();
+
+		app.advance(engine, now - last_frame);
+
+		last_frame = now;
+	}
+}
+
+// Runs the engine in a terminal UI, advancing `speed` time units per second.
+pub fn run<S: Scheduler>(engine: &mut Engine<S>, speed: f64) -> io::Result<()> {
+	let mut terminal = ratatui::init();
+
+	let result = run_app(&mut terminal, engine, speed);
+
+	ratatui::restore();
+
+	result
+}
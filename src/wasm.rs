@@ -0,0 +1,163 @@
+//! A step-by-step API for driving a simulation from JavaScript.
+//!
+//! A [`Simulation`] is created from a JSON configuration and advanced one
+//! engine step at a time, returning each step as JSON. Nothing is printed, so
+//! a web page can draw the queues and Gantt chart from the returned events.
+
+use alloc::{format, string::String, vec::Vec};
+
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::{
+	algorithms::{LevelPolicy, MultiLevelFeedbackQueue, DEFAULT_LEVELS},
+	engine::Engine,
+	report::ProcessRecord,
+	scheduler::{Event, LevelLists, Scheduler},
+	time::SimTime,
+	workload::Workload,
+};
+
+/// The configuration of a simulation, as given to [`Simulation::new`].
+///
+/// Every algorithm is a list of levels, so `[{"policy": "first-come-first-serve"}]`
+/// is plain FCFS and the default levels are the MLFQ.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Config {
+	/// The workload in the text format, one process per line.
+	workload: String,
+	#[serde(default = "default_levels")]
+	levels: Vec<LevelPolicy>,
+	#[serde(default)]
+	boost_interval: Option<SimTime>,
+	#[serde(default)]
+	donate: bool,
+	#[serde(default = "default_cpus")]
+	cpus: usize,
+}
+
+fn default_levels() -> Vec<LevelPolicy> {
+	DEFAULT_LEVELS.to_vec()
+}
+
+fn default_cpus() -> usize {
+	1
+}
+
+/// A step of the engine, as returned by [`Simulation::step_json`].
+#[derive(Serialize)]
+struct StepView<'a> {
+	cpu: usize,
+	process_id: u32,
+	start: SimTime,
+	end: SimTime,
+	events: &'a [Event],
+	finished: Option<ProcessRecord>,
+	io_time: SimTime,
+	io_completions: &'a [(u32, SimTime)],
+	/// The time the next step starts at.
+	current_time: SimTime,
+	/// The processes waiting in each level after the step, from the first down.
+	levels: Vec<LevelLists>,
+	is_finished: bool,
+}
+
+/// A simulation that a web page steps through.
+#[wasm_bindgen]
+pub struct Simulation {
+	engine: Engine<MultiLevelFeedbackQueue>,
+}
+
+#[wasm_bindgen]
+impl Simulation {
+	// Creates a simulation from its JSON configuration, throwing if it is invalid.
+	#[wasm_bindgen(constructor)]
+	pub fn new(config_json: &str) -> Result<Simulation, JsError> {
+		Self::from_config(config_json).map_err(|error| JsError::new(&error))
+	}
+
+	// Runs the next step and returns it as JSON, or `null` once the run is over.
+	pub fn step_json(&mut self) -> String {
+		if self.engine.is_stopped() {
+			return String::from("null");
+		}
+
+		let step = self.engine.step();
+		let current_time = self.engine.current_time();
+
+		let view = StepView {
+			cpu: step.cpu,
+			process_id: step.process_id,
+			start: step.start,
+			end: step.end,
+			events: &step.events,
+			finished: step.finished.as_ref().map(|process| {
+				ProcessRecord::new(process, step.end, &self.engine.summary().metrics)
+			}),
+			io_time: step.io_time,
+			io_completions: &step.io_completions,
+			current_time,
+			levels: self.engine.scheduler().levels(current_time),
+			is_finished: self.engine.is_finished(),
+		};
+
+		serde_json::to_string(&view).expect("steps serialize to JSON")
+	}
+
+	// Returns the summary of the run so far as JSON.
+	pub fn summary_json(&self) -> String {
+		serde_json::to_string(self.engine.summary()).expect("summaries serialize to JSON")
+	}
+
+	pub fn is_finished(&self) -> bool {
+		self.engine.is_finished()
+	}
+
+	pub fn current_time(&self) -> u64 {
+		self.engine.current_time().as_u64()
+	}
+}
+
+impl Simulation {
+	// Creates a simulation from its JSON configuration, for use outside of JavaScript.
+	pub fn from_config(config_json: &str) -> Result<Self, String> {
+		let config: Config = serde_json::from_str(config_json)
+			.map_err(|error| format!("invalid configuration: {error}"))?;
+
+		if config.levels.is_empty() {
+			return Err(String::from("invalid configuration: no levels"));
+		}
+
+		if let Some(level) = config.levels.iter().position(|level| !level.is_valid()) {
+			return Err(format!(
+				"invalid configuration: level {} has a zero quantum",
+				level + 1
+			));
+		}
+
+		if config
+			.boost_interval
+			.is_some_and(|interval| interval.is_zero())
+		{
+			return Err(String::from(
+				"invalid configuration: the boost interval must not be zero",
+			));
+		}
+
+		let workload = Workload::parse(&config.workload)
+			.map_err(|error| format!("invalid workload: {}", error.render("workload")))?;
+
+		let mut scheduler =
+			MultiLevelFeedbackQueue::with_levels(workload.into_processes().into(), &config.levels)
+				.with_donation(config.donate);
+
+		if let Some(boost_interval) = config.boost_interval {
+			scheduler = scheduler.with_boost_interval(boost_interval);
+		}
+
+		Ok(Self {
+			engine: Engine::new(scheduler).with_cpus(config.cpus.max(1)),
+		})
+	}
+}
@@ -0,0 +1,1218 @@
+use alloc::{
+	collections::VecDeque,
+	format,
+	string::{String, ToString},
+	vec::Vec,
+};
+use core::fmt;
+
+use crate::{
+	process::{
+		BurstPredictor, IoPriority, Process, RetryPolicy, SoftDeadline, Spawn, DEFAULT_TICKETS,
+	},
+	time::SimTime,
+};
+
+/// Loads the test processes.
+pub fn load_test_workload() -> Workload {
+	let mut workload = Workload::new();
+
+	workload
+		.process([27, 31, 43, 18, 22, 26, 24], [5, 3, 5, 4, 6, 4, 3, 4])
+		.add();
+	workload
+		.process(
+			[48, 44, 42, 37, 76, 41, 31, 43],
+			[4, 5, 7, 12, 9, 4, 9, 7, 8],
+		)
+		.add();
+	workload
+		.process(
+			[33, 41, 65, 21, 61, 18, 26, 31],
+			[8, 12, 18, 14, 4, 15, 14, 5, 6],
+		)
+		.add();
+	workload
+		.process(
+			[35, 41, 45, 51, 61, 54, 82, 77],
+			[3, 4, 5, 3, 4, 5, 6, 5, 3],
+		)
+		.add();
+	workload
+		.process(
+			[24, 21, 36, 26, 31, 28, 21, 13, 11],
+			[16, 17, 5, 16, 7, 13, 11, 6, 3, 4],
+		)
+		.add();
+	workload
+		.process(
+			[22, 8, 10, 12, 14, 18, 24, 30],
+			[11, 4, 5, 6, 7, 9, 12, 15, 8],
+		)
+		.add();
+	workload
+		.process([46, 41, 42, 21, 32, 19, 33], [14, 17, 11, 15, 4, 7, 16, 10])
+		.add();
+	workload
+		.process([14, 33, 51, 73, 87], [4, 5, 6, 14, 16, 6])
+		.add();
+
+	workload
+}
+
+/// Loads a desktop mix of an interactive shell, an editor, two compiler jobs, and a disk indexer.
+///
+/// The shell and editor run short bursts between long think times, the
+/// compilers run long CPU bursts with a little disk I/O, and the indexer
+/// mostly waits on the disk at idle I/O priority. Each process is tagged with
+/// its class, so the summary can show how each kind of program fared.
+pub fn load_desktop_workload() -> Workload {
+	let mut workload = Workload::new();
+
+	workload
+		.process([0; 9], [1, 2, 1, 3, 1, 2, 1, 2, 1, 1])
+		.class("shell")
+		.think_time(SimTime::new(30))
+		.add();
+	workload
+		.process([0, 0, 6, 0, 0, 6, 0], [3, 2, 4, 2, 3, 5, 2, 3])
+		.class("editor")
+		.think_time(SimTime::new(20))
+		.add();
+	workload
+		.process([5, 5, 5], [40, 35, 50, 45])
+		.class("compiler")
+		.arrival_time(SimTime::new(5))
+		.add();
+	workload
+		.process([5, 5, 5], [45, 30, 40, 50])
+		.class("compiler")
+		.arrival_time(SimTime::new(10))
+		.add();
+	workload
+		.process([18; 11], [2; 12])
+		.class("indexer")
+		.io_priority(IoPriority::Idle)
+		.add();
+
+	workload
+}
+
+/// The error returned when a workload file cannot be parsed.
+///
+/// Besides the message, it keeps the line it was found on and the part of it
+/// at fault, so [`ParseWorkloadError::render`] can point at it.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseWorkloadError {
+	pub line: usize,
+	/// The column the part at fault starts at, counting from 1.
+	pub column: usize,
+	/// The length of the part at fault, in characters.
+	pub length: usize,
+	pub message: String,
+	/// How to fix the line, when there is a likely fix.
+	pub help: Option<String>,
+	/// The text of the line.
+	pub text: String,
+}
+
+/// The fields of a workload file line.
+const FIELDS: [&str; 17] = [
+	"cpu",
+	"io",
+	"accel",
+	"arrival",
+	"priority",
+	"io_priority",
+	"tickets",
+	"class",
+	"think",
+	"parent",
+	"spawn_at",
+	"deadline",
+	"tolerance",
+	"period",
+	"timeout",
+	"retry",
+	"tags",
+];
+
+impl ParseWorkloadError {
+	// Creates an error pointing at the span, which must be a slice of the text of the line.
+	fn new(line: usize, text: &str, span: &str, message: impl Into<String>) -> Self {
+		let start = span.as_ptr() as usize - text.as_ptr() as usize;
+
+		Self {
+			line,
+			column: text[..start].chars().count() + 1,
+			length: span.chars().count().max(1),
+			message: message.into(),
+			help: None,
+			text: text.to_string(),
+		}
+	}
+
+	fn with_help(mut self, help: impl Into<String>) -> Self {
+		self.help = Some(help.into());
+		self
+	}
+
+	// Renders the error as a diagnostic of the named file, showing the line with the part at fault underlined.
+	pub fn render(&self, name: &str) -> String {
+		let number = self.line.to_string();
+		let gutter = " ".repeat(number.len());
+		let underline = format!("{}{}", " ".repeat(self.column - 1), "^".repeat(self.length));
+
+		let mut rendered = format!(
+			"{self}\n{gutter}--> {name}:{}:{}\n{gutter} |\n{number} | {}\n{gutter} | {underline}",
+			self.line, self.column, self.text
+		);
+
+		if let Some(help) = &self.help {
+			rendered += &format!("\n{gutter} |\n{gutter} = help: {help}");
+		}
+
+		rendered
+	}
+}
+
+impl fmt::Display for ParseWorkloadError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "line {}: {}", self.line, self.message)
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseWorkloadError {}
+
+// Returns the field a misspelled key most likely meant, if any is close enough.
+fn closest_field(key: &str) -> Option<&'static str> {
+	FIELDS
+		.into_iter()
+		.map(|field| (edit_distance(key, field), field))
+		.filter(|&(distance, _)| distance <= 2)
+		.min()
+		.map(|(_, field)| field)
+}
+
+// Returns the number of single character edits that turn one string into the other.
+fn edit_distance(first: &str, second: &str) -> usize {
+	let second: Vec<char> = second.chars().collect();
+	let mut previous: Vec<usize> = (0..=second.len()).collect();
+
+	for (index, first_char) in first.chars().enumerate() {
+		let mut current = Vec::with_capacity(previous.len());
+
+		current.push(index + 1);
+
+		for (other, &second_char) in second.iter().enumerate() {
+			let substitution = previous[other] + usize::from(first_char != second_char);
+
+			current.push(
+				substitution
+					.min(previous[other + 1] + 1)
+					.min(current[other] + 1),
+			);
+		}
+
+		previous = current;
+	}
+
+	previous[second.len()]
+}
+
+// Converts an error of a serialized workload into one pointing at the line and column it was found at.
+#[cfg(feature = "std")]
+fn json_error(text: &str, error: &serde_json::Error) -> ParseWorkloadError {
+	let line = error.line().max(1);
+	let source = text.lines().nth(line - 1).unwrap_or_default();
+	let span = source
+		.char_indices()
+		.nth(error.column().saturating_sub(1))
+		.map_or(&source[source.len()..], |(index, char)| {
+			&source[index..index + char.len_utf8()]
+		});
+
+	// The message of the error ends with where it was found, which the diagnostic shows instead.
+	let message = error.to_string();
+	let message = message
+		.rsplit_once(" at line ")
+		.map_or(message.as_str(), |(message, _)| message);
+
+	ParseWorkloadError::new(line, source, span, message)
+}
+
+// Creates an error about a process of a serialized workload, pointing at its ID.
+#[cfg(feature = "std")]
+fn json_process_error(text: &str, id: u32, message: &str) -> ParseWorkloadError {
+	// Find the `"id": <id>` of the process, wherever the serializer put the whitespace.
+	let position = text.match_indices("\"id\"").find_map(|(start, key)| {
+		let rest = text[start + key.len()..].trim_start().strip_prefix(':')?;
+		let digits = rest.trim_start();
+		let end = digits
+			.find(|char: char| !char.is_ascii_digit())
+			.unwrap_or(digits.len());
+
+		(digits[..end].parse() == Ok(id)).then_some((start, text.len() - digits.len() + end))
+	});
+
+	let Some((start, end)) = position else {
+		let line = text.lines().next().unwrap_or_default();
+
+		return ParseWorkloadError::new(1, line, line, format!("P{id}: {message}"));
+	};
+
+	let line_start = text[..start].rfind('\n').map_or(0, |index| index + 1);
+	let line_end = text[start..]
+		.find('\n')
+		.map_or(text.len(), |index| start + index);
+	let line = &text[line_start..line_end];
+	let number = text[..start].matches('\n').count() + 1;
+
+	ParseWorkloadError::new(
+		number,
+		line,
+		&text[start..end.min(line_end)],
+		format!("P{id}: {message}"),
+	)
+}
+
+// Returns a count of things, such as "1 time" or "3 times".
+fn count(count: usize, noun: &str) -> String {
+	if count == 1 {
+		format!("1 {noun}")
+	} else {
+		format!("{count} {noun}s")
+	}
+}
+
+// Parses a comma separated list of times.
+fn parse_times(value: &str) -> Option<Vec<u64>> {
+	if value.is_empty() {
+		return Some(Vec::new());
+	}
+
+	value
+		.split(',')
+		.map(|time| time.trim().parse().ok())
+		.collect()
+}
+
+// Checks a process against the rules of a workload file line, given the processes before it.
+fn check_process(process: &Process, earlier: &[Process]) -> Result<(), String> {
+	let cpu_count = process.cpu_times.len();
+
+	if cpu_count == 0 || process.cpu_times.contains(&SimTime::ZERO) {
+		return Err("cpu times must be positive".to_string());
+	}
+
+	let io_count = if process.period.is_some() {
+		0
+	} else {
+		cpu_count - 1
+	};
+
+	if process.io_times.len() != io_count {
+		return Err(format!(
+			"io has {} but cpu has {}",
+			count(process.io_times.len(), "time"),
+			count(cpu_count, "time")
+		));
+	}
+
+	if !process.accelerator_times.is_empty()
+		&& (process.period.is_some() || process.accelerator_times.len() + 1 != cpu_count)
+	{
+		return Err(format!(
+			"accel has {} but cpu has {}",
+			count(process.accelerator_times.len(), "time"),
+			count(cpu_count, "time")
+		));
+	}
+
+	if !process.burst_tags.is_empty() && process.burst_tags.len() != cpu_count {
+		return Err(format!(
+			"tags has {} but cpu has {}",
+			count(process.burst_tags.len(), "tag"),
+			count(cpu_count, "time")
+		));
+	}
+
+	if process.tickets == 0 {
+		return Err("tickets must be positive".to_string());
+	}
+
+	if process.period.is_some_and(SimTime::is_zero) {
+		return Err("period must be positive".to_string());
+	}
+
+	if let Some(soft_deadline) = process.soft_deadline {
+		if soft_deadline.deadline.is_zero() {
+			return Err("deadline must be positive".to_string());
+		}
+
+		if soft_deadline.tolerance > 100 {
+			return Err("tolerance must be a percentage".to_string());
+		}
+	}
+
+	if process.retry.is_some_and(|retry| retry.factor == 0) {
+		return Err("retry must be positive".to_string());
+	}
+
+	if let Some(spawn) = process.spawn {
+		let parent = earlier
+			.iter()
+			.find(|parent| parent.id == spawn.parent)
+			.ok_or("parent must be an earlier process")?;
+
+		if spawn.after_bursts == 0 || spawn.after_bursts as usize > parent.cpu_times.len() {
+			return Err(format!(
+				"spawn_at must be between 1 and the {} CPU bursts of the parent",
+				parent.cpu_times.len()
+			));
+		}
+	}
+
+	Ok(())
+}
+
+// Checks that the class and tags of a process can be written as fields of a workload file line.
+fn check_names(process: &Process) -> Result<(), String> {
+	// Whitespace and `#` end a field, and `,` separates tags.
+	let breaks_field = |text: &str, separators: &[char]| {
+		text.chars()
+			.any(|char| char.is_whitespace() || char == '#' || separators.contains(&char))
+	};
+
+	if process
+		.class
+		.as_deref()
+		.is_some_and(|class| breaks_field(class, &[]))
+	{
+		return Err("class must not contain whitespace or #".to_string());
+	}
+
+	if process
+		.burst_tags
+		.iter()
+		.flatten()
+		.any(|tag| breaks_field(tag, &[',']))
+	{
+		return Err("tags must not contain whitespace, commas, or #".to_string());
+	}
+
+	Ok(())
+}
+
+fn parse_io_priority(value: &str) -> Option<IoPriority> {
+	match value {
+		"realtime" => Some(IoPriority::RealTime),
+		"best-effort" => Some(IoPriority::BestEffort),
+		"idle" => Some(IoPriority::Idle),
+		_ => None,
+	}
+}
+
+/// A list of processes for a single simulation.
+///
+/// The workload owns ID assignment, so every simulation starts from P1 no
+/// matter how many other workloads the program has built.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Workload {
+	processes: Vec<Process>,
+	next_id: u32,
+	// The workload file of the processes built so far when recording, or why a process could not be recorded.
+	#[cfg_attr(feature = "serde", serde(skip))]
+	recording: Option<Result<String, String>>,
+}
+
+impl Workload {
+	pub fn new() -> Self {
+		Self {
+			processes: Vec::new(),
+			next_id: 1,
+			recording: None,
+		}
+	}
+
+	/// Creates an empty workload that records every process added to it.
+	///
+	/// The recording is the workload file that rebuilds the same processes, so
+	/// a workload generated by a program can be saved and replayed on its own.
+	/// A process the file format cannot describe, such as one with a class
+	/// name containing a space, stops the recording with an error instead.
+	pub fn recording() -> Self {
+		Self {
+			recording: Some(Ok(String::new())),
+			..Self::new()
+		}
+	}
+
+	// Returns the workload file of the processes added so far, if the workload is recording and every one could be recorded.
+	pub fn recorded(&self) -> Option<&str> {
+		self.recording.as_ref()?.as_deref().ok()
+	}
+
+	// Returns why the recording stopped, if a process could not be recorded.
+	pub fn recording_error(&self) -> Option<&str> {
+		self.recording.as_ref()?.as_ref().err().map(String::as_str)
+	}
+
+	// Starts building a process that is added with the next ID.
+	pub fn process(
+		&mut self,
+		io_times: impl IntoIterator<Item = u64>,
+		cpu_times: impl IntoIterator<Item = u64>,
+	) -> ProcessBuilder<'_> {
+		ProcessBuilder {
+			workload: self,
+			io_times: io_times.into_iter().map(SimTime::new).collect(),
+			cpu_times: cpu_times.into_iter().map(SimTime::new).collect(),
+			arrival_time: SimTime::ZERO,
+			io_priority: IoPriority::default(),
+			priority: 0,
+			tickets: DEFAULT_TICKETS,
+			class: None,
+			think_time: SimTime::ZERO,
+			spawn: None,
+			soft_deadline: None,
+			period: None,
+			retry: None,
+			burst_tags: Vec::new(),
+			accelerator_times: VecDeque::new(),
+		}
+	}
+
+	/// Parses a workload file.
+	///
+	/// Each line describes one process as `key=value` fields, and `#` starts a
+	/// comment. `cpu` and `io` are comma separated burst times, with one fewer IO
+	/// burst than CPU bursts. `arrival`, `priority`, `io_priority`, `tickets`,
+	/// `class`, and `think` are optional. A process with `parent` and `spawn_at`
+	/// does not arrive on its own, but is spawned by the process with that ID
+	/// once it has finished that many CPU bursts. A process with `deadline` is
+	/// soft real-time: each CPU burst should finish within that time of becoming
+	/// ready, and `tolerance` is the percentage of deadlines it may miss. A
+	/// process with `period` is periodic: it takes no `io` times, and each CPU
+	/// burst is a job released one period after the last, due by the next
+	/// release unless `deadline` is shorter. A process with `timeout` retries
+	/// when a CPU burst waits longer than that in ready queues, making its next
+	/// burst `retry` times as long (2 by default). `accel` gives the accelerator
+	/// burst after each CPU burst but the last, run before the I/O of that gap,
+	/// where 0 is none.
+	///
+	/// ```text
+	/// cpu=5,3,5 io=27,31 priority=2 io_priority=realtime
+	/// cpu=1,2,1 io=0,4 class=shell think=40
+	/// cpu=20,30 io=5 parent=2 spawn_at=1
+	/// cpu=2,2,2 io=8,8 deadline=4 tolerance=10
+	/// cpu=3,3,3,3 period=10
+	/// cpu=4,4,4 io=10,10 timeout=20 retry=2
+	/// cpu=8,2,2,6 io=3,3,3 tags=startup,steady,steady,shutdown
+	/// cpu=4,4,4 io=5,5 accel=30,0
+	/// ```
+	pub fn parse(text: &str) -> Result<Self, ParseWorkloadError> {
+		let mut workload = Self::new();
+
+		for (index, text) in text.lines().enumerate() {
+			let error =
+				|span: &str, message: &str| ParseWorkloadError::new(index + 1, text, span, message);
+
+			let line = text.split('#').next().unwrap_or_default().trim();
+
+			if line.is_empty() {
+				continue;
+			}
+
+			let mut cpu_times = None;
+			let mut io_times = Vec::new();
+			let mut accelerator_times = Vec::new();
+			let mut arrival_time = SimTime::ZERO;
+			let mut priority = 0;
+			let mut io_priority = IoPriority::default();
+			let mut tickets = DEFAULT_TICKETS;
+			let mut class = None;
+			let mut think_time = SimTime::ZERO;
+			let mut parent: Option<u32> = None;
+			let mut spawn_at: Option<u32> = None;
+			let mut deadline: Option<SimTime> = None;
+			let mut tolerance: Option<u32> = None;
+			let mut period: Option<SimTime> = None;
+			let mut timeout: Option<SimTime> = None;
+			let mut retry: Option<u64> = None;
+			let mut tags: Option<Vec<Option<String>>> = None;
+			// The fields of the line by key, to point errors found after reading them all at.
+			let mut fields: Vec<(&str, &str)> = Vec::new();
+
+			for field in line.split_whitespace() {
+				let Some((key, value)) = field.split_once('=') else {
+					return Err(error(field, "expected a key=value field")
+						.with_help("write each field as key=value, such as cpu=5,3"));
+				};
+
+				fields.push((key, field));
+
+				let error = |message: &str| error(field, message);
+
+				match key {
+					"cpu" => {
+						cpu_times = Some(parse_times(value).ok_or_else(|| {
+							error("invalid cpu times")
+								.with_help("list the times as whole numbers, such as cpu=5,3,5")
+						})?)
+					}
+					"io" => {
+						io_times = parse_times(value).ok_or_else(|| {
+							error("invalid io times")
+								.with_help("list the times as whole numbers, such as io=27,31")
+						})?
+					}
+					"accel" => {
+						accelerator_times = parse_times(value).ok_or_else(|| {
+							error("invalid accelerator times").with_help(
+								"list the times as whole numbers, leaving gaps without one at 0, such as accel=0,40",
+							)
+						})?
+					}
+					"arrival" => {
+						arrival_time = value.parse().map_err(|_| error("invalid arrival time"))?
+					}
+					"priority" => {
+						priority = value.parse().map_err(|_| error("invalid priority"))?
+					}
+					"io_priority" => {
+						io_priority = parse_io_priority(value).ok_or_else(|| {
+							error("io_priority must be realtime, best-effort, or idle")
+						})?;
+					}
+					"tickets" => {
+						tickets = value
+							.parse()
+							.ok()
+							.filter(|&tickets| tickets > 0)
+							.ok_or_else(|| error("tickets must be positive"))?;
+					}
+					"class" => class = Some(value.to_string()),
+					"think" => {
+						think_time = value.parse().map_err(|_| error("invalid think time"))?
+					}
+					"parent" => parent = Some(value.parse().map_err(|_| error("invalid parent"))?),
+					"spawn_at" => {
+						spawn_at = Some(
+							value
+								.parse()
+								.ok()
+								.filter(|&bursts| bursts > 0)
+								.ok_or_else(|| error("spawn_at must be positive"))?,
+						)
+					}
+					"deadline" => {
+						deadline = Some(
+							value
+								.parse()
+								.ok()
+								.filter(|deadline: &SimTime| !deadline.is_zero())
+								.ok_or_else(|| error("deadline must be positive"))?,
+						)
+					}
+					"period" => {
+						period = Some(
+							value
+								.parse()
+								.ok()
+								.filter(|period: &SimTime| !period.is_zero())
+								.ok_or_else(|| error("period must be positive"))?,
+						)
+					}
+					"timeout" => {
+						timeout = Some(value.parse().map_err(|_| error("invalid timeout"))?)
+					}
+					"retry" => {
+						retry = Some(
+							value
+								.parse()
+								.ok()
+								.filter(|&factor| factor > 0)
+								.ok_or_else(|| error("retry must be positive"))?,
+						)
+					}
+					"tolerance" => {
+						tolerance = Some(
+							value
+								.parse()
+								.ok()
+								.filter(|&tolerance| tolerance <= 100)
+								.ok_or_else(|| error("tolerance must be a percentage"))?,
+						)
+					}
+					"tags" => {
+						tags = Some(
+							value
+								.split(',')
+								.map(|tag| (!tag.is_empty()).then(|| tag.to_string()))
+								.collect(),
+						)
+					}
+					_ => {
+						let error = error(&format!("unknown field `{key}`"));
+
+						return Err(match closest_field(key) {
+							Some(field) => error.with_help(format!("did you mean `{field}`?")),
+							None => {
+								error.with_help(format!("the fields are {}", FIELDS.join(", ")))
+							}
+						});
+					}
+				}
+			}
+
+			// Points at the field with the key, or at the whole line without it.
+			let field = |key: &str| {
+				fields
+					.iter()
+					.rev()
+					.find(|&&(field, _)| field == key)
+					.map_or(line, |&(_, field)| field)
+			};
+
+			let cpu_times = cpu_times.ok_or_else(|| {
+				error(line, "missing cpu times").with_help("add the CPU bursts, such as cpu=5,3,5")
+			})?;
+
+			if cpu_times.is_empty() || cpu_times.contains(&0) {
+				return Err(error(field("cpu"), "cpu times must be positive"));
+			}
+
+			if period.is_some() {
+				if !io_times.is_empty() {
+					return Err(error(field("io"), "periodic processes take no io times")
+						.with_help(
+						"each burst is released a period after the last, so remove the io field",
+					));
+				}
+			} else if io_times.len() + 1 != cpu_times.len() {
+				let span = if io_times.is_empty() {
+					field("cpu")
+				} else {
+					field("io")
+				};
+				let message = format!(
+					"io has {} but cpu has {}",
+					count(io_times.len(), "time"),
+					count(cpu_times.len(), "time")
+				);
+
+				return Err(error(span, &message).with_help(
+					"expected io = cpu - 1, since an io time separates each pair of CPU bursts",
+				));
+			}
+
+			if !accelerator_times.is_empty() {
+				if period.is_some() {
+					return Err(error(
+						field("accel"),
+						"periodic processes take no accelerator times",
+					)
+					.with_help(
+						"each burst is released a period after the last, so remove the accel field",
+					));
+				}
+
+				if accelerator_times.len() + 1 != cpu_times.len() {
+					let message = format!(
+						"accel has {} but cpu has {}",
+						count(accelerator_times.len(), "time"),
+						count(cpu_times.len(), "time")
+					);
+
+					return Err(error(field("accel"), &message).with_help(
+						"expected accel = cpu - 1, since an accelerator burst can follow each CPU burst but the last",
+					));
+				}
+			}
+
+			if let Some(tags) = &tags {
+				if tags.len() != cpu_times.len() {
+					let message = format!(
+						"tags has {} but cpu has {}",
+						count(tags.len(), "tag"),
+						count(cpu_times.len(), "time")
+					);
+
+					return Err(error(field("tags"), &message).with_help(
+						"give one tag per CPU burst, leaving untagged bursts empty, such as tags=startup,,shutdown",
+					));
+				}
+			}
+
+			let spawn = match (parent, spawn_at) {
+				(Some(parent), Some(after_bursts)) => {
+					let parent = workload
+						.processes
+						.iter()
+						.find(|process| process.id == parent)
+						.ok_or_else(|| {
+							error(
+								field("parent"),
+								"parent must be a process on an earlier line",
+							)
+						})?;
+
+					if after_bursts as usize > parent.cpu_times.len() {
+						return Err(error(
+							field("spawn_at"),
+							"spawn_at is past the last CPU burst of the parent",
+						)
+						.with_help(format!(
+							"the parent has {} CPU bursts",
+							parent.cpu_times.len()
+						)));
+					}
+
+					Some((parent.id, after_bursts))
+				}
+				(None, None) => None,
+				(Some(_), None) => {
+					return Err(error(
+						field("parent"),
+						"parent and spawn_at must be given together",
+					)
+					.with_help("add the burst of the parent to spawn after, such as spawn_at=1"))
+				}
+				(None, Some(_)) => {
+					return Err(error(
+						field("spawn_at"),
+						"parent and spawn_at must be given together",
+					)
+					.with_help("add the process that spawns this one, such as parent=1"))
+				}
+			};
+
+			let mut builder = workload
+				.process(io_times, cpu_times)
+				.arrival_time(arrival_time)
+				.priority(priority)
+				.io_priority(io_priority)
+				.tickets(tickets)
+				.think_time(think_time)
+				.accelerator_times(accelerator_times);
+
+			if let Some(class) = class {
+				builder = builder.class(&class);
+			}
+
+			if let Some((parent, after_bursts)) = spawn {
+				builder = builder.spawned_by(parent, after_bursts);
+			}
+
+			match (deadline, tolerance) {
+				(Some(deadline), tolerance) => {
+					builder = builder.soft_deadline(deadline, tolerance.unwrap_or_default());
+				}
+				(None, Some(_)) => {
+					return Err(error(field("tolerance"), "tolerance needs a deadline")
+						.with_help("add the deadline of each burst, such as deadline=10"))
+				}
+				(None, None) => {}
+			}
+
+			if let Some(period) = period {
+				builder = builder.period(period);
+			}
+
+			if let Some(tags) = &tags {
+				builder =
+					builder.burst_tags(tags.iter().map(|tag| tag.as_deref().unwrap_or_default()));
+			}
+
+			match (timeout, retry) {
+				(Some(timeout), factor) => builder = builder.retry(timeout, factor.unwrap_or(2)),
+				(None, Some(_)) => {
+					return Err(error(field("retry"), "retry needs a timeout")
+						.with_help("add how long a burst may wait, such as timeout=20"))
+				}
+				(None, None) => {}
+			}
+
+			builder.add();
+		}
+
+		Ok(workload)
+	}
+
+	// Checks the processes of a deserialized workload the way `parse` checks each line of a file,
+	// returning the ID of the first invalid process and what is wrong with it.
+	#[cfg(feature = "std")]
+	fn check(&self) -> Result<(), (u32, String)> {
+		for (index, process) in self.processes.iter().enumerate() {
+			check_process(process, &self.processes[..index])
+				.map_err(|message| (process.id, message))?;
+		}
+
+		Ok(())
+	}
+
+	/// Loads a workload file from disk.
+	///
+	/// Files ending in `.json` hold a serialized workload, and any other file
+	/// is parsed as the workload file format. Either way, an invalid file fails
+	/// with a diagnostic that points at the line and field at fault, or for a
+	/// serialized process that breaks the rules of the file format, at its ID.
+	#[cfg(feature = "std")]
+	pub fn load(path: impl AsRef<std::path::Path>) -> std::io::Result<Self> {
+		let path = path.as_ref();
+		let text = std::fs::read_to_string(path)?;
+
+		let result = if path
+			.extension()
+			.is_some_and(|extension| extension == "json")
+		{
+			serde_json::from_str::<Self>(&text)
+				.map_err(|error| json_error(&text, &error))
+				.and_then(|workload| {
+					workload
+						.check()
+						.map_err(|(id, message)| json_process_error(&text, id, &message))?;
+
+					Ok(workload)
+				})
+		} else {
+			Self::parse(&text)
+		};
+
+		result.map_err(|error| {
+			std::io::Error::new(
+				std::io::ErrorKind::InvalidData,
+				error.render(&path.display().to_string()),
+			)
+		})
+	}
+
+	/// Saves the recorded workload file, failing if the workload is not
+	/// recording or a process could not be recorded.
+	#[cfg(feature = "std")]
+	pub fn save_recording(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+		let recording = match &self.recording {
+			Some(Ok(recording)) => recording,
+			Some(Err(error)) => return Err(std::io::Error::other(error.clone())),
+			None => return Err(std::io::Error::other("the workload is not recording")),
+		};
+
+		std::fs::write(path, recording)
+	}
+
+	/// Saves the workload as JSON, including the state of every process.
+	#[cfg(feature = "std")]
+	pub fn save(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+		let file = std::io::BufWriter::new(std::fs::File::create(path)?);
+
+		serde_json::to_writer_pretty(file, self)?;
+
+		Ok(())
+	}
+
+	pub fn len(&self) -> usize {
+		self.processes.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.processes.is_empty()
+	}
+
+	pub fn processes(&self) -> &[Process] {
+		&self.processes
+	}
+
+	pub fn into_processes(self) -> Vec<Process> {
+		self.processes
+	}
+
+	// Puts every process in the I/O priority class.
+	pub fn set_io_priority(&mut self, io_priority: IoPriority) {
+		for process in &mut self.processes {
+			process.io_priority = io_priority;
+		}
+	}
+
+	// Gives every process a copy of the burst predictor.
+	pub fn set_burst_predictor(&mut self, predictor: BurstPredictor) {
+		for process in &mut self.processes {
+			process.burst_predictor = predictor;
+		}
+	}
+}
+
+impl Default for Workload {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+/// Configures a process before it is added to a workload.
+pub struct ProcessBuilder<'a> {
+	workload: &'a mut Workload,
+	io_times: VecDeque<SimTime>,
+	cpu_times: VecDeque<SimTime>,
+	arrival_time: SimTime,
+	io_priority: IoPriority,
+	priority: u32,
+	tickets: u32,
+	class: Option<String>,
+	think_time: SimTime,
+	spawn: Option<Spawn>,
+	soft_deadline: Option<SoftDeadline>,
+	period: Option<SimTime>,
+	retry: Option<RetryPolicy>,
+	burst_tags: Vec<Option<String>>,
+	accelerator_times: VecDeque<SimTime>,
+}
+
+impl ProcessBuilder<'_> {
+	// Sets the time the process first arrives.
+	pub fn arrival_time(mut self, arrival_time: SimTime) -> Self {
+		self.arrival_time = arrival_time;
+		self
+	}
+
+	// Sets the I/O priority class of the process.
+	pub fn io_priority(mut self, io_priority: IoPriority) -> Self {
+		self.io_priority = io_priority;
+		self
+	}
+
+	// Sets the scheduling priority of the process.
+	pub fn priority(mut self, priority: u32) -> Self {
+		self.priority = priority;
+		self
+	}
+
+	// Sets the number of tickets the process holds.
+	pub fn tickets(mut self, tickets: u32) -> Self {
+		self.tickets = tickets;
+		self
+	}
+
+	// Sets the class the process is reported under.
+	pub fn class(mut self, class: &str) -> Self {
+		self.class = Some(class.to_string());
+		self
+	}
+
+	// Sets the time the process waits on the user before each of its I/O bursts.
+	pub fn think_time(mut self, think_time: SimTime) -> Self {
+		self.think_time = think_time;
+		self
+	}
+
+	// Makes the process a child that the parent spawns after finishing the given number of CPU bursts.
+	pub fn spawned_by(mut self, parent: u32, after_bursts: u32) -> Self {
+		self.spawn = Some(Spawn {
+			parent,
+			after_bursts,
+		});
+		self
+	}
+
+	// Makes the process soft real-time, with a deadline on every CPU burst and the percentage of them it may miss.
+	pub fn soft_deadline(mut self, deadline: SimTime, tolerance: u32) -> Self {
+		self.soft_deadline = Some(SoftDeadline {
+			deadline,
+			tolerance,
+		});
+		self
+	}
+
+	// Makes the process periodic, releasing each of its CPU bursts one period after the last.
+	pub fn period(mut self, period: SimTime) -> Self {
+		self.period = Some(period);
+		self
+	}
+
+	// Makes the process retry with a next CPU burst `factor` times as long when a burst waits past the timeout.
+	pub fn retry(mut self, timeout: SimTime, factor: u64) -> Self {
+		self.retry = Some(RetryPolicy { timeout, factor });
+		self
+	}
+
+	// Tags each CPU burst in order, such as with the phase of the program it is in, where an empty tag leaves it untagged.
+	//
+	// Bursts past the last tag are untagged, and tags past the last burst are ignored.
+	pub fn burst_tags<'t>(mut self, tags: impl IntoIterator<Item = &'t str>) -> Self {
+		self.burst_tags = tags
+			.into_iter()
+			.map(|tag| (!tag.is_empty()).then(|| tag.to_string()))
+			.collect();
+		self.burst_tags.resize(self.cpu_times.len(), None);
+		self
+	}
+
+	// Follows each CPU burst but the last with an accelerator burst of the given length, where 0 is none.
+	pub fn accelerator_times(mut self, times: impl IntoIterator<Item = u64>) -> Self {
+		self.accelerator_times = times.into_iter().map(SimTime::new).collect();
+		self
+	}
+
+	// Returns the line of the workload file that describes the process, leaving out default fields.
+	fn line(&self) -> String {
+		let times = |times: &VecDeque<SimTime>| {
+			times
+				.iter()
+				.map(ToString::to_string)
+				.collect::<Vec<_>>()
+				.join(",")
+		};
+
+		let mut line = format!("cpu={}", times(&self.cpu_times));
+
+		// The file needs an I/O time between every pair of CPU bursts, where the builder pads them with zeros,
+		// except for periodic processes, which wait for their next release instead.
+		let mut io_times = self.io_times.clone();
+
+		if self.period.is_none() {
+			io_times.resize(
+				io_times.len().max(self.cpu_times.len().saturating_sub(1)),
+				SimTime::ZERO,
+			);
+		}
+
+		if !io_times.is_empty() {
+			line += &format!(" io={}", times(&io_times));
+		}
+
+		if self.accelerator_times.iter().any(|time| !time.is_zero()) {
+			let mut accelerator_times = self.accelerator_times.clone();
+
+			accelerator_times.resize(self.cpu_times.len().saturating_sub(1), SimTime::ZERO);
+
+			line += &format!(" accel={}", times(&accelerator_times));
+		}
+
+		if !self.arrival_time.is_zero() {
+			line += &format!(" arrival={}", self.arrival_time);
+		}
+
+		if self.priority != 0 {
+			line += &format!(" priority={}", self.priority);
+		}
+
+		match self.io_priority {
+			IoPriority::RealTime => line += " io_priority=realtime",
+			IoPriority::BestEffort => {}
+			IoPriority::Idle => line += " io_priority=idle",
+		}
+
+		if self.tickets != DEFAULT_TICKETS {
+			line += &format!(" tickets={}", self.tickets);
+		}
+
+		if let Some(class) = &self.class {
+			line += &format!(" class={class}");
+		}
+
+		if !self.think_time.is_zero() {
+			line += &format!(" think={}", self.think_time);
+		}
+
+		if let Some(spawn) = self.spawn {
+			line += &format!(" parent={} spawn_at={}", spawn.parent, spawn.after_bursts);
+		}
+
+		if let Some(period) = self.period {
+			line += &format!(" period={period}");
+		}
+
+		if let Some(soft_deadline) = self.soft_deadline {
+			line += &format!(" deadline={}", soft_deadline.deadline);
+
+			if soft_deadline.tolerance != 0 {
+				line += &format!(" tolerance={}", soft_deadline.tolerance);
+			}
+		}
+
+		if let Some(retry) = self.retry {
+			line += &format!(" timeout={} retry={}", retry.timeout, retry.factor);
+		}
+
+		if self.burst_tags.iter().any(Option::is_some) {
+			let tags: Vec<_> = self
+				.burst_tags
+				.iter()
+				.map(|tag| tag.as_deref().unwrap_or_default())
+				.collect();
+
+			line += &format!(" tags={}", tags.join(","));
+		}
+
+		line
+	}
+
+	// Adds the process to the workload, returning its ID.
+	pub fn add(mut self) -> u32 {
+		let id = self.workload.next_id;
+
+		self.workload.next_id += 1;
+
+		let line = matches!(self.workload.recording, Some(Ok(_))).then(|| self.line());
+
+		// Every gap between CPU bursts gets an I/O time and, if any has one, an accelerator time, zero if none
+		// was given, except in periodic processes.
+		let gaps = self.cpu_times.len().saturating_sub(1);
+
+		if self.period.is_none() {
+			self.io_times
+				.resize(gaps.max(self.io_times.len()), SimTime::ZERO);
+
+			if !self.accelerator_times.is_empty() {
+				self.accelerator_times
+					.resize(gaps.max(self.accelerator_times.len()), SimTime::ZERO);
+			}
+		}
+
+		// The think time is spent in every gap between CPU bursts, on top of any I/O.
+		if !self.think_time.is_zero() {
+			for io_time in self.io_times.iter_mut().take(gaps) {
+				*io_time += self.think_time;
+			}
+		}
+
+		let mut process = Process::new(id, self.io_times, self.cpu_times);
+
+		process.arrive_at(self.arrival_time);
+		process.io_priority = self.io_priority;
+		process.priority = self.priority;
+		process.tickets = self.tickets;
+		process.class = self.class;
+		process.think_time = self.think_time;
+		process.spawn = self.spawn;
+		process.soft_deadline = self.soft_deadline;
+		process.period = self.period;
+		process.retry = self.retry;
+		process.burst_tags = self.burst_tags;
+		process.accelerator_times = self.accelerator_times;
+
+		// Record the line only if it rebuilds the process, stopping the recording otherwise.
+		if let Some(line) = line {
+			let recording = &mut self.workload.recording;
+
+			match check_process(&process, &self.workload.processes)
+				.and_then(|()| check_names(&process))
+			{
+				Ok(()) => {
+					if let Some(Ok(recording)) = recording {
+						recording.push_str(&line);
+						recording.push('\n');
+					}
+				}
+				Err(message) => {
+					*recording = Some(Err(format!("P{id} cannot be recorded: {message}")))
+				}
+			}
+		}
+
+		self.workload.processes.push(process);
+
+		id
+	}
+}
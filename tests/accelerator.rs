@@ -0,0 +1,105 @@
+use mlfq_scheduler_simulator::{
+	accelerator::{Accelerator, AcceleratorPolicy},
+	engine::Summary,
+	sink::CollectingSink,
+	testing,
+	time::SimTime,
+	workload::Workload,
+};
+
+// Runs the workload under FCFS with the accelerator, returning the summary and the finish time of each process.
+fn run(lines: &[&str], accelerator: Option<Accelerator>) -> (Summary, Vec<(u32, u64)>) {
+	let mut engine = testing::engine(lines, testing::fcfs());
+
+	if let Some(accelerator) = accelerator {
+		engine = engine.with_accelerator(accelerator);
+	}
+
+	let mut sink = CollectingSink::new();
+
+	engine.run_with(&mut sink);
+
+	let finished = sink
+		.finished
+		.iter()
+		.map(|&(id, time)| (id, time.as_u64()))
+		.collect();
+
+	(engine.summary().clone(), finished)
+}
+
+#[test]
+fn accelerator_bursts_queue_for_the_units() {
+	let lines = ["cpu=2,2 io=0 accel=10", "cpu=2,2 io=0 accel=10"];
+
+	// Without an accelerator each burst runs right away.
+	let (_, finished) = run(&lines, None);
+
+	assert_eq!(finished, [(1, 14), (2, 16)]);
+
+	// With one unit P2 waits from 4 until P1's burst is over at 12.
+	let (summary, finished) = run(
+		&lines,
+		Some(Accelerator::new(1, AcceleratorPolicy::FirstComeFirstServe)),
+	);
+
+	assert_eq!(finished, [(1, 14), (2, 24)]);
+
+	let accelerator = summary.accelerator.as_ref().unwrap();
+
+	assert_eq!(accelerator.burst_count, 2);
+	assert_eq!(accelerator.busy_time, SimTime::new(20));
+	assert_eq!(accelerator.waiting_average(), 4.0);
+	assert_eq!(
+		accelerator.utilization(summary.total_time),
+		20.0 / 24.0 * 100.0
+	);
+	assert_eq!(summary.total_turnaround_time, SimTime::new(14 + 24));
+
+	// With two units neither waits.
+	let (_, finished) = run(
+		&lines,
+		Some(Accelerator::new(2, AcceleratorPolicy::FirstComeFirstServe)),
+	);
+
+	assert_eq!(finished, [(1, 14), (2, 16)]);
+}
+
+#[test]
+fn the_accelerator_policy_orders_its_queue() {
+	let lines = [
+		"cpu=1,1 io=0 accel=20",
+		"cpu=1,1 io=0 accel=10",
+		"cpu=1,1 io=0 accel=5",
+	];
+
+	let (_, finished) = run(
+		&lines,
+		Some(Accelerator::new(1, AcceleratorPolicy::FirstComeFirstServe)),
+	);
+
+	assert_eq!(finished, [(1, 22), (2, 32), (3, 37)]);
+
+	let (_, finished) = run(
+		&lines,
+		Some(Accelerator::new(1, AcceleratorPolicy::ShortestJobFirst)),
+	);
+
+	assert_eq!(finished, [(1, 22), (3, 27), (2, 37)]);
+}
+
+#[test]
+fn io_follows_the_accelerator_burst() {
+	// P1 is on the accelerator from 2 to 7, then does I/O until 10.
+	let (_, finished) = run(&["cpu=2,2 io=3 accel=5"], None);
+
+	assert_eq!(finished, [(1, 12)]);
+}
+
+#[test]
+fn accelerator_times_need_one_per_gap() {
+	let error = Workload::parse("cpu=2,2,2 io=1,1 accel=5").err().unwrap();
+
+	assert_eq!(error.message, "accel has 1 time but cpu has 3 times");
+	assert!(Workload::parse("cpu=2,2 period=10 accel=5").is_err());
+}
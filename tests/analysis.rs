@@ -0,0 +1,122 @@
+use mlfq_scheduler_simulator::{
+	analysis::{order_inversions, priority_inversions, Inversion, Ranking},
+	process::Process,
+	schedule_test,
+	scheduler::Scheduler,
+	testing,
+	time::SimTime,
+	workload::Workload,
+};
+
+// Runs the workload under the algorithm and returns the inversions in its trace.
+fn inversions<S: Scheduler>(
+	lines: &[&str],
+	algorithm: impl FnOnce(Vec<Process>) -> S,
+	ranking: Ranking,
+) -> Vec<Inversion> {
+	let trace = testing::record(lines, algorithm, 1);
+	let workload = Workload::parse(&lines.join("\n")).unwrap();
+
+	priority_inversions(&trace, workload.processes(), ranking)
+}
+
+fn inversion(waiting: u32, running: u32, start: u64, end: u64) -> Inversion {
+	Inversion {
+		waiting,
+		running,
+		cpu: 0,
+		start: SimTime::new(start),
+		end: SimTime::new(end),
+	}
+}
+
+const LATE_HIGH_PRIORITY: [&str; 2] = ["cpu=6 priority=1", "cpu=2 arrival=2 priority=5"];
+
+#[test]
+fn non_preemptive_schedules_may_finish_the_running_burst() {
+	let ranking = Ranking::Priority { preemptive: false };
+
+	assert_eq!(
+		inversions(&LATE_HIGH_PRIORITY, testing::priority(false), ranking),
+		[]
+	);
+}
+
+#[test]
+fn preemptive_rankings_count_every_wait() {
+	let ranking = Ranking::Priority { preemptive: true };
+
+	assert_eq!(
+		inversions(&LATE_HIGH_PRIORITY, testing::priority(false), ranking),
+		[inversion(2, 1, 2, 6)]
+	);
+}
+
+#[test]
+fn priority_schedules_have_no_inversions() {
+	let lines = [
+		"cpu=3,3 io=4 priority=1",
+		"cpu=2,2,2 io=3,3 priority=3",
+		"cpu=4 arrival=1 priority=2",
+	];
+
+	assert_eq!(
+		inversions(
+			&lines,
+			testing::priority(false),
+			Ranking::Priority { preemptive: false }
+		),
+		[]
+	);
+}
+
+// When no process is ready, the MLFQ waits on its lowest level even if a higher level gets a process first.
+#[test]
+fn idle_multi_level_feedback_queues_wait_on_the_lowest_level() {
+	const LINES: [&str; 2] = ["cpu=3,2 io=2", "cpu=1,1 io=2"];
+
+	schedule_test! {
+		workload: ["cpu=3,2 io=2", "cpu=1,1 io=2"],
+		algorithm: mlfq_levels("rr:2,fcfs"),
+		expect: [P1@0..2, P2@2..3, P1@3..4, P1@6..8, P2@8..9],
+	}
+
+	assert_eq!(
+		inversions(&LINES, testing::mlfq_levels("rr:2,fcfs"), Ranking::Levels),
+		[inversion(2, 1, 6, 8)]
+	);
+}
+
+#[test]
+fn order_inversions_count_the_swapped_pairs() {
+	assert_eq!(order_inversions(&[1, 2, 3, 4], &[1, 2, 3, 4]), 0);
+	assert_eq!(order_inversions(&[4, 3, 2, 1], &[1, 2, 3, 4]), 6);
+	assert_eq!(order_inversions(&[2, 1, 4, 3, 5], &[1, 2, 3, 4, 5]), 2);
+
+	// Processes that only finished in one of the runs are left out.
+	assert_eq!(order_inversions(&[3, 1], &[1, 2, 3]), 1);
+	assert_eq!(order_inversions(&[], &[1, 2]), 0);
+}
+
+// SJF runs the short P3 ahead of P2, which arrived first, and RR lets the short processes finish before P1.
+#[test]
+fn summaries_keep_the_completion_order() {
+	const LINES: [&str; 3] = ["cpu=10", "cpu=5 arrival=1", "cpu=2 arrival=2"];
+
+	let fcfs = completion_order(&LINES, testing::fcfs());
+	let sjf = completion_order(&LINES, testing::sjf());
+	let rr = completion_order(&LINES, testing::rr(2));
+
+	assert_eq!(fcfs, [1, 2, 3]);
+	assert_eq!(sjf, [1, 3, 2]);
+	assert_eq!(rr, [3, 2, 1]);
+	assert_eq!(order_inversions(&sjf, &fcfs), 1);
+	assert_eq!(order_inversions(&rr, &fcfs), 3);
+}
+
+fn completion_order<S: Scheduler>(
+	lines: &[&str],
+	algorithm: impl FnOnce(Vec<Process>) -> S,
+) -> Vec<u32> {
+	testing::summary(lines, algorithm).completion_order
+}
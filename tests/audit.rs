@@ -0,0 +1,87 @@
+use mlfq_scheduler_simulator::{
+	audit::{AuditLog, KEYFRAME_INTERVAL},
+	engine::Engine,
+	scheduler::LevelLists,
+	testing,
+	time::SimTime,
+	workload::load_test_workload,
+};
+
+fn audited_run() -> (AuditLog, usize) {
+	let processes = load_test_workload().into_processes();
+	let mut engine = Engine::new(testing::mlfq()(processes)).with_audit(AuditLog::new());
+	let mut steps = 0;
+
+	while !engine.is_stopped() {
+		engine.step();
+		steps += 1;
+	}
+
+	(engine.audit().unwrap().clone(), steps)
+}
+
+#[test]
+fn every_dispatch_is_recorded_with_its_queues() {
+	let (audit, steps) = audited_run();
+
+	assert_eq!(audit.len(), steps);
+	assert!(audit.len() > KEYFRAME_INTERVAL);
+
+	let (decision, queues) = audit.decision(2).unwrap();
+
+	assert_eq!((decision.time, decision.id), (SimTime::new(9), 3));
+	assert_eq!(decision.end, SimTime::new(14));
+	assert_eq!(
+		queues.levels[0],
+		LevelLists {
+			io: vec![(1, SimTime::new(23)), (2, SimTime::new(48))],
+			cpu: [(3, 8), (4, 3), (5, 16), (6, 11), (7, 14), (8, 4)]
+				.map(|(id, time)| (id, SimTime::new(time)))
+				.to_vec(),
+		}
+	);
+	assert!(queues.devices.is_empty());
+	assert!(queues.accelerator.is_empty());
+}
+
+#[test]
+fn snapshots_past_a_keyframe_are_rebuilt() {
+	let (audit, _) = audited_run();
+
+	// The dispatched process was always waiting in one of the queues it was chosen from.
+	for (index, decision) in audit.decisions().enumerate() {
+		let (rebuilt, queues) = audit.decision(index).unwrap();
+
+		assert_eq!(rebuilt, *decision);
+		assert!(queues.levels.iter().any(|level| {
+			level
+				.cpu
+				.iter()
+				.chain(&level.io)
+				.any(|&(id, _)| id == decision.id)
+		}));
+	}
+
+	// Only the queues that changed are stored between keyframes.
+	let queue_count = audit.decision(0).unwrap().1.levels.len() * 2 + 1;
+
+	assert!(audit.stored_queues() < audit.len() * queue_count);
+	assert!(audit.decision(audit.len()).is_none());
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn audit_logs_round_trip_through_json() {
+	let (audit, _) = audited_run();
+	let path = std::env::temp_dir().join("mlfq_audit.json");
+
+	audit.save(&path).unwrap();
+	let loaded = AuditLog::load(&path).unwrap();
+	std::fs::remove_file(&path).unwrap();
+
+	assert_eq!(loaded.len(), audit.len());
+
+	for index in [0, KEYFRAME_INTERVAL - 1, KEYFRAME_INTERVAL, audit.len() - 1] {
+		assert_eq!(loaded.decision(index), audit.decision(index));
+	}
+}
@@ -0,0 +1,80 @@
+use mlfq_scheduler_simulator::{
+	engine::Summary,
+	experiments::{self, Sweep, WorkloadResult},
+	testing,
+	time::SimTime,
+};
+
+// Runs the workload under FCFS and SJF, which only differ when a long process arrives first.
+fn result(name: &str, lines: &str) -> WorkloadResult {
+	let fcfs = testing::summary(&[lines], testing::fcfs());
+	let sjf = testing::summary(&[lines], testing::sjf());
+
+	WorkloadResult {
+		name: name.to_string(),
+		turnaround_averages: vec![
+			("FCFS".to_string(), fcfs.turnaround_average()),
+			("SJF".to_string(), sjf.turnaround_average()),
+		],
+		summary: fcfs,
+	}
+}
+
+#[test]
+fn workloads_are_ranked_by_how_much_the_algorithms_differ() {
+	// SJF only changes the order of processes waiting together, after P1.
+	let mut results = vec![
+		result("ordered", "cpu=3\ncpu=2 arrival=1\ncpu=20 arrival=2"),
+		result(
+			"convoy",
+			"cpu=3\ncpu=20 arrival=1\ncpu=2 arrival=2\ncpu=2 arrival=2",
+		),
+		result("mild", "cpu=3\ncpu=4 arrival=1\ncpu=2 arrival=2"),
+	];
+
+	assert_eq!(results[0].sensitivity(), 0.0);
+
+	// The short processes wait behind P2 under FCFS, averaging 18.25 instead of 9.25.
+	let convoy = &results[1];
+
+	assert_eq!(convoy.sensitivity(), (18.25 - 9.25) / 9.25 * 100.0);
+	assert_eq!(convoy.best().unwrap().0, "SJF");
+	assert_eq!(convoy.worst().unwrap().0, "FCFS");
+
+	experiments::rank_by_sensitivity(&mut results);
+
+	let names: Vec<&str> = results.iter().map(|result| result.name.as_str()).collect();
+
+	assert_eq!(names, ["convoy", "mild", "ordered"]);
+}
+
+#[test]
+fn workloads_without_finished_processes_are_insensitive() {
+	let result = WorkloadResult {
+		name: "empty".to_string(),
+		summary: Summary::default(),
+		turnaround_averages: vec![("FCFS".to_string(), f64::NAN)],
+	};
+
+	assert_eq!(result.sensitivity(), 0.0);
+	assert!(result.best().is_none());
+}
+
+#[test]
+fn sweeps_reject_configurations_they_cannot_build() {
+	let sweep = |quantum: u64, level_count: usize| Sweep {
+		quanta: vec![SimTime::new(quantum)],
+		boost_intervals: vec![None],
+		level_counts: vec![level_count],
+	};
+
+	assert_eq!(sweep(5, 3).check(), Ok(()));
+	assert!(sweep(5, 0).check().is_err());
+	assert!(sweep(5, 70).check().is_err());
+	assert!(sweep(u64::MAX / 2, 3).check().is_err());
+
+	let configuration = sweep(5, 64).configurations()[0];
+
+	assert_eq!(configuration.quantum_of(1), Some(SimTime::new(10)));
+	assert_eq!(configuration.quantum_of(63), None);
+}
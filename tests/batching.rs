@@ -0,0 +1,82 @@
+use mlfq_scheduler_simulator::{
+	algorithms::{LevelPolicy, MultiLevelFeedbackQueue},
+	engine::Engine,
+	sink::CollectingSink,
+	time::SimTime,
+	workload::Workload,
+};
+
+const LEVELS: [LevelPolicy; 2] = [
+	LevelPolicy::RoundRobin {
+		quanta: SimTime::new(5),
+	},
+	LevelPolicy::FirstComeFirstServe,
+];
+
+// P1 and P2 are demoted by 10, and the short P3 arrives in the first level while P1 runs in the last.
+const WORKLOAD: &str = "cpu=20\ncpu=20\ncpu=2 arrival=16";
+
+// Runs the workload, returning the scheduler and the finish time of each process.
+fn run(
+	scheduler: impl FnOnce(MultiLevelFeedbackQueue) -> MultiLevelFeedbackQueue,
+) -> (MultiLevelFeedbackQueue, Vec<(u32, u64)>) {
+	let processes = Workload::parse(WORKLOAD).unwrap().into_processes();
+	let mut engine = Engine::new(scheduler(MultiLevelFeedbackQueue::with_levels(
+		processes.into(),
+		&LEVELS,
+	)));
+	let mut sink = CollectingSink::new();
+
+	engine.run_with(&mut sink);
+
+	let finished = sink
+		.finished
+		.iter()
+		.map(|&(id, time)| (id, time.as_u64()))
+		.collect();
+
+	(engine.scheduler().clone(), finished)
+}
+
+#[test]
+fn higher_levels_wait_for_the_batch() {
+	let (scheduler, finished) = run(|scheduler| scheduler);
+
+	assert_eq!(finished, [(1, 25), (3, 27), (2, 42)]);
+	assert!(scheduler.batching().is_none());
+
+	// P2 was ready along with P1 when the batch started, so it runs before P3.
+	let (scheduler, finished) = run(|scheduler| scheduler.with_batching(true));
+
+	assert_eq!(finished, [(1, 25), (2, 40), (3, 42)]);
+
+	let batching = scheduler.batching().unwrap();
+
+	assert_eq!(batching.batches, 1);
+	assert_eq!(batching.bursts, 2);
+	assert_eq!(batching.delay, SimTime::new(15));
+}
+
+#[test]
+fn a_boost_ends_the_batch() {
+	let (scheduler, finished) = run(|scheduler| {
+		scheduler
+			.with_batching(true)
+			.with_boost_interval(SimTime::new(20))
+	});
+
+	// The boost at 25 moves P2 up along with P3, so P3 no longer waits for it.
+	assert!(finished.contains(&(3, 27)));
+
+	let batching = scheduler.batching().unwrap();
+
+	assert_eq!(batching.bursts, batching.batches);
+	assert_eq!(batching.delay, SimTime::ZERO);
+}
+
+#[test]
+#[should_panic(expected = "the boost interval must not be zero")]
+fn a_zero_boost_interval_is_rejected() {
+	MultiLevelFeedbackQueue::with_levels(Vec::new().into(), &LEVELS)
+		.with_boost_interval(SimTime::ZERO);
+}
@@ -0,0 +1,53 @@
+use std::collections::VecDeque;
+
+use mlfq_scheduler_simulator::{
+	algorithms::MultiLevelFeedbackQueue, cancel::CancellationToken, engine::Engine,
+	trace::TraceRecorder, workload::load_test_workload,
+};
+
+fn engine(token: &CancellationToken) -> Engine<MultiLevelFeedbackQueue> {
+	let processes: VecDeque<_> = load_test_workload().into_processes().into();
+
+	Engine::new(MultiLevelFeedbackQueue::from_processes(processes))
+		.with_recorder(TraceRecorder::new())
+		.with_cancellation(token.clone())
+}
+
+#[test]
+fn cancelled_runs_stop_between_steps() {
+	let token = CancellationToken::new();
+	let mut engine = engine(&token);
+
+	for _ in 0..30 {
+		engine.step();
+	}
+
+	let time = engine.current_time();
+
+	token.cancel();
+	engine.run();
+
+	assert!(engine.is_cancelled());
+	assert!(!engine.is_finished());
+	assert_eq!(
+		engine.summary().cancelled_at,
+		Some(engine.summary().total_time)
+	);
+	assert!(engine.summary().total_time > time);
+	assert_eq!(
+		engine.recorder().unwrap().trace().cancelled_at,
+		engine.summary().cancelled_at
+	);
+}
+
+#[test]
+fn uncancelled_runs_finish() {
+	let token = CancellationToken::new();
+	let mut engine = engine(&token);
+
+	engine.run();
+
+	assert!(engine.is_finished());
+	assert_eq!(engine.summary().cancelled_at, None);
+	assert_eq!(engine.recorder().unwrap().trace().cancelled_at, None);
+}
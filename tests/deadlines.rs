@@ -0,0 +1,121 @@
+use mlfq_scheduler_simulator::{
+	process::Process, report::Report, schedulability::Schedulability, scheduler::Scheduler,
+	testing, time::SimTime, workload::Workload,
+};
+
+// Runs the workload under the algorithm and returns the finished processes.
+fn finished<S: Scheduler>(lines: &[&str], algorithm: impl FnOnce(Vec<Process>) -> S) -> Report {
+	Report::run(&mut testing::engine(lines, algorithm))
+}
+
+#[test]
+fn deadlines_count_from_when_each_burst_is_ready() {
+	// P1's second burst is ready at 6 and due at 11, but P2 holds the CPU until 14.
+	let report = finished(
+		&["cpu=4,4 io=2 deadline=5 tolerance=50", "cpu=10"],
+		testing::fcfs(),
+	);
+	let process = report
+		.processes
+		.iter()
+		.find(|process| process.id == 1)
+		.unwrap();
+
+	assert_eq!(process.deadlines_met, 1);
+	assert_eq!(process.deadlines_missed, 1);
+}
+
+#[test]
+fn the_budget_is_a_share_of_every_burst() {
+	let mut engine = testing::engine(
+		&["cpu=4,4 io=2 deadline=5 tolerance=50", "cpu=10"],
+		testing::fcfs(),
+	);
+
+	let mut budgets = Vec::new();
+
+	while !engine.is_stopped() {
+		if let Some(process) = engine.step().finished.filter(|process| process.id == 1) {
+			budgets.push(process.budget_used());
+		}
+	}
+
+	// Half of two bursts is one miss, which P1 used.
+	assert_eq!(budgets, [1.0]);
+}
+
+#[test]
+fn processes_without_deadlines_are_not_counted() {
+	let report = finished(&["cpu=4,4 io=2", "cpu=10"], testing::soft_rt(5));
+
+	assert!(report
+		.processes
+		.iter()
+		.all(|process| process.deadlines_met == 0 && process.deadlines_missed == 0));
+}
+
+#[test]
+fn tolerances_need_a_deadline() {
+	assert!(Workload::parse("cpu=4 tolerance=10").is_err());
+	assert!(Workload::parse("cpu=4 deadline=0").is_err());
+	assert!(Workload::parse("cpu=4 deadline=5 tolerance=101").is_err());
+}
+
+#[test]
+fn periodic_bursts_wait_for_their_release() {
+	let report = finished(&["cpu=1,1,1 period=5"], testing::fcfs());
+	let process = &report.processes[0];
+
+	assert_eq!(process.finish_time, SimTime::new(11));
+	assert_eq!(process.turnaround_time, SimTime::new(11));
+	assert_eq!(process.deadlines_met, 3);
+}
+
+#[test]
+fn late_periodic_jobs_miss_their_deadlines() {
+	// P2's first job is released at 0 and due at 4, but P1 holds the CPU until 6.
+	let report = finished(&["cpu=6", "cpu=1,1 period=4"], testing::fcfs());
+	let process = report
+		.processes
+		.iter()
+		.find(|process| process.id == 2)
+		.unwrap();
+
+	assert_eq!(process.deadlines_missed, 1);
+	assert_eq!(process.deadlines_met, 1);
+}
+
+#[test]
+fn periodic_processes_take_no_io_times() {
+	assert!(Workload::parse("cpu=1,1 io=2 period=4").is_err());
+	assert!(Workload::parse("cpu=1 period=0").is_err());
+}
+
+#[test]
+fn schedulability_follows_utilization_and_response_times() {
+	let workload =
+		Workload::parse("cpu=1 period=4\ncpu=2 period=6\ncpu=3 period=8\ncpu=20").unwrap();
+	let schedulability = Schedulability::analyze(workload.processes()).unwrap();
+
+	assert_eq!(
+		schedulability.utilization,
+		1.0 / 4.0 + 2.0 / 6.0 + 3.0 / 8.0
+	);
+	assert!(schedulability.earliest_deadline_first);
+	assert!(!schedulability.rate_monotonic());
+	assert_eq!(
+		schedulability.response_times,
+		[
+			(1, Some(SimTime::new(1))),
+			(2, Some(SimTime::new(3))),
+			(3, None)
+		]
+	);
+}
+
+#[test]
+fn only_periodic_processes_are_analyzed() {
+	let workload = Workload::parse("cpu=4 deadline=5").unwrap();
+
+	assert_eq!(Schedulability::analyze(workload.processes()), None);
+}
@@ -0,0 +1,36 @@
+use mlfq_scheduler_simulator::{testing, time::SimTime};
+
+#[test]
+fn identical_progress_is_perfectly_fair() {
+	let summary = testing::summary(&["cpu=4", "cpu=4 arrival=4"], testing::fcfs());
+
+	assert_eq!(summary.fairness.jain_index(), 1.0);
+	assert_eq!(summary.fairness.longest_wait, SimTime::ZERO);
+}
+
+#[test]
+fn waiting_lowers_the_index() {
+	// P1 never waits and P2 waits half of its turnaround, so the progress rates are 1 and 0.5.
+	let summary = testing::summary(&["cpu=4", "cpu=4"], testing::fcfs());
+	let fairness = &summary.fairness;
+
+	assert_eq!(fairness.jain_index(), 1.5 * 1.5 / (2.0 * 1.25));
+	assert_eq!(fairness.longest_wait, SimTime::new(4));
+	assert_eq!(fairness.longest_waiting_process, Some(2));
+	assert_eq!(fairness.most_waiting_process, Some(2));
+}
+
+#[test]
+fn the_mlfq_counts_demotions_and_time_in_each_level() {
+	// P1 is demoted after 5 and 10, finishing at 23 in level 3, and P2 runs 5..8 in level 1.
+	let summary = testing::summary(&["cpu=20", "cpu=3"], testing::mlfq());
+	let fairness = &summary.fairness;
+
+	assert_eq!(fairness.demotions, 2);
+	assert_eq!(fairness.boosts, 0);
+	assert_eq!(
+		fairness.level_times,
+		[SimTime::new(5 + 8), SimTime::new(13), SimTime::new(5)]
+	);
+	assert_eq!(fairness.longest_wait, SimTime::new(5));
+}
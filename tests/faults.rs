@@ -0,0 +1,40 @@
+use mlfq_scheduler_simulator::{
+	diff::ReportDiff, faults::FaultInjector, process::Process, report::Report,
+	scheduler::Scheduler, testing,
+};
+
+// Runs the workload under the algorithm with each burst crashing its process with the probability.
+fn crashed<S: Scheduler>(
+	lines: &[&str],
+	algorithm: impl FnOnce(Vec<Process>) -> S,
+	probability: f64,
+) -> Report {
+	let faults = FaultInjector::new(probability, 7);
+
+	Report::run(&mut testing::engine(lines, algorithm).with_faults(faults))
+}
+
+#[test]
+fn runs_where_every_process_crashes_have_no_averages() {
+	let before = crashed(&["cpu=4", "cpu=6"], testing::fcfs(), 1.0);
+	let after = crashed(&["cpu=6", "cpu=4"], testing::fcfs(), 1.0);
+
+	assert_eq!(before.summary.process_count, 0);
+	assert_eq!(before.summary.crashed_count, 2);
+	assert!(before.summary.turnaround_average().is_nan());
+
+	let changelog = ReportDiff::new(&before, &after).changelog();
+
+	assert!(changelog.iter().all(|line| !line.starts_with("Average")));
+}
+
+#[test]
+fn preemption_does_not_raise_the_crash_rate() {
+	// Each process runs one burst, so both draw the same 40 rolls however often the MLFQ preempts.
+	let lines = ["cpu=100"; 40];
+	let fcfs = crashed(&lines, testing::fcfs(), 0.5).summary;
+	let mlfq = crashed(&lines, testing::mlfq(), 0.5).summary;
+
+	assert_eq!(fcfs.crashed_count, mlfq.crashed_count);
+	assert!(fcfs.crashed_count > 0 && fcfs.crashed_count < 40);
+}
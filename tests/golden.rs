@@ -0,0 +1,152 @@
+//! Runs a fixed workload under every algorithm and compares the whole trace and
+//! the final metrics with the files in `tests/golden`.
+//!
+//! After an intended change to a schedule, regenerate the files with
+//! `UPDATE_GOLDEN=1 cargo test --test golden` and review the diff.
+
+use std::{fmt::Write, fs, path::PathBuf};
+
+use mlfq_scheduler_simulator::{
+	engine::Engine, process::Process, scheduler::Scheduler, testing, trace::TraceRecorder,
+	workload::Workload,
+};
+
+fn golden_path(name: &str) -> PathBuf {
+	PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+		.join("tests/golden")
+		.join(name)
+}
+
+// Runs the golden workload and returns its trace and metrics as text.
+fn render<S: Scheduler>(algorithm: impl FnOnce(Vec<Process>) -> S, cpus: usize) -> String {
+	let text = fs::read_to_string(golden_path("workload.txt")).unwrap();
+	let workload = Workload::parse(&text).unwrap();
+	let mut engine = Engine::new(algorithm(workload.into_processes()))
+		.with_cpus(cpus)
+		.with_recorder(TraceRecorder::new());
+
+	engine.run();
+
+	let mut output = String::new();
+
+	for record in engine.recorder().unwrap().trace().records {
+		writeln!(
+			output,
+			"{} CPU {}: {:?}",
+			record.time,
+			record.cpu + 1,
+			record.event
+		)
+		.unwrap();
+	}
+
+	let summary = engine.summary();
+
+	writeln!(output).unwrap();
+	writeln!(output, "Processes: {}", summary.process_count).unwrap();
+	writeln!(output, "Turnaround Time: {}", summary.total_turnaround_time).unwrap();
+	writeln!(output, "Waiting Time: {}", summary.total_waiting_time).unwrap();
+	writeln!(output, "Response Time: {}", summary.total_response_time).unwrap();
+	writeln!(output, "Idle Time: {}", summary.idle_time).unwrap();
+	writeln!(output, "Total Time: {}", summary.total_time).unwrap();
+
+	output
+}
+
+// Compares the output with its golden file, or rewrites the file when `UPDATE_GOLDEN` is set.
+fn check(name: &str, output: String) {
+	let path = golden_path(&format!("{name}.trace"));
+
+	if std::env::var_os("UPDATE_GOLDEN").is_some() {
+		fs::write(&path, output).unwrap();
+
+		return;
+	}
+
+	let expected = fs::read_to_string(&path).unwrap_or_else(|_| {
+		panic!(
+			"{} is missing, run with UPDATE_GOLDEN=1 to create it",
+			path.display()
+		)
+	});
+
+	if let Some((line, (expected, actual))) = expected
+		.lines()
+		.zip(output.lines())
+		.enumerate()
+		.find(|(_, (expected, actual))| expected != actual)
+	{
+		panic!(
+			"{name} differs from {} at line {}:\nexpected: {expected}\n  actual: {actual}",
+			path.display(),
+			line + 1
+		);
+	}
+
+	assert_eq!(
+		expected.lines().count(),
+		output.lines().count(),
+		"{name} has a different number of lines than {}",
+		path.display()
+	);
+}
+
+#[test]
+fn first_come_first_serve() {
+	check("fcfs", render(testing::fcfs(), 1));
+}
+
+#[test]
+fn shortest_job_first() {
+	check("sjf", render(testing::sjf(), 1));
+}
+
+#[test]
+fn priority() {
+	check("priority", render(testing::priority(false), 1));
+}
+
+#[test]
+fn preemptive_priority() {
+	check("priority-preemptive", render(testing::priority(true), 1));
+}
+
+#[test]
+fn round_robin() {
+	check("rr", render(testing::rr(4), 1));
+}
+
+#[test]
+fn lottery() {
+	check("lottery", render(testing::lottery(4, 7), 1));
+}
+
+#[test]
+fn stride() {
+	check("stride", render(testing::stride(4), 1));
+}
+
+#[test]
+fn soft_real_time() {
+	check("soft-rt", render(testing::soft_rt(4), 1));
+}
+
+#[test]
+fn earliest_deadline_first() {
+	check("edf", render(testing::edf(), 1));
+}
+
+#[test]
+fn rate_monotonic() {
+	check("rm", render(testing::rm(), 1));
+}
+
+#[test]
+fn multi_level_feedback_queue() {
+	check("mlfq", render(testing::mlfq(), 1));
+}
+
+#[test]
+fn multi_level_feedback_queue_on_two_cpus() {
+	check("mlfq-2-cpus", render(testing::mlfq(), 2));
+}
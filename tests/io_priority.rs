@@ -0,0 +1,30 @@
+use mlfq_scheduler_simulator::{
+	devices::{IoPolicy, IoSubsystem},
+	engine::Engine,
+	process::IoPriority,
+	testing,
+	workload::Workload,
+};
+
+// Returns the average turnaround of the shell in the example workload, run under FCFS on one device.
+fn shell_turnaround(workload: Workload) -> f64 {
+	let mut engine = Engine::new(testing::fcfs()(workload.into_processes()))
+		.with_io(IoSubsystem::new(1, IoPolicy::FirstComeFirstServe));
+
+	engine.run();
+	engine
+		.summary()
+		.class("shell")
+		.unwrap()
+		.turnaround_average()
+}
+
+#[test]
+fn realtime_io_priority_shortens_interactive_turnaround() {
+	let workload = Workload::parse(include_str!("../workloads/io-priority.txt")).unwrap();
+	let mut without = workload.clone();
+
+	without.set_io_priority(IoPriority::BestEffort);
+
+	assert!(shell_turnaround(workload) < shell_turnaround(without));
+}
@@ -0,0 +1,80 @@
+use mlfq_scheduler_simulator::{
+	metrics::{MetricsPolicy, ResponseTime, TurnaroundTime, WaitingTime},
+	report::{ProcessRecord, Report},
+	testing,
+	time::SimTime,
+};
+
+// Runs the workload under FCFS with the metrics policy, returning the report.
+fn report(lines: &[&str], metrics: MetricsPolicy) -> Report {
+	Report::run(&mut testing::engine(lines, testing::fcfs()).with_metrics(metrics))
+}
+
+// Returns the turnaround, waiting, and response times of a finished process.
+fn times(report: &Report, id: u32) -> (u64, u64, u64) {
+	let process: &ProcessRecord = report.process(id).unwrap();
+
+	(
+		process.turnaround_time.as_u64(),
+		process.waiting_time.as_u64(),
+		process.response_time.as_u64(),
+	)
+}
+
+// P1 runs from 0 to 2 and is back from I/O at 5, but P2 holds the CPU from 2 to 8, so P1 ends at 10.
+const WORKLOAD: [&str; 2] = ["cpu=2,2 io=3", "cpu=6 arrival=1"];
+
+#[test]
+fn the_defaults_measure_each_process_from_its_arrival() {
+	let report = report(&WORKLOAD, MetricsPolicy::default());
+
+	assert_eq!(times(&report, 1), (10, 3, 0));
+	assert_eq!(times(&report, 2), (7, 1, 1));
+	assert_eq!(report.summary.total_response_time, SimTime::new(1));
+}
+
+#[test]
+fn textbook_conventions_can_be_picked_per_metric() {
+	let metrics = MetricsPolicy {
+		response: ResponseTime::FirstRun,
+		waiting: WaitingTime::TurnaroundLessCpu,
+		turnaround: TurnaroundTime::PerJob,
+	};
+	let report = report(&WORKLOAD, metrics);
+
+	// Waiting counts P1's I/O too, and response is the time P2 first ran at.
+	assert_eq!(times(&report, 1), (10, 6, 0));
+	assert_eq!(times(&report, 2), (7, 1, 2));
+	assert_eq!(report.summary.total_waiting_time, SimTime::new(7));
+	assert_eq!(report.summary.metrics, metrics);
+}
+
+#[test]
+fn per_burst_metrics_average_over_the_bursts() {
+	let metrics = MetricsPolicy {
+		response: ResponseTime::PerBurst,
+		turnaround: TurnaroundTime::PerBurst,
+		..MetricsPolicy::default()
+	};
+	let report = report(&WORKLOAD, metrics);
+
+	// P1's bursts took 2 and 5 from becoming ready, and its second one waited 3 to start.
+	assert_eq!(times(&report, 1), (4, 3, 2));
+	assert_eq!(times(&report, 2), (7, 1, 1));
+}
+
+#[test]
+fn policies_parse_from_their_definitions() {
+	let metrics: MetricsPolicy = "response=per-burst,turnaround=per-burst".parse().unwrap();
+
+	assert_eq!(metrics.response, ResponseTime::PerBurst);
+	assert_eq!(metrics.waiting, WaitingTime::ReadyQueue);
+	assert_eq!(
+		metrics.to_string(),
+		"response=per-burst,waiting=ready-queue,turnaround=per-burst"
+	);
+	assert_eq!(metrics.to_string().parse(), Ok(metrics));
+	assert_eq!("".parse(), Ok(MetricsPolicy::default()));
+	assert!("response=fastest".parse::<MetricsPolicy>().is_err());
+	assert!("latency=per-burst".parse::<MetricsPolicy>().is_err());
+}
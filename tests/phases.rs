@@ -0,0 +1,61 @@
+use mlfq_scheduler_simulator::{testing, time::SimTime, workload::Workload};
+
+#[test]
+fn bursts_are_measured_from_when_they_are_ready() {
+	// P1 starts up from 0 to 4 and is ready for its steady burst at 7, but P2 starts up until 9.
+	let summary = testing::summary(
+		&[
+			"cpu=4,2 io=3 tags=startup,steady",
+			"cpu=5 arrival=1 tags=startup",
+		],
+		testing::fcfs(),
+	);
+
+	let startup = summary.phase("startup").unwrap();
+
+	assert_eq!(startup.burst_count, 2);
+	assert_eq!(startup.total_waiting_time, SimTime::new(3));
+	assert_eq!(startup.total_turnaround_time, SimTime::new(4 + 8));
+	assert_eq!(startup.waiting_average(), 1.5);
+
+	let steady = summary.phase("steady").unwrap();
+
+	assert_eq!(steady.burst_count, 1);
+	assert_eq!(steady.total_waiting_time, SimTime::new(2));
+	assert_eq!(steady.total_turnaround_time, SimTime::new(4));
+}
+
+#[test]
+fn untagged_bursts_are_left_out() {
+	let summary = testing::summary(&["cpu=3,3,3 io=1,1 tags=,steady,", "cpu=3"], testing::rr(2));
+
+	assert_eq!(summary.phases.len(), 1);
+	assert_eq!(summary.phases[0].tag, "steady");
+	assert_eq!(summary.phases[0].burst_count, 1);
+}
+
+#[test]
+fn every_burst_needs_a_tag() {
+	let error = Workload::parse("cpu=4,2,2 io=1,1 tags=startup,steady")
+		.err()
+		.unwrap();
+
+	assert_eq!(error.message, "tags has 2 tags but cpu has 3 times");
+	assert_eq!(error.column, 18);
+}
+
+#[test]
+fn built_tags_are_recorded() {
+	let mut workload = Workload::recording();
+
+	workload
+		.process([2], [4, 1])
+		.burst_tags(["", "shutdown"])
+		.add();
+
+	assert_eq!(workload.recorded(), Some("cpu=4,1 io=2 tags=,shutdown\n"));
+
+	let process = &workload.processes()[0];
+
+	assert_eq!(process.burst_tags, [None, Some(String::from("shutdown"))]);
+}
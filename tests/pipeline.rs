@@ -0,0 +1,67 @@
+use mlfq_scheduler_simulator::{
+	algorithms::{FirstComeFirstServe, MultiLevelFeedbackQueue},
+	engine::Engine,
+	pipeline::Pipeline,
+	process::Process,
+	sink::CollectingSink,
+	time::SimTime,
+	workload::Workload,
+};
+
+fn processes(workload: &str) -> Vec<Process> {
+	Workload::parse(workload).unwrap().into_processes()
+}
+
+#[test]
+fn processes_arrive_at_the_next_stage_when_they_finish_the_last() {
+	let mut pipeline = Pipeline::new();
+
+	pipeline.run(processes("cpu=20\ncpu=3"), |processes| {
+		Engine::new(MultiLevelFeedbackQueue::from_processes(processes.into()))
+	});
+
+	let mut sink = CollectingSink::new();
+	let summary = pipeline.run_with(
+		processes("cpu=4\ncpu=10"),
+		|processes| Engine::new(FirstComeFirstServe::from_processes(processes)),
+		&mut sink,
+	);
+
+	// P2 leaves the CPU stage at 8 and P1 at 23, and each waits only for the accelerator.
+	assert_eq!(summary.process_count, 2);
+	assert_eq!(summary.total_waiting_time.as_u64(), 0);
+	assert_eq!(
+		sink.finished,
+		[(2, SimTime::new(18)), (1, SimTime::new(27))]
+	);
+
+	let turnaround_times: Vec<_> = pipeline
+		.turnaround_times()
+		.into_iter()
+		.map(|(id, time)| (id, time.as_u64()))
+		.collect();
+
+	assert_eq!(turnaround_times, [(1, 27), (2, 18)]);
+	assert_eq!(pipeline.turnaround_average(), 22.5);
+	assert_eq!(pipeline.stages.len(), 2);
+}
+
+#[test]
+fn processes_without_bursts_in_a_stage_leave_the_pipeline() {
+	let mut pipeline = Pipeline::new();
+
+	pipeline.run(processes("cpu=5\ncpu=5"), |processes| {
+		Engine::new(FirstComeFirstServe::from_processes(processes))
+	});
+
+	// Only P1 goes on to the second stage, and P3 never ran in the first.
+	let mut second = processes("cpu=2\ncpu=2\ncpu=2");
+	second.remove(1);
+
+	let summary = pipeline.run(second, |processes| {
+		Engine::new(FirstComeFirstServe::from_processes(processes))
+	});
+
+	assert_eq!(summary.process_count, 1);
+	assert_eq!(pipeline.turnaround_times()[0].1.as_u64(), 7);
+}
@@ -0,0 +1,146 @@
+use mlfq_scheduler_simulator::{
+	engine::Engine, process::Process, scheduler::Scheduler, testing, time::SimTime,
+	workload::Workload,
+};
+use proptest::prelude::*;
+
+/// A process as generated for a property, before it is added to a workload.
+#[derive(Clone, Debug)]
+struct Spec {
+	cpu_times: Vec<u64>,
+	io_times: Vec<u64>,
+	arrival_time: u64,
+	priority: u32,
+	tickets: u32,
+}
+
+fn spec() -> impl Strategy<Value = Spec> {
+	(
+		prop::collection::vec(1..20u64, 1..5),
+		0..40u64,
+		0..4u32,
+		1..200u32,
+	)
+		.prop_flat_map(|(cpu_times, arrival_time, priority, tickets)| {
+			let gaps = cpu_times.len() - 1;
+
+			prop::collection::vec(0..15u64, gaps).prop_map(move |io_times| Spec {
+				cpu_times: cpu_times.clone(),
+				io_times,
+				arrival_time,
+				priority,
+				tickets,
+			})
+		})
+}
+
+fn workload(specs: &[Spec]) -> Workload {
+	let mut workload = Workload::new();
+
+	for spec in specs {
+		workload
+			.process(spec.io_times.clone(), spec.cpu_times.clone())
+			.arrival_time(SimTime::new(spec.arrival_time))
+			.priority(spec.priority)
+			.tickets(spec.tickets)
+			.add();
+	}
+
+	workload
+}
+
+// Runs the workload step by step, checking the invariants every scheduler must keep.
+fn check_invariants<S: Scheduler>(
+	specs: &[Spec],
+	algorithm: impl FnOnce(Vec<Process>) -> S,
+	cpus: usize,
+) -> Result<(), TestCaseError> {
+	let mut engine = Engine::new(algorithm(workload(specs).into_processes())).with_cpus(cpus);
+
+	let mut cpu_clocks = vec![SimTime::ZERO; cpus];
+	let mut cpu_time = SimTime::ZERO;
+	let mut finished = 0;
+
+	while !engine.is_stopped() {
+		let step = engine.step();
+
+		// No step starts before the previous one on its CPU ended, so idle time is never negative.
+		prop_assert!(step.start >= cpu_clocks[step.cpu]);
+		prop_assert!(step.end >= step.start);
+
+		cpu_clocks[step.cpu] = step.end;
+		cpu_time += step.end - step.start;
+
+		if let Some(process) = step.finished {
+			let spec = &specs[process.id as usize - 1];
+			let bursts: u64 = spec.cpu_times.iter().chain(&spec.io_times).sum();
+
+			prop_assert!(process.turnaround_time >= SimTime::new(bursts));
+			prop_assert!(process.response_time <= process.turnaround_time);
+			prop_assert!(process.waiting_time <= process.turnaround_time);
+
+			finished += 1;
+		}
+	}
+
+	// Every burst ran to completion, for exactly as long as it asked for.
+	let total: u64 = specs.iter().flat_map(|spec| &spec.cpu_times).sum();
+
+	prop_assert_eq!(cpu_time, SimTime::new(total));
+	prop_assert_eq!(finished, specs.len());
+	prop_assert_eq!(engine.summary().process_count as usize, specs.len());
+
+	Ok(())
+}
+
+proptest! {
+	#[test]
+	fn first_come_first_serve(specs in prop::collection::vec(spec(), 1..8)) {
+		check_invariants(&specs, testing::fcfs(), 1)?;
+	}
+
+	#[test]
+	fn shortest_job_first(specs in prop::collection::vec(spec(), 1..8)) {
+		check_invariants(&specs, testing::sjf(), 1)?;
+	}
+
+	#[test]
+	fn priority(specs in prop::collection::vec(spec(), 1..8), preemptive: bool) {
+		check_invariants(&specs, testing::priority(preemptive), 1)?;
+	}
+
+	#[test]
+	fn round_robin(specs in prop::collection::vec(spec(), 1..8), quantum in 1..10u64) {
+		check_invariants(&specs, testing::rr(quantum), 1)?;
+	}
+
+	#[test]
+	fn lottery(specs in prop::collection::vec(spec(), 1..8), seed: u64) {
+		check_invariants(&specs, testing::lottery(4, seed), 1)?;
+	}
+
+	#[test]
+	fn stride(specs in prop::collection::vec(spec(), 1..8)) {
+		check_invariants(&specs, testing::stride(4), 1)?;
+	}
+
+	#[test]
+	fn earliest_deadline_first(specs in prop::collection::vec(spec(), 1..8)) {
+		check_invariants(&specs, testing::edf(), 1)?;
+	}
+
+	#[test]
+	fn rate_monotonic(specs in prop::collection::vec(spec(), 1..8)) {
+		check_invariants(&specs, testing::rm(), 1)?;
+	}
+
+	#[test]
+	fn soft_real_time(specs in prop::collection::vec(spec(), 1..8), quantum in 1..10u64) {
+		check_invariants(&specs, testing::soft_rt(quantum), 1)?;
+	}
+
+	#[test]
+	fn multi_level_feedback_queue(specs in prop::collection::vec(spec(), 1..8), cpus in 1..4usize) {
+		check_invariants(&specs, testing::mlfq(), cpus)?;
+	}
+}
@@ -0,0 +1,67 @@
+use mlfq_scheduler_simulator::{
+	report::Report, scheduler::Event, sink::CollectingSink, testing, time::SimTime,
+	workload::Workload,
+};
+
+#[test]
+fn timeouts_are_reported_with_the_retried_burst() {
+	let mut engine = testing::engine(
+		&["cpu=10", "cpu=2,3 io=0 timeout=5 retry=3"],
+		testing::fcfs(),
+	);
+	let mut sink = CollectingSink::new();
+
+	engine.run_with(&mut sink);
+
+	let timeouts: Vec<_> = sink
+		.events
+		.iter()
+		.filter(|event| matches!(event, Event::Timeout { .. }))
+		.collect();
+
+	assert_eq!(
+		timeouts,
+		[&Event::Timeout {
+			id: 2,
+			waited: SimTime::new(10),
+			burst: SimTime::new(9)
+		}]
+	);
+	assert_eq!(
+		sink.finished,
+		[(1, SimTime::new(10)), (2, SimTime::new(21))]
+	);
+}
+
+#[test]
+fn waits_are_counted_across_every_slice_of_a_burst() {
+	// Under round robin P1 waits 2 and then 2 more for its first burst, which is only past the timeout together.
+	let report = Report::run(&mut testing::engine(
+		&["cpu=6,1 io=0 timeout=3", "cpu=6"],
+		testing::rr(2),
+	));
+	let process = report
+		.processes
+		.iter()
+		.find(|process| process.id == 1)
+		.unwrap();
+
+	assert_eq!(process.timeouts, 1);
+	assert_eq!(process.cpu_time, SimTime::new(6 + 2));
+}
+
+#[test]
+fn the_last_burst_has_nothing_to_retry() {
+	let report = Report::run(&mut testing::engine(
+		&["cpu=10", "cpu=2 timeout=1"],
+		testing::fcfs(),
+	));
+
+	assert!(report.processes.iter().all(|process| process.timeouts == 0));
+}
+
+#[test]
+fn retries_need_a_timeout() {
+	assert!(Workload::parse("cpu=1,1 io=0 retry=2").is_err());
+	assert!(Workload::parse("cpu=1,1 io=0 timeout=2 retry=0").is_err());
+}
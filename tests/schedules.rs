@@ -0,0 +1,234 @@
+use mlfq_scheduler_simulator::schedule_test;
+
+#[test]
+fn first_come_first_serve_runs_in_arrival_order() {
+	schedule_test! {
+		workload: ["cpu=5", "cpu=3 arrival=1", "cpu=2 arrival=2"],
+		algorithm: fcfs,
+		expect: [P1@0..5, P2@5..8, P3@8..10],
+	}
+}
+
+#[test]
+fn first_come_first_serve_idles_until_an_arrival() {
+	schedule_test! {
+		workload: ["cpu=2", "cpu=3 arrival=10"],
+		algorithm: fcfs,
+		expect: [P1@0..2, P2@10..13],
+	}
+}
+
+#[test]
+fn first_come_first_serve_waits_for_io() {
+	schedule_test! {
+		workload: ["cpu=2,2 io=5", "cpu=4"],
+		algorithm: fcfs,
+		expect: [P1@0..2, P2@2..6, P1@7..9],
+	}
+}
+
+#[test]
+fn first_come_first_serve_fills_every_cpu() {
+	schedule_test! {
+		workload: ["cpu=4", "cpu=2", "cpu=3"],
+		algorithm: fcfs,
+		cpus: 2,
+		expect: [P1@0..4, P2@0..2, P3@2..5],
+	}
+}
+
+#[test]
+fn shortest_job_first_runs_the_shortest_burst() {
+	schedule_test! {
+		workload: ["cpu=6", "cpu=2", "cpu=4"],
+		algorithm: sjf,
+		expect: [P2@0..2, P3@2..6, P1@6..12],
+	}
+}
+
+#[test]
+fn shortest_job_first_does_not_preempt() {
+	schedule_test! {
+		workload: ["cpu=6", "cpu=2 arrival=1"],
+		algorithm: sjf,
+		expect: [P1@0..6, P2@6..8],
+	}
+}
+
+#[test]
+fn priority_runs_the_highest_priority() {
+	schedule_test! {
+		workload: ["cpu=3 priority=1", "cpu=3 priority=3", "cpu=3 priority=2"],
+		algorithm: priority(false),
+		expect: [P2@0..3, P3@3..6, P1@6..9],
+	}
+}
+
+#[test]
+fn preemptive_priority_preempts_when_io_completes() {
+	schedule_test! {
+		workload: ["cpu=6 priority=1", "cpu=1,2 io=1 priority=5"],
+		algorithm: priority(true),
+		expect: [P2@0..1, P1@1..2, P2@2..4, P1@4..9],
+	}
+}
+
+#[test]
+fn preemptive_priority_preempts_for_late_arrivals() {
+	schedule_test! {
+		workload: ["cpu=10 priority=1", "cpu=2 arrival=3 priority=5"],
+		algorithm: priority(true),
+		expect: [P1@0..3, P2@3..5, P1@5..12],
+	}
+}
+
+#[test]
+fn preemptive_priority_levels_preempt_for_late_arrivals() {
+	schedule_test! {
+		workload: ["cpu=10 priority=1", "cpu=2 arrival=3 priority=5"],
+		algorithm: mlfq_levels("priority:preemptive"),
+		expect: [P1@0..3, P2@3..5, P1@5..12],
+	}
+	schedule_test! {
+		workload: ["cpu=10 priority=1", "cpu=2 arrival=3 priority=5"],
+		algorithm: mlfq_levels("priority:preemptive,fcfs"),
+		expect: [P1@0..3, P2@3..5, P1@5..12],
+	}
+}
+
+#[test]
+fn round_robin_alternates() {
+	schedule_test! {
+		workload: ["cpu=8", "cpu=8"],
+		algorithm: rr(4),
+		expect: [P1@0..4, P2@4..8, P1@8..12, P2@12..16],
+	}
+}
+
+#[test]
+fn round_robin_ends_short_bursts_early() {
+	schedule_test! {
+		workload: ["cpu=2", "cpu=6"],
+		algorithm: rr(4),
+		expect: [P1@0..2, P2@2..6, P2@6..8],
+	}
+}
+
+#[test]
+fn round_robin_donates_the_rest_of_a_quantum() {
+	schedule_test! {
+		workload: ["cpu=2", "cpu=6"],
+		algorithm: rr_donating(4),
+		expect: [P1@0..2, P2@2..4, P2@4..8],
+	}
+}
+
+#[test]
+fn round_robin_donations_lapse_when_the_cpu_idles() {
+	schedule_test! {
+		workload: ["cpu=2", "arrival=5 cpu=6"],
+		algorithm: rr_donating(4),
+		expect: [P1@0..2, P2@5..9, P2@9..11],
+	}
+}
+
+#[test]
+fn multi_level_feedback_queue_demotes_long_bursts() {
+	schedule_test! {
+		workload: ["cpu=20", "cpu=3"],
+		algorithm: mlfq,
+		expect: [P1@0..5, P2@5..8, P1@8..18, P1@18..23],
+	}
+}
+
+#[test]
+fn multi_level_feedback_queue_uses_the_given_levels() {
+	schedule_test! {
+		workload: ["cpu=6", "cpu=6"],
+		algorithm: mlfq_levels("rr:2,fcfs"),
+		expect: [P1@0..2, P2@2..4, P1@4..8, P2@8..12],
+	}
+}
+
+#[test]
+fn stride_shares_by_tickets() {
+	schedule_test! {
+		workload: ["cpu=4 tickets=200", "cpu=4 tickets=100"],
+		algorithm: stride(1),
+		expect: [P1@0..1, P2@1..2, P1@2..3, P1@3..4, P2@4..5, P1@5..6, P2@6..7, P2@7..8],
+	}
+}
+
+#[test]
+fn spawned_children_arrive_when_spawned() {
+	schedule_test! {
+		workload: ["cpu=2,2 io=3", "cpu=4 parent=1 spawn_at=1"],
+		algorithm: fcfs,
+		expect: [P1@0..2, P2@2..6, P1@6..8],
+	}
+}
+
+#[test]
+fn soft_real_time_processes_run_first() {
+	schedule_test! {
+		workload: ["cpu=10", "cpu=2 arrival=1 deadline=3"],
+		algorithm: soft_rt(4),
+		expect: [P1@0..4, P2@4..6, P1@6..10, P1@10..12],
+	}
+}
+
+#[test]
+fn soft_real_time_favors_the_process_closest_to_its_budget() {
+	// P2 misses its first deadline and so runs ahead of P1, whose deadline is earlier.
+	schedule_test! {
+		workload: ["cpu=3,1 io=0 deadline=8 tolerance=100", "cpu=5,1 io=0 deadline=4 tolerance=50"],
+		algorithm: soft_rt(10),
+		expect: [P2@0..5, P2@5..6, P1@6..9, P1@9..10],
+	}
+}
+
+#[test]
+fn earliest_deadline_first_preempts_for_earlier_deadlines() {
+	schedule_test! {
+		workload: ["cpu=6 deadline=20", "cpu=1,2 io=1 deadline=3"],
+		algorithm: edf,
+		expect: [P2@0..1, P1@1..2, P2@2..4, P1@4..9],
+	}
+}
+
+#[test]
+fn earliest_deadline_first_preempts_for_late_arrivals() {
+	schedule_test! {
+		workload: ["cpu=10 deadline=100", "cpu=2 arrival=3 deadline=3"],
+		algorithm: edf,
+		expect: [P1@0..3, P2@3..5, P1@5..12],
+	}
+}
+
+#[test]
+fn rate_monotonic_preempts_for_shorter_periods() {
+	schedule_test! {
+		workload: ["cpu=5,5 period=20", "cpu=1,1,1 period=3"],
+		algorithm: rm,
+		expect: [P2@0..1, P1@1..3, P2@3..4, P1@4..6, P2@6..7, P1@7..8, P1@20..25],
+	}
+}
+
+#[test]
+fn rate_monotonic_preempts_for_late_arrivals() {
+	schedule_test! {
+		workload: ["cpu=6,6 period=20", "cpu=1,1,1,1 period=5 arrival=2"],
+		algorithm: rm,
+		expect: [P1@0..2, P2@2..3, P1@3..7, P2@7..8, P2@12..13, P2@17..18, P1@20..26],
+	}
+}
+
+#[test]
+fn timeouts_lengthen_the_next_burst() {
+	// P2 waits 10 for its first burst, past its timeout, so its second burst doubles.
+	schedule_test! {
+		workload: ["cpu=10", "cpu=2,2 io=0 timeout=5"],
+		algorithm: fcfs,
+		expect: [P1@0..10, P2@10..12, P2@12..16],
+	}
+}
@@ -0,0 +1,64 @@
+#![cfg(all(feature = "std", feature = "serde"))]
+
+use mlfq_scheduler_simulator::{
+	algorithms::{FirstComeFirstServe, LevelPolicy, DEFAULT_LEVELS},
+	scheduler::Scheduler,
+	time::SimTime,
+	workload::{load_desktop_workload, Workload},
+};
+
+#[test]
+fn workloads_round_trip_through_json() {
+	let workload = load_desktop_workload();
+	let path = std::env::temp_dir().join("mlfq_round_trip_workload.json");
+
+	workload.save(&path).unwrap();
+	let loaded = Workload::load(&path).unwrap();
+	std::fs::remove_file(&path).unwrap();
+
+	assert_eq!(
+		serde_json::to_value(&loaded).unwrap(),
+		serde_json::to_value(&workload).unwrap()
+	);
+}
+
+#[test]
+fn level_policies_are_tagged() {
+	let json = serde_json::to_string(&DEFAULT_LEVELS).unwrap();
+
+	assert_eq!(
+		json,
+		r#"[{"policy":"round-robin","quanta":5},{"policy":"round-robin","quanta":10},{"policy":"first-come-first-serve"}]"#
+	);
+	assert_eq!(
+		serde_json::from_str::<Vec<LevelPolicy>>(&json).unwrap(),
+		DEFAULT_LEVELS
+	);
+}
+
+#[test]
+fn first_come_first_serve_queues_serialize_in_queue_order() {
+	let mut workload = Workload::new();
+
+	workload
+		.process([], [4])
+		.arrival_time(SimTime::new(3))
+		.add();
+	workload.process([], [2]).add();
+
+	let scheduler = FirstComeFirstServe::from_processes(workload.into_processes());
+	let json = serde_json::to_value(&scheduler).unwrap();
+	let ids: Vec<_> = json["processes"]
+		.as_array()
+		.unwrap()
+		.iter()
+		.map(|process| process["id"].as_u64().unwrap())
+		.collect();
+
+	assert_eq!(ids, [1, 2]);
+
+	let restored: FirstComeFirstServe = serde_json::from_value(json).unwrap();
+
+	assert_eq!(restored.get(1).unwrap().next_arrival, SimTime::new(3));
+	assert!(restored.is_ready(SimTime::ZERO));
+}
@@ -0,0 +1,88 @@
+#![cfg(feature = "std")]
+
+use std::{
+	io::{BufRead, BufReader},
+	net::TcpStream,
+	thread,
+	time::Duration,
+};
+
+use mlfq_scheduler_simulator::{
+	algorithms::MultiLevelFeedbackQueue, engine::Engine, serve::Server, workload::Workload,
+};
+use serde_json::Value;
+
+#[test]
+fn clients_receive_each_step_and_the_summary_as_json_lines() {
+	let mut server = Server::bind("127.0.0.1:0").unwrap();
+	let address = server.local_addr().unwrap();
+
+	let client = thread::spawn(move || {
+		let stream = TcpStream::connect(address).unwrap();
+
+		BufReader::new(stream)
+			.lines()
+			.map(|line| serde_json::from_str::<Value>(&line.unwrap()).unwrap())
+			.collect::<Vec<_>>()
+	});
+
+	let processes = Workload::parse("cpu=20\ncpu=3").unwrap().into_processes();
+	let mut engine = Engine::new(MultiLevelFeedbackQueue::from_processes(processes.into()));
+
+	server.wait_for_client().unwrap();
+	engine.run_with(&mut server);
+	server.finish(engine.summary());
+	drop(server);
+
+	let messages = client.join().unwrap();
+	let (summary, steps) = messages.split_last().unwrap();
+
+	let runs: Vec<_> = steps
+		.iter()
+		.map(|step| {
+			assert_eq!(step["type"], "step");
+			let time = |field: &str| step[field].as_u64().unwrap();
+
+			(time("process_id"), time("start"), time("end"))
+		})
+		.collect();
+
+	assert_eq!(runs, [(1, 0, 5), (2, 5, 8), (1, 8, 18), (1, 18, 23)]);
+	assert!(steps[0]["metrics"]["turnaround_average"].is_null());
+	assert_eq!(steps[1]["finished"]["turnaround_time"], 8);
+	assert_eq!(steps[1]["metrics"]["finished"], 1);
+	assert_eq!(steps[1]["metrics"]["turnaround_average"], 8.0);
+	assert_eq!(steps[1]["metrics"]["turnaround_p90"], 8.0);
+	assert_eq!(steps[3]["metrics"]["time"], 23);
+	assert_eq!(summary["type"], "summary");
+	assert_eq!(summary["summary"]["process_count"], 2);
+}
+
+#[test]
+fn clients_that_stop_reading_are_dropped() {
+	let mut server = Server::bind("127.0.0.1:0")
+		.unwrap()
+		.with_write_timeout(Duration::from_millis(100));
+	let address = server.local_addr().unwrap();
+
+	// The client connects but never reads, so the socket buffers fill up.
+	let client = TcpStream::connect(address).unwrap();
+
+	let processes = Workload::parse("cpu=20\ncpu=3").unwrap().into_processes();
+	let mut engine = Engine::new(MultiLevelFeedbackQueue::from_processes(processes.into()));
+
+	server.wait_for_client().unwrap();
+	engine.run_with(&mut server);
+
+	// Without the timeout, one of these writes would block for good.
+	for _ in 0..100_000 {
+		if server.client_count() == 0 {
+			break;
+		}
+
+		server.finish(engine.summary());
+	}
+
+	assert_eq!(server.client_count(), 0);
+	drop(client);
+}
@@ -0,0 +1,60 @@
+use std::thread;
+
+use mlfq_scheduler_simulator::{
+	algorithms::Lottery,
+	engine::Engine,
+	faults::FaultInjector,
+	scheduler::Scheduler,
+	time::SimTime,
+	trace::{Trace, TraceEvent, TraceRecorder},
+	workload::load_test_workload,
+};
+
+// Runs a session whose lottery draws and crashes both come from the seed.
+fn run_session(seed: u64) -> Trace {
+	let processes = load_test_workload().into_processes();
+	let mut engine = Engine::new(Lottery::from_processes(processes, SimTime::new(5), seed))
+		.with_faults(FaultInjector::new(0.05, seed))
+		.with_recorder(TraceRecorder::new());
+
+	engine.run();
+
+	engine.recorder().unwrap().trace()
+}
+
+#[test]
+fn engines_are_send() {
+	fn assert_send<T: Send>() {}
+
+	assert_send::<Engine<Lottery>>();
+	assert_send::<Engine<Box<dyn Scheduler>>>();
+}
+
+#[test]
+fn concurrent_sessions_do_not_interfere() {
+	let seeds = [1, 2, 3, 4, 5, 6, 7, 8];
+	let expected: Vec<Trace> = seeds.iter().map(|&seed| run_session(seed)).collect();
+
+	let sessions: Vec<_> = seeds
+		.iter()
+		.map(|&seed| thread::spawn(move || run_session(seed)))
+		.collect();
+
+	for (session, expected) in sessions.into_iter().zip(expected) {
+		let trace = session.join().unwrap();
+
+		// Every session numbers its own processes from P1.
+		assert_eq!(
+			trace
+				.records
+				.iter()
+				.filter_map(|record| match record.event {
+					TraceEvent::Dispatch { id } => Some(id),
+					_ => None,
+				})
+				.min(),
+			Some(1)
+		);
+		assert_eq!(trace, expected);
+	}
+}
@@ -0,0 +1,76 @@
+use mlfq_scheduler_simulator::{
+	engine::{Engine, Step},
+	process::Process,
+	scheduler::{Event, Scheduler},
+	sink::{CollectingSink, EventSink, NullSink},
+	testing,
+	time::SimTime,
+	workload::Workload,
+};
+
+fn engine<S: Scheduler>(lines: &[&str], algorithm: impl FnOnce(Vec<Process>) -> S) -> Engine<S> {
+	let workload = Workload::parse(&lines.join("\n")).unwrap();
+
+	Engine::new(algorithm(workload.into_processes()))
+}
+
+#[test]
+fn collected_events_follow_the_run() {
+	let mut engine = engine(&["cpu=20", "cpu=3"], testing::mlfq());
+	let mut sink = CollectingSink::new();
+
+	engine.run_with(&mut sink);
+
+	assert_eq!(
+		sink.events[..3],
+		[
+			Event::Start {
+				id: 1,
+				time: SimTime::ZERO
+			},
+			Event::Downgrade { id: 1, level: 2 },
+			Event::Start {
+				id: 2,
+				time: SimTime::new(5)
+			},
+		]
+	);
+	assert_eq!(sink.finished, [(2, SimTime::new(8)), (1, SimTime::new(23))]);
+}
+
+#[test]
+fn sinks_do_not_change_the_run() {
+	let mut silent = engine(&["cpu=20", "cpu=3", "cpu=7 arrival=4"], testing::mlfq());
+	let mut collected = engine(&["cpu=20", "cpu=3", "cpu=7 arrival=4"], testing::mlfq());
+
+	silent.run_with(&mut NullSink);
+	collected.run_with(&mut CollectingSink::new());
+
+	assert_eq!(silent.summary().total_time, collected.summary().total_time);
+	assert_eq!(
+		silent.summary().turnaround_average(),
+		collected.summary().turnaround_average()
+	);
+}
+
+#[test]
+fn sinks_see_the_engine_before_each_step() {
+	/// Records the time of the engine before each step.
+	#[derive(Default)]
+	struct Times(Vec<SimTime>);
+
+	impl EventSink for Times {
+		fn before_step<S: Scheduler>(&mut self, engine: &Engine<S>) {
+			self.0.push(engine.current_time());
+		}
+
+		fn on_step<S: Scheduler>(&mut self, _engine: &Engine<S>, _step: &Step) {}
+	}
+
+	let mut engine = engine(&["cpu=4", "cpu=4"], testing::fcfs());
+	let mut times = Times::default();
+
+	engine.run_with(&mut times);
+
+	assert_eq!(times.0, [SimTime::ZERO, SimTime::new(4)]);
+}
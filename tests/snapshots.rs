@@ -0,0 +1,76 @@
+#![cfg(all(feature = "std", feature = "serde"))]
+
+use std::collections::VecDeque;
+
+use mlfq_scheduler_simulator::{
+	algorithms::MultiLevelFeedbackQueue,
+	devices::{IoPolicy, IoSubsystem},
+	engine::{Engine, Snapshot},
+	faults::FaultInjector,
+	time::SimTime,
+	trace::{Trace, TraceRecorder},
+	workload::load_test_workload,
+};
+
+// Creates an engine whose run depends on boosting, devices, and crashes.
+fn engine() -> Engine<MultiLevelFeedbackQueue> {
+	let processes: VecDeque<_> = load_test_workload().into_processes().into();
+	let scheduler =
+		MultiLevelFeedbackQueue::from_processes(processes).with_boost_interval(SimTime::new(100));
+
+	Engine::new(scheduler)
+		.with_io(IoSubsystem::new(2, IoPolicy::ShortestSeekTimeFirst).with_seed(3))
+		.with_faults(FaultInjector::new(0.02, 3))
+		.with_recorder(TraceRecorder::new())
+}
+
+// Runs the engine to completion, returning its trace and summary.
+fn finish(mut engine: Engine<MultiLevelFeedbackQueue>) -> (Trace, serde_json::Value) {
+	engine.run();
+
+	(
+		engine.recorder().unwrap().trace(),
+		serde_json::to_value(engine.summary()).unwrap(),
+	)
+}
+
+#[test]
+fn restored_runs_continue_where_the_snapshot_was_taken() {
+	let expected = finish(engine());
+
+	let mut engine = engine();
+
+	for _ in 0..40 {
+		engine.step();
+	}
+
+	let path = std::env::temp_dir().join("mlfq_snapshot.json");
+
+	engine.snapshot().save(&path).unwrap();
+	let snapshot = Snapshot::load(&path).unwrap();
+	std::fs::remove_file(&path).unwrap();
+
+	assert_eq!(snapshot.current_time(), engine.current_time());
+	assert_eq!(finish(Engine::restore(snapshot)), expected);
+	assert_eq!(finish(engine), expected);
+}
+
+#[test]
+fn snapshots_branch_into_independent_runs() {
+	let mut engine = engine();
+
+	for _ in 0..40 {
+		engine.step();
+	}
+
+	let snapshot = engine.snapshot();
+	let mut branch = Engine::restore(snapshot.clone());
+
+	for _ in 0..10 {
+		branch.step();
+	}
+
+	assert!(branch.current_time() > snapshot.current_time());
+	assert_eq!(finish(branch), finish(Engine::restore(snapshot.clone())));
+	assert_eq!(engine.current_time(), snapshot.current_time());
+}
@@ -0,0 +1,134 @@
+use mlfq_scheduler_simulator::{
+	engine::Engine,
+	rng::Rng,
+	stats::{Distribution, OnlineStats, Quantile},
+	testing,
+	workload::Workload,
+};
+
+fn samples(count: usize) -> Vec<f64> {
+	let mut rng = Rng::new(7);
+
+	(0..count).map(|_| rng.next_f64() * 100.0).collect()
+}
+
+// Returns the sample at the quantile of the sorted samples.
+fn exact(samples: &[f64], probability: f64) -> f64 {
+	let mut sorted = samples.to_vec();
+
+	sorted.sort_by(f64::total_cmp);
+	sorted[((sorted.len() as f64 * probability).ceil() as usize).max(1) - 1]
+}
+
+#[test]
+fn online_stats_match_the_buffered_formulas() {
+	let samples = samples(1000);
+	let mut stats = OnlineStats::new();
+
+	for &sample in &samples {
+		stats.add(sample);
+	}
+
+	let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+	let variance = samples
+		.iter()
+		.map(|sample| (sample - mean).powi(2))
+		.sum::<f64>()
+		/ samples.len() as f64;
+
+	assert_eq!(stats.count(), 1000);
+	assert!((stats.mean() - mean).abs() < 1e-9);
+	assert!((stats.variance() - variance).abs() < 1e-6);
+	#[cfg(feature = "std")]
+	assert!((stats.std_dev() - variance.sqrt()).abs() < 1e-6);
+	assert_eq!(stats.min(), samples.iter().copied().reduce(f64::min));
+	assert_eq!(stats.max(), samples.iter().copied().reduce(f64::max));
+
+	// Merging the halves gives the same as adding every sample.
+	let (mut first, mut second) = (OnlineStats::new(), OnlineStats::new());
+
+	samples[..300].iter().for_each(|&sample| first.add(sample));
+	samples[300..].iter().for_each(|&sample| second.add(sample));
+	first.merge(&second);
+
+	assert_eq!(first.count(), stats.count());
+	assert!((first.mean() - stats.mean()).abs() < 1e-9);
+	assert!((first.variance() - stats.variance()).abs() < 1e-6);
+}
+
+#[test]
+fn empty_stats_have_no_values() {
+	let stats = OnlineStats::new();
+
+	assert!(stats.mean().is_nan());
+	assert!(stats.variance().is_nan());
+	assert_eq!(stats.min(), None);
+	assert!(Quantile::new(0.5).estimate().is_nan());
+}
+
+#[test]
+fn quantiles_are_exact_below_five_samples() {
+	let mut median = Quantile::new(0.5);
+
+	for sample in [9.0, 1.0, 5.0, 3.0] {
+		median.add(sample);
+	}
+
+	assert_eq!(median.estimate(), 3.0);
+}
+
+#[test]
+fn quantiles_converge_without_keeping_samples() {
+	let samples = samples(10_000);
+	let mut distribution = Distribution::new();
+
+	for &sample in &samples {
+		distribution.add(sample);
+	}
+
+	// The samples are uniform over 0 to 100, so each estimate is within a point of the exact quantile.
+	assert!((distribution.median() - exact(&samples, 0.5)).abs() < 1.0);
+	assert!((distribution.p90() - exact(&samples, 0.9)).abs() < 1.0);
+	assert!((distribution.p99() - exact(&samples, 0.99)).abs() < 1.0);
+}
+
+#[test]
+fn summaries_keep_the_distribution_of_each_metric() {
+	let workload = Workload::parse("cpu=4\ncpu=4\ncpu=4\ncpu=4\ncpu=4").unwrap();
+	let mut engine = Engine::new(testing::fcfs()(workload.into_processes()));
+
+	engine.run();
+
+	// The processes finish every 4, having waited 0, 4, 8, 12, and 16.
+	let summary = engine.summary();
+
+	assert_eq!(summary.waiting.count(), 5);
+	assert_eq!(summary.waiting.mean(), summary.waiting_average());
+	assert_eq!(summary.waiting.median(), 8.0);
+	assert_eq!(summary.turnaround.stats.max(), Some(20.0));
+	assert_eq!(summary.waiting.stats.variance(), 32.0);
+}
+
+#[test]
+fn classes_and_phases_keep_their_statistics() {
+	let summary = testing::summary(
+		&[
+			"cpu=4,2 io=1 class=batch tags=startup,steady",
+			"cpu=4 class=batch tags=startup",
+			"cpu=6 class=shell",
+		],
+		testing::fcfs(),
+	);
+
+	let batch = summary.class("batch").unwrap();
+
+	// P1's second burst waits for P3 until 14, so it finishes at 16 having waited 9, and P2 at 8 having waited 4.
+	assert_eq!(batch.turnaround.count(), 2);
+	assert_eq!(batch.turnaround_average(), 12.0);
+	assert_eq!(batch.waiting.stats.max(), Some(9.0));
+
+	let startup = summary.phase("startup").unwrap();
+
+	assert_eq!(startup.waiting.count(), 2);
+	assert_eq!(startup.waiting_average(), 2.0);
+}
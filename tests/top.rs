@@ -0,0 +1,109 @@
+use mlfq_scheduler_simulator::{
+	engine::Engine,
+	process::Process,
+	scheduler::Scheduler,
+	testing,
+	time::SimTime,
+	top::{Row, State, Top},
+	workload::Workload,
+};
+
+fn engine<S: Scheduler>(
+	lines: &[&str],
+	algorithm: impl FnOnce(Vec<Process>) -> S,
+	cpus: usize,
+) -> Engine<S> {
+	let workload = Workload::parse(&lines.join("\n")).unwrap();
+
+	Engine::new(algorithm(workload.into_processes())).with_cpus(cpus)
+}
+
+fn row(id: u32, state: State, level: u32, cpu_time: u64, recent_share: f64) -> Row {
+	Row {
+		id,
+		state,
+		level,
+		cpu_time: SimTime::new(cpu_time),
+		recent_share,
+	}
+}
+
+#[test]
+fn shares_cover_the_window_so_far() {
+	let mut engine = engine(&["cpu=4", "cpu=4"], testing::fcfs(), 1);
+	let mut top = Top::new(SimTime::new(100));
+
+	top.observe(&engine.step());
+
+	assert_eq!(
+		top.rows(&engine),
+		[
+			row(1, State::Finished, 1, 4, 1.0),
+			row(2, State::Ready, 1, 0, 0.0)
+		]
+	);
+
+	top.observe(&engine.step());
+
+	assert_eq!(
+		top.rows(&engine),
+		[
+			row(1, State::Finished, 1, 4, 0.5),
+			row(2, State::Finished, 1, 4, 0.5)
+		]
+	);
+}
+
+#[test]
+fn runs_past_the_current_time_have_not_happened_yet() {
+	let mut engine = engine(&["cpu=4", "cpu=8"], testing::fcfs(), 2);
+	let mut top = Top::new(SimTime::new(100));
+
+	top.observe(&engine.step());
+
+	assert_eq!(
+		top.rows(&engine),
+		[
+			row(1, State::Running, 1, 0, 0.0),
+			row(2, State::Ready, 1, 0, 0.0)
+		]
+	);
+
+	top.observe(&engine.step());
+
+	assert_eq!(
+		top.rows(&engine),
+		[
+			row(1, State::Finished, 1, 4, 1.0),
+			row(2, State::Running, 1, 4, 1.0)
+		]
+	);
+}
+
+#[test]
+fn old_runs_leave_the_window() {
+	let mut engine = engine(&["cpu=4", "cpu=8"], testing::fcfs(), 1);
+	let mut top = Top::new(SimTime::new(6));
+
+	top.observe(&engine.step());
+	top.observe(&engine.step());
+
+	// Over 6..12, P1 has not run at all.
+	assert_eq!(
+		top.rows(&engine),
+		[
+			row(2, State::Finished, 1, 8, 1.0),
+			row(1, State::Finished, 1, 4, 0.0)
+		]
+	);
+}
+
+#[test]
+fn demotions_move_processes_down_a_level() {
+	let mut engine = engine(&["cpu=20"], testing::mlfq(), 1);
+	let mut top = Top::new(SimTime::new(100));
+
+	top.observe(&engine.step());
+
+	assert_eq!(top.rows(&engine), [row(1, State::Ready, 2, 5, 1.0)]);
+}
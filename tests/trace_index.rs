@@ -0,0 +1,116 @@
+use mlfq_scheduler_simulator::{
+	diff::first_divergence,
+	testing,
+	time::SimTime,
+	trace::{Slice, TraceEvent, TraceIndex},
+};
+
+fn slice(cpu: usize, id: u32, start: u64, end: u64) -> Slice {
+	Slice {
+		cpu,
+		id,
+		start: SimTime::new(start),
+		end: SimTime::new(end),
+	}
+}
+
+#[test]
+fn finds_what_each_cpu_was_running() {
+	// P1 runs until 10 on the first CPU, and P2 and P3 take turns on the second.
+	let trace = testing::record(
+		&["cpu=10", "cpu=3", "cpu=3", "cpu=2 arrival=20"],
+		testing::fcfs(),
+		2,
+	);
+	let index = TraceIndex::new(&trace);
+
+	assert_eq!(
+		index.running_at(SimTime::new(4)),
+		[slice(0, 1, 0, 10), slice(1, 3, 3, 6)]
+	);
+	// A slice ends at its end, so the second CPU is idle from 6.
+	assert_eq!(index.running_at(SimTime::new(6)), [slice(0, 1, 0, 10)]);
+	assert!(index.running_at(SimTime::new(15)).is_empty());
+	assert_eq!(index.running_at(SimTime::new(21)), [slice(1, 4, 20, 22)]);
+}
+
+#[test]
+fn queries_the_events_of_a_process_in_a_range() {
+	let trace = testing::record(&["cpu=4,4 io=6", "cpu=20"], testing::rr(5), 1);
+	let index = TraceIndex::new(&trace);
+
+	let events: Vec<(u64, TraceEvent)> = index
+		.process_events(1, SimTime::new(4)..SimTime::new(20))
+		.into_iter()
+		.map(|record| (record.time.as_u64(), record.event))
+		.collect();
+
+	assert_eq!(
+		events,
+		[
+			(
+				4,
+				TraceEvent::IoStart {
+					id: 1,
+					length: SimTime::new(6)
+				}
+			),
+			(10, TraceEvent::IoComplete { id: 1 }),
+			(10, TraceEvent::Dispatch { id: 1 }),
+			(
+				14,
+				TraceEvent::Finish {
+					id: 1,
+					turnaround_time: SimTime::new(14),
+					waiting_time: SimTime::ZERO,
+					response_time: SimTime::ZERO,
+				}
+			),
+		]
+	);
+	assert_eq!(
+		index.process_slices(1),
+		[slice(0, 1, 0, 4), slice(0, 1, 10, 14)]
+	);
+	assert!(index
+		.process_events(3, SimTime::ZERO..SimTime::MAX)
+		.is_empty());
+
+	// A time range covers every process, up to but not including its end.
+	let between: Vec<(u64, TraceEvent)> = index
+		.between(SimTime::new(4)..SimTime::new(10))
+		.iter()
+		.map(|record| (record.time.as_u64(), record.event))
+		.collect();
+
+	assert_eq!(
+		between,
+		[
+			(
+				4,
+				TraceEvent::IoStart {
+					id: 1,
+					length: SimTime::new(6)
+				}
+			),
+			(4, TraceEvent::Dispatch { id: 2 }),
+			(9, TraceEvent::Preempt { id: 2 }),
+		]
+	);
+}
+
+#[test]
+fn finds_where_two_schedules_first_differ() {
+	// Both run P1 until 8, then FCFS runs P2 while SJF runs the shorter P3.
+	let workload = ["cpu=8", "cpu=5 arrival=1", "cpu=2 arrival=2"];
+	let fcfs = testing::record(&workload, testing::fcfs(), 1);
+	let sjf = testing::record(&workload, testing::sjf(), 1);
+	let (fcfs, sjf) = (TraceIndex::new(&fcfs), TraceIndex::new(&sjf));
+
+	let divergence = first_divergence(&fcfs, &sjf).unwrap();
+
+	assert_eq!(divergence.time, SimTime::new(8));
+	assert_eq!(divergence.before, [slice(0, 2, 8, 13)]);
+	assert_eq!(divergence.after, [slice(0, 3, 8, 10)]);
+	assert_eq!(first_divergence(&fcfs, &fcfs), None);
+}
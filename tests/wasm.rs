@@ -0,0 +1,93 @@
+#![cfg(feature = "wasm")]
+
+use mlfq_scheduler_simulator::wasm::Simulation;
+use serde_json::Value;
+
+fn steps(simulation: &mut Simulation) -> Vec<Value> {
+	let mut steps = Vec::new();
+
+	loop {
+		let step: Value = serde_json::from_str(&simulation.step_json()).unwrap();
+
+		if step.is_null() {
+			return steps;
+		}
+
+		steps.push(step);
+	}
+}
+
+#[test]
+fn steps_are_returned_as_json_until_the_run_is_over() {
+	let config = r#"{ "workload": "cpu=20\ncpu=3" }"#;
+	let mut simulation = Simulation::from_config(config).unwrap();
+
+	let steps = steps(&mut simulation);
+	let runs: Vec<_> = steps
+		.iter()
+		.map(|step| {
+			let time = |field: &str| step[field].as_u64().unwrap();
+
+			(time("process_id"), time("start"), time("end"))
+		})
+		.collect();
+
+	assert_eq!(runs, [(1, 0, 5), (2, 5, 8), (1, 8, 18), (1, 18, 23)]);
+	assert_eq!(steps[0]["events"][1]["event"], "downgrade");
+	assert_eq!(steps[1]["finished"]["turnaround_time"], 8);
+	assert_eq!(steps[3]["is_finished"], true);
+	assert!(simulation.is_finished());
+
+	let summary: Value = serde_json::from_str(&simulation.summary_json()).unwrap();
+
+	assert_eq!(summary["process_count"], 2);
+}
+
+#[test]
+fn levels_select_the_algorithm() {
+	let config = r#"{
+		"workload": "cpu=8\ncpu=8",
+		"levels": [{ "policy": "round-robin", "quanta": 4 }]
+	}"#;
+	let mut simulation = Simulation::from_config(config).unwrap();
+
+	let starts: Vec<_> = steps(&mut simulation)
+		.iter()
+		.map(|step| step["start"].as_u64().unwrap())
+		.collect();
+
+	assert_eq!(starts, [0, 4, 8, 12]);
+}
+
+#[test]
+fn invalid_configurations_are_rejected() {
+	assert!(Simulation::from_config(r#"{ "workload": "cpu=x" }"#).is_err());
+	assert!(Simulation::from_config(r#"{ "workload": "cpu=1", "levels": [] }"#).is_err());
+	assert!(Simulation::from_config(r#"{ "workload": "cpu=1", "quanta": 5 }"#).is_err());
+}
+
+#[test]
+fn configurations_that_never_progress_are_rejected() {
+	let levels = [
+		r#"{ "policy": "round-robin", "quanta": 0 }"#,
+		r#"{ "policy": "lottery", "quanta": 0, "seed": 1 }"#,
+		r#"{ "policy": "stride", "quanta": 0 }"#,
+	];
+
+	for level in levels {
+		let config = format!(r#"{{ "workload": "cpu=1", "levels": [{level}] }}"#);
+		let error = Simulation::from_config(&config).err();
+
+		assert_eq!(
+			error.as_deref(),
+			Some("invalid configuration: level 1 has a zero quantum")
+		);
+	}
+
+	let error = Simulation::from_config(r#"{ "workload": "cpu=1", "boost_interval": 0 }"#).err();
+
+	assert_eq!(
+		error.as_deref(),
+		Some("invalid configuration: the boost interval must not be zero")
+	);
+}
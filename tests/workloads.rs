@@ -0,0 +1,249 @@
+#![cfg(all(feature = "std", feature = "serde"))]
+
+use mlfq_scheduler_simulator::{
+	engine::Engine, process::IoPriority, testing, time::SimTime, workload::Workload,
+};
+
+// Builds a workload through the builder API, the way a host program would.
+fn build(workload: &mut Workload) {
+	let shell = workload
+		.process([4, 6], [1, 2, 1])
+		.class("shell")
+		.think_time(SimTime::new(40))
+		.add();
+
+	workload
+		.process([], [20, 30])
+		.arrival_time(SimTime::new(3))
+		.priority(2)
+		.io_priority(IoPriority::RealTime)
+		.tickets(50)
+		.spawned_by(shell, 2)
+		.add();
+
+	// The builder pads the missing I/O time with zero.
+	workload
+		.process([], [5, 5])
+		.think_time(SimTime::new(2))
+		.add();
+}
+
+#[test]
+fn recordings_are_workload_files() {
+	let mut workload = Workload::recording();
+
+	build(&mut workload);
+
+	assert_eq!(
+		workload.recorded(),
+		Some(
+			"cpu=1,2,1 io=4,6 class=shell think=40\n\
+			 cpu=20,30 io=0 arrival=3 priority=2 io_priority=realtime tickets=50 parent=1 spawn_at=2\n\
+			 cpu=5,5 io=0 think=2\n"
+		)
+	);
+}
+
+#[test]
+fn recordings_replay_the_same_run() {
+	let mut workload = Workload::recording();
+
+	build(&mut workload);
+
+	let replayed = Workload::parse(workload.recorded().unwrap()).unwrap();
+
+	for (built, parsed) in workload.processes().iter().zip(replayed.processes()) {
+		assert_eq!(built.cpu_times, parsed.cpu_times);
+		assert_eq!(built.think_time, parsed.think_time);
+		assert_eq!(built.spawn, parsed.spawn);
+	}
+
+	let mut built = Engine::new(testing::mlfq()(workload.into_processes()));
+	let mut parsed = Engine::new(testing::mlfq()(replayed.into_processes()));
+
+	built.run();
+	parsed.run();
+
+	assert_eq!(built.summary().total_time, parsed.summary().total_time);
+	assert_eq!(
+		built.summary().waiting_average(),
+		parsed.summary().waiting_average()
+	);
+}
+
+#[test]
+fn recordings_round_trip_every_field() {
+	let mut workload = Workload::recording();
+
+	workload
+		.process([3], [4, 6])
+		.class("editor")
+		.burst_tags(["", "save"])
+		.accelerator_times([12])
+		.soft_deadline(SimTime::new(9), 20)
+		.retry(SimTime::new(15), 3)
+		.add();
+	workload.process([], [2, 2]).period(SimTime::new(10)).add();
+	workload
+		.process([5, 5], [1, 7, 1])
+		.accelerator_times([30])
+		.add();
+
+	let replayed = Workload::parse(workload.recorded().unwrap()).unwrap();
+
+	assert_eq!(replayed.len(), workload.len());
+
+	for (built, parsed) in workload.processes().iter().zip(replayed.processes()) {
+		assert_eq!(built.cpu_times, parsed.cpu_times);
+		assert_eq!(built.io_times, parsed.io_times);
+		assert_eq!(built.accelerator_times, parsed.accelerator_times);
+		assert_eq!(built.class, parsed.class);
+		assert_eq!(built.burst_tags, parsed.burst_tags);
+		assert_eq!(built.soft_deadline, parsed.soft_deadline);
+		assert_eq!(built.period, parsed.period);
+		assert_eq!(built.retry, parsed.retry);
+	}
+}
+
+// Records a process around the second one, returning why the recording stopped.
+fn recording_error(build: impl FnOnce(&mut Workload)) -> Option<String> {
+	let mut workload = Workload::recording();
+
+	workload.process([], [1]).add();
+	build(&mut workload);
+	// The recording stays stopped after a process that could be recorded.
+	workload.process([], [1]).add();
+
+	assert_eq!(workload.len(), 3);
+	assert_eq!(workload.recorded(), None);
+
+	workload.recording_error().map(str::to_string)
+}
+
+#[test]
+fn processes_the_file_cannot_describe_stop_the_recording() {
+	let error = recording_error(|workload| {
+		workload.process([], [5]).class("text editor").add();
+	});
+
+	assert_eq!(
+		error.as_deref(),
+		Some("P2 cannot be recorded: class must not contain whitespace or #")
+	);
+
+	let error = recording_error(|workload| {
+		workload.process([], [5, 5]).burst_tags(["a,b", ""]).add();
+	});
+
+	assert_eq!(
+		error.as_deref(),
+		Some("P2 cannot be recorded: tags must not contain whitespace, commas, or #")
+	);
+
+	let error = recording_error(|workload| {
+		workload.process([1, 2], [5, 5]).add();
+	});
+
+	assert_eq!(
+		error.as_deref(),
+		Some("P2 cannot be recorded: io has 2 times but cpu has 2 times")
+	);
+
+	let error = recording_error(|workload| {
+		workload.process([], [5]).retry(SimTime::new(4), 0).add();
+	});
+
+	assert_eq!(
+		error.as_deref(),
+		Some("P2 cannot be recorded: retry must be positive")
+	);
+}
+
+#[test]
+fn workloads_only_record_when_asked() {
+	let mut workload = Workload::new();
+
+	build(&mut workload);
+
+	assert_eq!(workload.recorded(), None);
+}
+
+#[test]
+fn parse_errors_point_at_the_field_at_fault() {
+	let error = Workload::parse("cpu=4\ncpu=5,3,5 io=27 # two bursts")
+		.err()
+		.unwrap();
+
+	assert_eq!((error.line, error.column, error.length), (2, 11, 5));
+	assert_eq!(error.message, "io has 1 time but cpu has 3 times");
+	assert_eq!(
+		error.render("mine.txt"),
+		"line 2: io has 1 time but cpu has 3 times\n \
+		 --> mine.txt:2:11\n  \
+		 |\n\
+		 2 | cpu=5,3,5 io=27 # two bursts\n  \
+		 |           ^^^^^\n  \
+		 |\n  \
+		 = help: expected io = cpu - 1, since an io time separates each pair of CPU bursts"
+	);
+}
+
+#[test]
+fn misspelled_fields_suggest_the_closest_one() {
+	let error = Workload::parse("cpu=5 arival=3").err().unwrap();
+
+	assert_eq!(error.message, "unknown field `arival`");
+	assert_eq!(error.column, 7);
+	assert_eq!(error.help.as_deref(), Some("did you mean `arrival`?"));
+
+	let error = Workload::parse("cpu=5 retry=3").err().unwrap();
+
+	assert_eq!(error.column, 7);
+	assert_eq!(
+		error.help.as_deref(),
+		Some("add how long a burst may wait, such as timeout=20")
+	);
+}
+
+#[test]
+fn json_workloads_fail_with_the_line_and_column() {
+	let path = std::env::temp_dir().join("mlfq_invalid_workload.json");
+
+	std::fs::write(&path, "{\n  \"processes\": [\n    { \"id\": x }\n  ]\n}\n").unwrap();
+
+	let error = Workload::load(&path).err().unwrap().to_string();
+
+	std::fs::remove_file(&path).unwrap();
+
+	assert!(error.starts_with("line 3: expected value\n"));
+	assert!(error.contains("3 |     { \"id\": x }\n  |             ^"));
+}
+
+#[test]
+fn json_workloads_are_checked_like_workload_files() {
+	let path = std::env::temp_dir().join("mlfq_unchecked_workload.json");
+	let mut workload = Workload::new();
+
+	build(&mut workload);
+	workload.save(&path).unwrap();
+
+	assert_eq!(Workload::load(&path).unwrap().len(), 3);
+
+	// Empty the CPU bursts of the second process, which would otherwise panic once it runs.
+	let mut json: serde_json::Value =
+		serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+
+	json["processes"][1]["cpu_times"] = serde_json::json!([]);
+	std::fs::write(&path, serde_json::to_string_pretty(&json).unwrap()).unwrap();
+
+	let error = Workload::load(&path).err().unwrap().to_string();
+
+	std::fs::remove_file(&path).unwrap();
+
+	assert!(error.starts_with("line "), "{error}");
+	assert!(
+		error.contains(": P2: cpu times must be positive\n"),
+		"{error}"
+	);
+	assert!(error.contains("\"id\": 2"), "{error}");
+}